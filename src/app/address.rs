@@ -3,16 +3,35 @@ use ratatui::text::{Line, Span, Text};
 use super::App;
 
 impl App {
+    pub(super) const ADDRESS_WIDTH: u16 = 17;
+
     pub(super) fn get_address_view(&self, start_row: usize, end_row: usize) -> Text<'static> {
         let mut ret = Text::default();
         ret.lines.reserve(end_row - start_row);
         let selected_row = self.get_cursor_position().line_index;
+        let bytes_per_row = self.block_size * self.blocks_per_row;
+        let pinned_row = self.pinned.map(|pinned| pinned / bytes_per_row);
+        let gutter = self.settings.app.change_gutter as usize;
+        let digits = self.get_address_width() as usize - 1 - gutter;
         for i in start_row..end_row {
             let mut line = Line::default();
+            if self.settings.app.change_gutter {
+                line.spans.push(Span::styled(
+                    if self.is_row_changed(i) { "~" } else { " " },
+                    self.settings.color.address_changed,
+                ));
+            }
+            let address = i * bytes_per_row;
             line.spans.push(Span::styled(
-                format!("{:16X}", i * self.block_size * self.blocks_per_row),
+                if self.settings.app.uppercase_hex {
+                    format!("{:digits$X}", address)
+                } else {
+                    format!("{:digits$x}", address)
+                },
                 if i == selected_row {
                     self.settings.color.address_selected
+                } else if pinned_row == Some(i) {
+                    self.settings.color.address_pinned
                 } else {
                     self.settings.color.address_default
                 },
@@ -21,4 +40,76 @@ impl App {
         }
         ret
     }
+
+    /// Returns the width of the address column, border and change gutter included.
+    /// In compact mode only the digits needed by the largest offset of the file are shown,
+    /// the full offset is always shown in the status bar.
+    pub(super) fn get_address_width(&self) -> u16 {
+        let gutter = self.settings.app.change_gutter as u16;
+        if self.settings.app.compact_address {
+            let digits = format!("{:X}", self.data.len().saturating_sub(1)).len();
+            // the title of the column must fit
+            digits.max(8) as u16 + 1 + gutter
+        } else {
+            Self::ADDRESS_WIDTH + gutter
+        }
+    }
+
+    /// Keeps a copy of the data to mark the rows changed since the file was opened,
+    /// nothing is kept if the `change_gutter` setting is off.
+    pub(super) fn keep_original_bytes(&mut self) {
+        self.original_bytes = self
+            .settings
+            .app
+            .change_gutter
+            .then(|| self.data.bytes().to_vec());
+    }
+
+    /// Returns true if a byte of `row` differs from the bytes the file had when it was opened,
+    /// the bytes shifted by an insertion count as changed.
+    /// No row is changed if the `change_gutter` setting was off when the file was opened.
+    pub(super) fn is_row_changed(&self, row: usize) -> bool {
+        let Some(original_bytes) = &self.original_bytes else {
+            return false;
+        };
+        let bytes_per_row = self.block_size * self.blocks_per_row;
+        let start = row * bytes_per_row;
+        let end = start + bytes_per_row;
+        // the range of the row clamped to the length of the bytes
+        let row_range = |len: usize| start.min(len)..end.min(len);
+        let bytes = self.data.bytes();
+        bytes[row_range(bytes.len())] != original_bytes[row_range(original_bytes.len())]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_change_gutter() {
+        let mut app = App::mockup((0..=0xFF).collect());
+        app.settings.app.change_gutter = true;
+        // the setting was off when the file was opened, there is nothing to compare with
+        app.data.push_change(0, vec![1]);
+        assert!(!app.is_row_changed(0));
+        app.data.push_change(0, vec![0]);
+        app.keep_original_bytes();
+        let bytes_per_row = app.block_size * app.blocks_per_row;
+        app.data.push_change(bytes_per_row + 1, vec![0]);
+        assert!(!app.is_row_changed(0));
+        assert!(app.is_row_changed(1));
+        let address_view = app.get_address_view(0, 2);
+        assert_eq!(address_view.lines[0].spans[0].content, " ");
+        assert_eq!(address_view.lines[1].spans[0].content, "~");
+        assert_eq!(
+            address_view.lines[1].spans[1].content.len(),
+            app.get_address_width() as usize - 2
+        );
+
+        app.data.push_change(bytes_per_row + 1, vec![bytes_per_row as u8 + 1]);
+        assert!(!app.is_row_changed(1));
+        app.data.insert(0, vec![1]);
+        assert!(app.is_row_changed(1));
+    }
 }
@@ -1,44 +1,102 @@
 #![allow(clippy::module_inception)]
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crossterm::event;
 use ratatui::{
     backend::Backend,
+    buffer::Buffer,
     layout::Rect,
-    text::{Line, Text},
-    widgets::{Block, Borders, Clear, ScrollbarOrientation, ScrollbarState},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph, ScrollbarOrientation, ScrollbarState, Widget},
 };
 use termbg::Theme;
 
 use super::{
-    asm::assembly_line::AssemblyLine,
+    asm::{assembly_line::AssemblyLine, fold::Fold},
+    color_tags::ColorTag,
     data::Data,
-    files::filesystem::FileSystem,
+    fields::Field,
+    files::{
+        filesystem::{FileSystem, PendingRead},
+        path,
+        recent_dirs::RecentDirs,
+        remembered_files::RememberedFiles,
+        OpeningFile,
+    },
+    focus::Focus,
+    follow::FileStamp,
     frame_info::{FrameInfo, InfoViewFrameInfo},
     help::HelpLine,
     info_mode::InfoMode,
     log::{logger::Logger, NotificationLevel},
+    pane::SecondPane,
     plugins::plugin_manager::PluginManager,
     popup::popup_state::PopupState,
+    profiles::Profiles,
+    search::SearchQuery,
+    session::Session,
     settings::{color_settings::ColorSettings, Settings},
+    text_encoding::TextEncoding,
     widgets::logo::Logo,
 };
 
-use crate::{args::Args, get_app_context, headers::Header};
+use crate::{
+    args::Args,
+    get_app_context,
+    headers::{arch_override::ArchOverride, Header},
+};
 
 pub struct App {
     pub(super) plugin_manager: PluginManager,
     pub(super) filesystem: FileSystem,
     pub(super) header: Header,
+    pub(super) arch_override: Option<ArchOverride>,
+    pub(super) file_arch_override: Option<ArchOverride>,
+    pub(super) file_key: Option<String>,
+    pub(super) remembered_files_path: Option<PathBuf>,
+    pub(super) profiles_path: Option<PathBuf>,
+    pub(super) session_path: Option<PathBuf>,
     pub(super) logger: Logger,
     pub(super) help_list: Vec<HelpLine>,
     pub(super) data: Data,
     pub(super) assembly_offsets: Vec<usize>,
     pub(super) assembly_instructions: Vec<AssemblyLine>,
+    pub(super) assembly_folds: Vec<Fold>,
     pub(super) text_last_searched_string: String,
+    pub(super) text_last_searched_encoding: TextEncoding,
+    pub(super) hex_last_searched_string: String,
+    pub(super) last_search: Option<SearchQuery>,
     pub(super) info_mode: InfoMode,
+    pub(super) previous_info_mode: Option<InfoMode>,
+    pub(super) focus: Focus,
     pub(super) scroll: usize,
     pub(super) cursor: (u16, u16),
+    pub(super) selection_anchor: Option<usize>,
+    /// True if the selection was started by moving with Shift.
+    pub(super) shift_selection: bool,
+    pub(super) highlight_equal_bytes: bool,
+    pub(super) pinned: Option<usize>,
+    pub(super) mark: Option<usize>,
+    pub(super) compare_range: Option<(usize, usize)>,
+    pub(super) selection_differences: Vec<usize>,
+    /// The bytes of the file when it was opened, kept only if the `change_gutter` setting is on.
+    pub(super) original_bytes: Option<Vec<u8>>,
+    pub(super) follow: Option<FileStamp>,
+    /// The read of a file that timed out, waited for again if the user tries again.
+    pub(super) pending_read: Option<PendingRead>,
+    pub(super) opening_file: Option<OpeningFile>,
+    /// A plugin command chosen in the run popup, it runs once the popup is gone from the screen.
+    pub(super) pending_plugin_command: Option<String>,
+    pub(super) disassembly_base: usize,
+    /// The open file is not disassembled, chosen when a large file is opened.
+    pub(super) skip_disassembly: bool,
+    pub(super) pane: Option<SecondPane>,
+    pub(super) fields: Vec<Field>,
+    pub(super) color_tags: Vec<ColorTag>,
+    pub(super) recent_dirs: RecentDirs,
+    pub(super) recent_offsets: Vec<usize>,
+    pub(super) recent_offset_index: usize,
     pub(super) poll_time: Duration,
     pub(super) needs_to_exit: bool,
     pub(super) screen_size: (u16, u16),
@@ -90,6 +148,38 @@ impl App {
         Ok(())
     }
 
+    /// Prints the status in place of the status bar, over what is already on the screen.
+    /// Only the last row is written, the terminal must be cleared before the next frame.
+    pub(super) fn print_status_over<B: Backend>(
+        color_settings: &ColorSettings,
+        status: &str,
+        terminal: &mut ratatui::Terminal<B>,
+    ) -> Result<(), String> {
+        let size = terminal.size().map_err(|e| e.to_string())?;
+        let status_rect = Rect::new(
+            0,
+            size.height.saturating_sub(1),
+            size.width,
+            size.height.min(1),
+        );
+        let mut buffer = Buffer::empty(status_rect);
+        let status_line = Line::from(vec![
+            Span::styled(" ", color_settings.status_bar),
+            Span::styled("●", color_settings.status_info),
+            Span::styled(" ", color_settings.status_bar),
+            Span::styled(status.to_string(), color_settings.status_bar),
+        ])
+        .style(color_settings.status_bar);
+        Paragraph::new(status_line).render(status_rect, &mut buffer);
+        let cells = buffer.content.iter().enumerate().map(|(i, cell)| {
+            let (x, y) = buffer.pos_of(i);
+            (x, y, cell)
+        });
+        let backend = terminal.backend_mut();
+        backend.draw(cells).map_err(|e| e.to_string())?;
+        backend.flush().map_err(|e| e.to_string())
+    }
+
     pub(super) fn get_size<B: Backend>(
         terminal: &mut ratatui::Terminal<B>,
     ) -> Result<(u16, u16), String> {
@@ -145,6 +235,11 @@ impl App {
             filesystem,
             screen_size,
             help_list: Self::help_list(&settings.key),
+            arch_override: args.arch_override()?,
+            recent_dirs: RecentDirs::load(None),
+            remembered_files_path: RememberedFiles::get_default_path(),
+            profiles_path: Profiles::get_default_path(),
+            session_path: Session::get_default_session_path(),
             settings,
             logger,
             ..Default::default()
@@ -161,56 +256,206 @@ impl App {
                 PluginManager::default()
             }
         };
+        app.check_command_aliases();
+
+        let session = if args.restore {
+            match Session::load(None) {
+                Ok(session) => Some(session),
+                Err(e) => {
+                    app.log(
+                        NotificationLevel::Warning,
+                        &format!("Could not load the last session: {e}"),
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
-        if app.filesystem.is_file(app.filesystem.pwd()) {
-            let path = app.filesystem.pwd().to_string();
-            app.open_file(&path, terminal).map_err(|e| e.to_string())?;
+        if let Some(session) = session {
+            app.restore_session(session, terminal)
+                .map_err(|e| e.to_string())?;
         } else {
-            let dir = app.filesystem.pwd().to_string();
-            Self::open_dir(&mut app.popup, &dir, &mut app.filesystem).map_err(|e| e.to_string())?;
+            app.open_start_path(terminal).map_err(|e| e.to_string())?;
         }
 
         Ok(app)
     }
 
+    /// The scrollbar is measured in rows so that the thumb is as long as the visible part of the file,
+    /// its position is the row of the byte under the cursor.
+    fn get_scrollbar_state(&self) -> ScrollbarState {
+        let bytes_per_row = (self.blocks_per_row * self.block_size).max(1);
+        let total_rows = self.data.len().div_ceil(bytes_per_row);
+        let visible_rows = self.screen_size.1.saturating_sub(self.vertical_margin) as usize;
+        ScrollbarState::new(total_rows)
+            .viewport_content_length(visible_rows)
+            .position(self.get_cursor_position().global_byte_index / bytes_per_row)
+    }
+
+    /// Builds the info view shown in `rect`, the rows of the text view are aligned with the hex view.
+    fn get_info_view_block(
+        &self,
+        info_mode: InfoMode,
+        rect: Rect,
+        focused: bool,
+    ) -> (Paragraph<'_>, InfoViewFrameInfo) {
+        match info_mode {
+            InfoMode::Text => {
+                let line_start_index = self.scroll + rect.y as usize;
+                let line_end_index = self.scroll + (rect.y + rect.height).saturating_sub(1) as usize;
+                let text_subview = self.get_text_view(line_start_index, line_end_index);
+                (
+                    Paragraph::new(text_subview).block(
+                        Block::default()
+                            .title("Text View")
+                            .borders(Borders::TOP | Borders::RIGHT)
+                            .border_style(if focused {
+                                self.settings.color.focused_border
+                            } else {
+                                Style::default()
+                            }),
+                    ),
+                    InfoViewFrameInfo::TextView,
+                )
+            }
+            InfoMode::Assembly => {
+                let visible_lines = rect.height.saturating_sub(1) as usize;
+                let assembly_start_index = self.get_assembly_view_scroll(visible_lines);
+                let folded_lines = self.get_folded_lines();
+                let assembly_end_index = (assembly_start_index + visible_lines)
+                    .min(self.get_assembly_view_rows(&folded_lines));
+                let mut assembly_subview = Text::default();
+                let address_format = self.get_assembly_address_format();
+                assembly_subview
+                    .lines
+                    .extend((assembly_start_index..assembly_end_index).map(|row| {
+                        match Self::assembly_row_to_line(row, &folded_lines) {
+                            (first, Some(last)) => {
+                                self.folded_lines_to_line(first, last, address_format)
+                            }
+                            (index, None) => self.assembly_instructions[index].to_line(
+                                &self.settings.color,
+                                self.get_cursor_position().global_byte_index,
+                                &self.header,
+                                address_format,
+                            ),
+                        }
+                    }));
+                let title = if self.disassembly_base == 0 {
+                    "Assembly View".to_string()
+                } else {
+                    format!("Assembly View @ {:X}", self.disassembly_base)
+                };
+                (
+                    Paragraph::new(assembly_subview).block(
+                        Block::default()
+                            .title(title)
+                            .borders(Borders::TOP | Borders::RIGHT),
+                    ),
+                    InfoViewFrameInfo::AssemblyView {
+                        scroll: assembly_start_index,
+                    },
+                )
+            }
+        }
+    }
+
     pub fn draw<B: Backend>(
         &mut self,
         terminal: &mut ratatui::Terminal<B>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         terminal.draw(|f| {
-            let min_width = self.block_size as u16 * 3 + 17 + 3;
+            let address_width = self.get_address_width();
+            let min_width = self.block_size as u16 * 3 + address_width + 3;
             if f.area().width < min_width {
                 return;
             }
             let status_rect = Rect::new(0, f.area().height - 1, f.area().width, 1);
-            let address_rect = Rect::new(0, 0, 17, f.area().height - status_rect.height);
-            let hex_editor_rect = Rect::new(
-                address_rect.width,
+            let hex_editor_width =
+                (self.block_size * 3 * self.blocks_per_row + self.blocks_per_row) as u16;
+            let info_view_width = f
+                .area()
+                .width
+                .saturating_sub(hex_editor_width + address_width + 2);
+            let info_view_width = if self.pane.is_some() {
+                info_view_width.min(hex_editor_width.saturating_sub(1))
+            } else if self.settings.app.bytes_per_row != 0 {
+                info_view_width.min(
+                    (self.block_size * 2 * self.blocks_per_row + self.blocks_per_row + 1) as u16,
+                )
+            } else if self.settings.app.info_view_width != 0 {
+                // the same width in every view so that the layout does not shift
+                info_view_width.min(self.settings.app.info_view_width as u16)
+            } else {
+                info_view_width
+            };
+            // with a fixed number of bytes per row the panels are centered,
+            // the scrollbar stays on the right edge
+            // and on narrow terminals the panels are cut to the space left of it
+            let panels_area = Rect::new(
+                0,
                 0,
-                (self.block_size * 3 * self.blocks_per_row + self.blocks_per_row) as u16,
+                f.area().width.saturating_sub(1),
                 f.area().height - status_rect.height,
             );
+            let margin = if self.pane.is_none() && self.settings.app.bytes_per_row != 0 {
+                f.area()
+                    .width
+                    .saturating_sub(address_width + hex_editor_width + info_view_width + 2)
+                    / 2
+            } else {
+                0
+            };
+            let address_rect =
+                Rect::new(margin, 0, address_width, panels_area.height).intersection(panels_area);
+            let hex_editor_rect = Rect::new(
+                margin + address_width,
+                0,
+                hex_editor_width,
+                panels_area.height,
+            )
+            .intersection(panels_area);
             let info_view_rect = Rect::new(
-                address_rect.width + hex_editor_rect.width,
+                margin + address_width + hex_editor_width,
                 0,
-                f.area().width - hex_editor_rect.width - address_rect.width - 2,
-                f.area().height - status_rect.height,
-            );
+                info_view_width,
+                panels_area.height,
+            )
+            .intersection(panels_area);
+            let split_info_mode = match self.pane {
+                Some(_) => None,
+                None => self.get_split_info_mode(),
+            };
+            let (info_view_rect, split_info_view_rect) = if split_info_mode.is_some() {
+                let top_height = info_view_rect.height / 2;
+                (
+                    Rect::new(info_view_rect.x, 0, info_view_rect.width, top_height),
+                    Rect::new(
+                        info_view_rect.x,
+                        top_height,
+                        info_view_rect.width,
+                        info_view_rect.height - top_height,
+                    ),
+                )
+            } else {
+                (info_view_rect, Rect::default())
+            };
             let scrollbar_rect = Rect::new(f.area().width - 1, 0, 1, f.area().height);
 
             let status_block = ratatui::widgets::Paragraph::new(self.build_status_bar())
                 .block(Block::default().borders(Borders::NONE));
 
-            let scrolled_amount = self.get_cursor_position().global_byte_index;
-            let total_amount = self.data.len();
             let scrollbar = ratatui::widgets::Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .style(self.settings.color.scrollbar)
                 .begin_symbol(None)
                 .end_symbol(None)
                 .track_symbol(None);
-            let mut scrollbar_state = ScrollbarState::new(total_amount).position(scrolled_amount);
+            let mut scrollbar_state = self.get_scrollbar_state();
 
             let mut info_view_frame_info = InfoViewFrameInfo::TextView;
+            let mut split_info_view = None;
 
             if !self.data.is_empty() {
                 let line_start_index = self.scroll;
@@ -228,67 +473,61 @@ impl App {
                 let editor_title =
                     format!("Hex Editor{}", if self.data.dirty() { " *" } else { "" });
 
+                // the keys go to the popup while it is open
+                let hex_editor_border = if self.popup.is_none() && self.focus == Focus::Hex {
+                    self.settings.color.focused_border
+                } else {
+                    Style::default()
+                };
                 let hex_editor_block = ratatui::widgets::Paragraph::new(hex_view).block(
                     Block::default()
                         .title(editor_title)
-                        .borders(Borders::LEFT | Borders::TOP | Borders::RIGHT),
+                        .borders(Borders::LEFT | Borders::TOP | Borders::RIGHT)
+                        .border_style(hex_editor_border),
                 );
 
                 let info_view_block = match &self.info_mode {
-                    InfoMode::Text => {
-                        let text_subview_lines =
-                            self.get_text_view(line_start_index, line_end_index);
-                        info_view_frame_info = InfoViewFrameInfo::TextView;
-                        let mut text_subview = Text::default();
-                        text_subview
-                            .lines
-                            .extend(text_subview_lines.iter().cloned());
-                        ratatui::widgets::Paragraph::new(text_subview).block(
+                    _ if self.pane.is_some() => {
+                        let pane_view = self.get_pane_view(line_start_index, line_end_index);
+                        info_view_frame_info = InfoViewFrameInfo::PaneView;
+                        let title = self
+                            .pane
+                            .as_ref()
+                            .and_then(|pane| path::filename(&pane.path))
+                            .unwrap_or_default()
+                            .to_string();
+                        ratatui::widgets::Paragraph::new(pane_view).block(
                             Block::default()
-                                .title("Text View")
+                                .title(title)
                                 .borders(Borders::TOP | Borders::RIGHT),
                         )
                     }
-                    InfoMode::Assembly => {
-                        let assembly_start_index = self.get_assembly_view_scroll();
-                        info_view_frame_info = InfoViewFrameInfo::AssemblyView {
-                            scroll: assembly_start_index,
-                        };
-                        let assembly_end_index = (assembly_start_index + f.area().height as usize
-                            - 2)
-                        .min(self.assembly_instructions.len());
-                        let assembly_subview_lines =
-                            &self.assembly_instructions[assembly_start_index..assembly_end_index];
-                        let mut assembly_subview = Text::default();
-                        let address_min_width = self
-                            .assembly_instructions
-                            .last()
-                            .map(|x| format!("{:X}", x.file_address()).len() + 1)
-                            .unwrap_or(1);
-                        assembly_subview
-                            .lines
-                            .extend(assembly_subview_lines.iter().map(|x| {
-                                x.to_line(
-                                    &self.settings.color,
-                                    self.get_cursor_position().global_byte_index,
-                                    &self.header,
-                                    address_min_width,
-                                )
-                            }));
-                        ratatui::widgets::Paragraph::new(assembly_subview).block(
-                            Block::default()
-                                .title("Assembly View")
-                                .borders(Borders::TOP | Borders::RIGHT),
-                        )
+                    info_mode => {
+                        let (info_view_block, frame_info) = self.get_info_view_block(
+                            *info_mode,
+                            info_view_rect,
+                            self.popup.is_none() && self.focus == Focus::Text,
+                        );
+                        info_view_frame_info = frame_info;
+                        info_view_block
                     }
                 };
 
                 f.render_widget(address_block, address_rect);
                 f.render_widget(hex_editor_block, hex_editor_rect);
                 f.render_widget(info_view_block, info_view_rect);
+                if let Some(split_info_mode) = split_info_mode {
+                    let (split_info_view_block, frame_info) =
+                        self.get_info_view_block(split_info_mode, split_info_view_rect, false);
+                    split_info_view = Some((split_info_view_rect, frame_info));
+                    f.render_widget(split_info_view_block, split_info_view_rect);
+                }
             }
             f.render_widget(status_block, status_rect);
             f.render_stateful_widget(scrollbar, scrollbar_rect, &mut scrollbar_state);
+            if let Some(position) = self.get_terminal_cursor(hex_editor_rect, info_view_rect) {
+                f.set_cursor_position(position);
+            }
 
             let mut this_frame_info = FrameInfo {
                 popup: None,
@@ -298,6 +537,7 @@ impl App {
                 hex_view: hex_editor_rect,
                 info_view: info_view_rect,
                 info_view_frame_info,
+                split_info_view,
                 blocks_per_row: self.blocks_per_row,
                 scroll: self.scroll,
                 file_size: self.data.len(),
@@ -330,7 +570,12 @@ impl App {
                 match popup_result {
                     Ok(()) => {
                         let popup = ratatui::widgets::Paragraph::new(popup_text)
-                            .block(Block::default().title(popup_title).borders(Borders::ALL))
+                            .block(
+                                Block::default()
+                                    .title(popup_title)
+                                    .borders(Borders::ALL)
+                                    .border_style(self.settings.color.focused_border),
+                            )
                             .alignment(ratatui::layout::Alignment::Center);
                         f.render_widget(Clear, popup_rect);
                         f.render_widget(popup, popup_rect);
@@ -356,7 +601,7 @@ impl App {
         self.resize_to_size(self.screen_size.0, self.screen_size.1);
 
         while !self.needs_to_exit {
-            if event::poll(self.poll_time)? {
+            if event::poll(self.get_poll_time())? {
                 while event::poll(Duration::from_millis(0))? {
                     let event = event::read()?;
                     let event_result = self.handle_event(event, terminal);
@@ -364,11 +609,24 @@ impl App {
                         self.log(NotificationLevel::Error, &e.to_string());
                     }
                 }
+            } else if let Err(e) = self.check_follow() {
+                self.log(NotificationLevel::Error, &e.to_string());
+            }
+            if let Err(e) = self.poll_opening_file(terminal) {
+                self.log(NotificationLevel::Error, &e.to_string());
             }
+            self.close_expired_popup();
 
             self.draw(terminal)?;
+            if let Err(e) = self.run_pending_plugin_command(terminal) {
+                self.log(NotificationLevel::Error, &e.to_string());
+            }
         }
 
+        self.save_session();
+        self.remember_file();
+        self.save_recent_dirs();
+
         Ok(())
     }
 }
@@ -379,15 +637,47 @@ impl Default for App {
             plugin_manager: PluginManager::default(),
             filesystem: FileSystem::default(),
             header: Header::None,
+            arch_override: None,
+            file_arch_override: None,
+            file_key: None,
+            remembered_files_path: None,
+            profiles_path: None,
+            session_path: None,
             logger: Logger::default(),
             help_list: Self::help_list(&Settings::default().key),
             data: Data::default(),
             assembly_offsets: Vec::new(),
             assembly_instructions: Vec::new(),
+            assembly_folds: Vec::new(),
             text_last_searched_string: String::new(),
+            text_last_searched_encoding: TextEncoding::default(),
+            hex_last_searched_string: String::new(),
+            last_search: None,
             info_mode: InfoMode::Text,
+            previous_info_mode: None,
+            focus: Focus::Hex,
             scroll: 0,
             cursor: (0, 0),
+            selection_anchor: None,
+            shift_selection: false,
+            highlight_equal_bytes: false,
+            pinned: None,
+            mark: None,
+            compare_range: None,
+            selection_differences: Vec::new(),
+            original_bytes: None,
+            follow: None,
+            pending_read: None,
+            opening_file: None,
+            pending_plugin_command: None,
+            disassembly_base: 0,
+            skip_disassembly: false,
+            pane: None,
+            fields: Vec::new(),
+            color_tags: Vec::new(),
+            recent_dirs: RecentDirs::default(),
+            recent_offsets: Vec::new(),
+            recent_offset_index: 0,
             poll_time: Duration::from_millis(1000),
             needs_to_exit: false,
             screen_size: (0, 0),
@@ -408,6 +698,7 @@ impl Default for App {
                 hex_view: Rect::default(),
                 info_view: Rect::default(),
                 info_view_frame_info: InfoViewFrameInfo::TextView,
+                split_info_view: None,
                 blocks_per_row: 1,
                 scroll: 0,
                 file_size: 0,
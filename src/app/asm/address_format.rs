@@ -0,0 +1,116 @@
+use crate::app::{settings::assembly_address_base::AssemblyAddressBase, App};
+
+/// How the addresses of the assembly view are written,
+/// independent from the address column of the hex view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressFormat {
+    pub base: AssemblyAddressBase,
+    /// The minimum width of the address column.
+    pub width: usize,
+    pub uppercase: bool,
+}
+
+impl Default for AddressFormat {
+    fn default() -> Self {
+        Self {
+            base: AssemblyAddressBase::File,
+            width: 0,
+            uppercase: true,
+        }
+    }
+}
+
+impl AddressFormat {
+    fn hex(&self, address: u64) -> String {
+        if self.uppercase {
+            format!("{:X}", address)
+        } else {
+            format!("{:x}", address)
+        }
+    }
+
+    fn address(&self, file_address: u64, virtual_address: u64) -> u64 {
+        match self.base {
+            AssemblyAddressBase::File => file_address,
+            AssemblyAddressBase::Virtual => virtual_address,
+        }
+    }
+
+    /// The address shown in the address column, padded to `width`.
+    pub fn column(&self, file_address: u64, virtual_address: u64) -> String {
+        let address = self.address(file_address, virtual_address);
+        format!("{:>width$}", self.hex(address), width = self.width)
+    }
+
+    /// The address shown after the instruction, the one that is not in the address column.
+    pub fn suffix(&self, file_address: u64, virtual_address: u64) -> String {
+        let address = match self.base {
+            AssemblyAddressBase::File => virtual_address,
+            AssemblyAddressBase::Virtual => file_address,
+        };
+        format!(" @{}", self.hex(address))
+    }
+}
+
+impl App {
+    pub(in crate::app) fn get_assembly_address_format(&self) -> AddressFormat {
+        let mut address_format = AddressFormat {
+            base: self.settings.app.assembly_address_base,
+            width: self.settings.app.assembly_address_width,
+            uppercase: self.settings.app.assembly_uppercase_hex,
+        };
+        if address_format.width == 0 {
+            // virtual addresses don't grow with the file offset, the sections can be in any order
+            address_format.width = self
+                .assembly_instructions
+                .iter()
+                .map(|line| address_format.address(line.file_address(), line.virtual_address()))
+                .max()
+                .map_or(1, |address| address_format.hex(address).len() + 1);
+        }
+        address_format
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::asm::{assembly_line::AssemblyLine, section_tag::SectionTag};
+
+    use super::*;
+
+    #[test]
+    fn test_address_format() {
+        let mut address_format = AddressFormat {
+            width: 6,
+            ..Default::default()
+        };
+        assert_eq!(address_format.column(0xAB, 0x4000AB), "    AB");
+        assert_eq!(address_format.suffix(0xAB, 0x4000AB), " @4000AB");
+        address_format.base = AssemblyAddressBase::Virtual;
+        address_format.uppercase = false;
+        assert_eq!(address_format.column(0xAB, 0x4000AB), "4000ab");
+        assert_eq!(address_format.suffix(0xAB, 0x4000AB), " @ab");
+
+        let mut app = App::mockup(vec![0x90; 0x100]);
+        assert_eq!(app.get_assembly_address_format().width, 3);
+        app.settings.app.assembly_address_width = 16;
+        assert_eq!(app.get_assembly_address_format().width, 16);
+
+        // the widest address is not always the one of the last line
+        let section = |file_address, virtual_address| {
+            AssemblyLine::SectionTag(SectionTag {
+                name: ".text".to_string(),
+                file_address,
+                virtual_address,
+                size: 0x10,
+                malformed: false,
+            })
+        };
+        app.assembly_instructions = vec![section(0, 0x400000), section(0x10, 0)];
+        app.settings.app.assembly_address_width = 0;
+        app.settings.app.assembly_address_base = AssemblyAddressBase::Virtual;
+        assert_eq!(app.get_assembly_address_format().width, 7);
+        app.settings.app.assembly_address_base = AssemblyAddressBase::File;
+        assert_eq!(app.get_assembly_address_format().width, 3);
+    }
+}
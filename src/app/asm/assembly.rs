@@ -1,9 +1,10 @@
+use object::Architecture;
 use ratatui::text::{Line, Span};
 
 use crate::{
     app::{
-        instruction::Instruction, log::NotificationLevel, settings::color_settings::ColorSettings,
-        App,
+        info_mode::InfoMode, instruction::Instruction, log::NotificationLevel,
+        settings::color_settings::ColorSettings, App,
     },
     asm::assembler::assemble,
     get_app_context,
@@ -11,7 +12,8 @@ use crate::{
 };
 
 use super::{
-    assembly_line::AssemblyLine, instruction_tag::InstructionTag, section_tag::SectionTag,
+    address_format::AddressFormat, assembly_line::AssemblyLine, instruction_tag::InstructionTag,
+    section_tag::SectionTag,
 };
 
 impl App {
@@ -38,12 +40,12 @@ impl App {
         instruction: &InstructionTag,
         selected: bool,
         header: &Header,
-        address_min_width: usize,
+        address_format: AddressFormat,
     ) -> Line<'static> {
         let symbol_table = header.get_symbols();
         let mut line = Line::default();
         line.spans.push(Span::styled(
-            format!("{:>address_min_width$X}", instruction.file_address),
+            address_format.column(instruction.file_address, instruction.instruction.ip()),
             if selected {
                 color_settings.assembly_selected
             } else {
@@ -81,89 +83,122 @@ impl App {
             ));
         }
         line.spans.push(Span::styled(
-            format!(" @{:X}", instruction.instruction.ip()),
+            address_format.suffix(instruction.file_address, instruction.instruction.ip()),
             color_settings.assembly_virtual_address,
         ));
 
         line
     }
 
+    /// Disassembles the code sections of the file,
+    /// if there is no header everything after `disassembly_base` is treated as code.
+    /// Code sections that can't be disassembled are shown as a single tag and the
+    /// reason is returned alongside the lines, so that the rest of the file is still usable.
     pub(in crate::app) fn sections_from_bytes(
         bytes: &[u8],
         header: &Header,
-    ) -> (Vec<usize>, Vec<AssemblyLine>) {
+        disassembly_base: usize,
+    ) -> (Vec<usize>, Vec<AssemblyLine>, Vec<String>) {
         let mut line_offsets = vec![0; bytes.len()];
         let mut lines = Vec::new();
+        let mut errors = Vec::new();
         let mut sections = header.get_sections();
-        if sections.is_empty() {
+        let from_header = !sections.is_empty();
+        if !from_header {
+            let disassembly_base = disassembly_base.min(bytes.len());
             sections.push(Section {
                 name: ".text".to_string(),
-                virtual_address: 0,
-                file_offset: 0,
-                size: bytes.len() as u64,
+                virtual_address: disassembly_base as u64,
+                file_offset: disassembly_base as u64,
+                size: (bytes.len() - disassembly_base) as u64,
             });
         }
 
         let mut current_byte = 0;
+        let mut previous_end = 0;
         for section in sections {
-            if section.file_offset > current_byte as u64 {
+            let malformed = from_header
+                && section
+                    .malformation(previous_end, bytes.len() as u64)
+                    .is_some();
+            previous_end = previous_end.max(section.file_offset.saturating_add(section.size));
+            // malformed sections are clamped to the file so that they can still be listed
+            let file_offset = (section.file_offset as usize).min(bytes.len());
+            let size = (section.size as usize).min(bytes.len() - file_offset);
+            if file_offset > current_byte {
                 lines.push(AssemblyLine::SectionTag(SectionTag {
                     name: "Unknown".to_string(),
                     file_address: current_byte as u64,
                     virtual_address: 0,
-                    size: section.file_offset as usize - current_byte,
+                    size: file_offset - current_byte,
+                    malformed: false,
                 }));
-                for _ in 0..section.file_offset as usize - current_byte {
+                for _ in 0..file_offset - current_byte {
                     line_offsets[current_byte] = lines.len() - 1;
                     current_byte += 1;
                 }
             }
             // if there are any overlapping sections, this should fix it
-            current_byte = section.file_offset as usize;
+            let covered_until = current_byte;
+            current_byte = file_offset;
+            lines.push(AssemblyLine::SectionTag(SectionTag {
+                name: section.name.clone(),
+                file_address: file_offset as u64,
+                virtual_address: section.virtual_address,
+                size,
+                malformed,
+            }));
             match section.name.as_str() {
-                ".text" | "__text" => {
-                    lines.push(AssemblyLine::SectionTag(SectionTag {
-                        name: section.name.clone(),
-                        file_address: section.file_offset,
-                        virtual_address: section.virtual_address,
-                        size: section.size as usize,
-                    }));
-                    let (offsets, instructions) = Self::assembly_from_section(
+                ".text" | "__text" if size > 0 => {
+                    match Self::assembly_from_section(
                         bytes,
                         header,
                         section.virtual_address as usize,
                         current_byte,
-                        section.size as usize,
+                        size,
                         lines.len(),
-                    );
-                    line_offsets.splice(
-                        section.file_offset as usize
-                            ..section.file_offset as usize + section.size as usize,
-                        offsets,
-                    );
-                    lines.extend(instructions);
-                    current_byte += section.size as usize;
+                    ) {
+                        Ok((offsets, instructions)) => {
+                            line_offsets.splice(file_offset..file_offset + size, offsets);
+                            lines.extend(instructions);
+                            current_byte += size;
+                        }
+                        Err(e) => {
+                            errors.push(format!(
+                                "Disassembly unavailable for {} at {:X}: {}",
+                                section.name, file_offset, e
+                            ));
+                            lines.push(AssemblyLine::SectionTag(SectionTag {
+                                name: "Disassembly unavailable".to_string(),
+                                file_address: file_offset as u64,
+                                virtual_address: section.virtual_address,
+                                size,
+                                malformed: true,
+                            }));
+                            for _ in 0..size {
+                                line_offsets[current_byte] = lines.len() - 1;
+                                current_byte += 1;
+                            }
+                        }
+                    }
                 }
-                name => {
-                    lines.push(AssemblyLine::SectionTag(SectionTag {
-                        name: name.to_string(),
-                        file_address: section.file_offset,
-                        virtual_address: section.virtual_address,
-                        size: section.size as usize,
-                    }));
-                    for _ in 0..section.size as usize {
+                _ => {
+                    for _ in 0..size {
                         line_offsets[current_byte] = lines.len() - 1;
                         current_byte += 1;
                     }
                 }
             }
+            current_byte = current_byte.max(covered_until);
         }
         if current_byte < bytes.len() {
             lines.push(AssemblyLine::SectionTag(SectionTag {
-                name: "Unknown".to_string(),
+                // data after the last section of an executable is usually called overlay
+                name: if from_header { "Overlay" } else { "Unknown" }.to_string(),
                 file_address: current_byte as u64,
                 virtual_address: 0,
                 size: bytes.len() - current_byte,
+                malformed: false,
             }));
             let initial_current_byte = current_byte;
             for _ in initial_current_byte..bytes.len() {
@@ -172,7 +207,7 @@ impl App {
             }
         }
 
-        (line_offsets, lines)
+        (line_offsets, lines, errors)
     }
 
     pub(in crate::app) fn assembly_from_section(
@@ -182,17 +217,19 @@ impl App {
         starting_file_address: usize,
         section_size: usize,
         starting_sections: usize,
-    ) -> (Vec<usize>, Vec<AssemblyLine>) {
+    ) -> Result<(Vec<usize>, Vec<AssemblyLine>), String> {
         let mut line_offsets = vec![0; section_size];
         let mut instructions = Vec::new();
         let mut current_byte = 0;
-        let decoder = header.get_decoder().expect("Failed to create decoder");
+        let section_bytes = bytes
+            .get(starting_file_address..starting_file_address.saturating_add(section_size))
+            .ok_or("The section is outside of the file")?;
+        let decoder = header
+            .get_decoder()
+            .map_err(|e| format!("Failed to create decoder: {}", e))?;
         let decoded = decoder
-            .disasm_all(
-                &bytes[starting_file_address..starting_file_address + section_size],
-                starting_ip as u64,
-            )
-            .expect("Failed to disassemble");
+            .disasm_all(section_bytes, starting_ip as u64)
+            .map_err(|e| format!("Failed to disassemble: {}", e))?;
         for instruction in decoded.iter() {
             let instruction_tag = InstructionTag {
                 instruction: Instruction::new(instruction, header.get_symbols()),
@@ -200,11 +237,67 @@ impl App {
             };
             instructions.push(AssemblyLine::Instruction(instruction_tag));
             for _ in 0..instruction.len() {
+                if current_byte >= section_size {
+                    return Err("The decoder read past the end of the section".into());
+                }
                 line_offsets[current_byte] = starting_sections + instructions.len() - 1;
                 current_byte += 1;
             }
         }
-        (line_offsets, instructions)
+        Ok((line_offsets, instructions))
+    }
+
+    /// Returns true unless disassembly is disabled in the settings or for the open file.
+    pub(in crate::app) fn is_disassembly_enabled(&self) -> bool {
+        self.settings.app.disassembly && !self.skip_disassembly
+    }
+
+    /// Disassembles the whole file, unless disassembly is disabled in the settings
+    /// or for the open file.
+    pub(in crate::app) fn disassemble(&mut self) {
+        if self.is_disassembly_enabled() {
+            let errors;
+            (self.assembly_offsets, self.assembly_instructions, errors) =
+                Self::sections_from_bytes(self.data.bytes(), &self.header, self.disassembly_base);
+            for error in errors {
+                self.log(NotificationLevel::Error, &error);
+            }
+        } else {
+            self.assembly_offsets = Vec::new();
+            self.assembly_instructions = Vec::new();
+        }
+    }
+
+    pub(in crate::app) fn toggle_disassembly(&mut self) {
+        self.settings.app.disassembly = !self.is_disassembly_enabled();
+        self.skip_disassembly = false;
+        self.disassemble();
+        if self.settings.app.disassembly {
+            self.log(NotificationLevel::Info, "Disassembly enabled");
+        } else {
+            if self.info_mode == InfoMode::Assembly {
+                self.info_mode = InfoMode::Text;
+            }
+            self.log(NotificationLevel::Info, "Disassembly disabled");
+        }
+    }
+
+    /// Starts the disassembly of a file without header at the cursor,
+    /// this fixes the disassembly of raw code that does not start at the beginning of the file.
+    pub(in crate::app) fn set_disassembly_base_to_cursor(&mut self) {
+        if !self.header.get_sections().is_empty() {
+            self.log(
+                NotificationLevel::Warning,
+                "The disassembly start can only be changed for files without sections.",
+            );
+            return;
+        }
+        self.disassembly_base = self.get_cursor_position().global_byte_index;
+        self.disassemble();
+        self.log(
+            NotificationLevel::Info,
+            &format!("Disassembly starts at {:X}", self.disassembly_base),
+        );
     }
 
     pub(in crate::app) fn bytes_from_assembly(
@@ -224,28 +317,30 @@ impl App {
         bytes: &[u8],
         start_from_beginning_of_instruction: bool,
     ) {
-        let current_instruction = self.get_current_instruction();
-        if let Some(current_instruction) = current_instruction {
-            let current_instruction = current_instruction.clone();
-            let current_ip = match &current_instruction {
-                AssemblyLine::Instruction(instruction) => instruction.file_address,
-                AssemblyLine::SectionTag(_) => self.get_cursor_position().global_byte_index as u64,
-            };
-            let instruction_offset = if start_from_beginning_of_instruction {
-                0
-            } else {
-                self.get_cursor_position().global_byte_index - current_ip as usize
-            };
-            let offset = current_ip as usize + instruction_offset;
-            let mut bytes = bytes.to_vec();
-            let mut app_context = get_app_context!(self);
-            app_context.offset = offset;
-            self.plugin_manager.on_edit(&mut bytes, &mut app_context);
+        if self.data.is_empty() {
+            return;
+        }
+        // without disassembly there is no current instruction, the cursor is used instead
+        let current_ip = match self.get_current_instruction() {
+            Some(AssemblyLine::Instruction(instruction)) => instruction.file_address,
+            _ => self.get_cursor_position().global_byte_index as u64,
+        };
+        let instruction_offset = if start_from_beginning_of_instruction {
+            0
+        } else {
+            self.get_cursor_position().global_byte_index - current_ip as usize
+        };
+        let offset = current_ip as usize + instruction_offset;
+        let cursor_position = self.get_cursor_position();
+        let mut bytes = bytes.to_vec();
+        let mut app_context = get_app_context!(self);
+        app_context.offset = offset;
+        self.plugin_manager.on_edit(&mut bytes, &mut app_context);
 
-            let modified_bytes = self.data.push_change(offset, bytes);
+        let modified_bytes = self.data.push_change(offset, bytes);
 
-            self.edit_assembly(modified_bytes + instruction_offset);
-        }
+        self.edit_assembly(modified_bytes + instruction_offset);
+        self.scroll_to_edit(cursor_position.global_byte_index, cursor_position.high_byte);
     }
 
     pub(in crate::app) fn patch(&mut self, assembly: &str) {
@@ -266,18 +361,150 @@ impl App {
         }
     }
 
-    pub(in crate::app) fn get_assembly_view_scroll(&self) -> usize {
+    /// Disassembles the bytes in the range [start, end) and formats them as text,
+    /// one instruction per line with its file address and bytes.
+    pub(in crate::app) fn disassemble_range(&self, start: usize, end: usize) -> String {
+        let mut ret = String::new();
+        if let Some(AssemblyLine::Instruction(instruction)) = self.get_instruction_at_checked(start)
+        {
+            if instruction.file_address as usize != start {
+                ret.push_str(&format!(
+                    "; the range starts in the middle of the instruction at {:X}\n",
+                    instruction.file_address
+                ));
+            }
+        }
+        let virtual_address = self
+            .header
+            .physical_to_virtual_address(start as u64)
+            .unwrap_or(start as u64);
+        let decoder = match self.header.get_decoder() {
+            Ok(decoder) => decoder,
+            Err(e) => return format!("; failed to create decoder: {}\n", e),
+        };
+        let decoded = match decoder.disasm_all(&self.data.bytes()[start..end], virtual_address) {
+            Ok(decoded) => decoded,
+            Err(e) => return format!("; failed to disassemble: {}\n", e),
+        };
+        let mut file_address = start;
+        for instruction in decoded.iter() {
+            let instruction = Instruction::new(instruction, self.header.get_symbols());
+            let bytes = instruction
+                .bytes
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let line = format!(
+                "{:X} @{:X}: {:<24} ",
+                file_address,
+                instruction.ip(),
+                bytes
+            );
+            if self.settings.app.uppercase_hex {
+                ret.push_str(&line);
+            } else {
+                ret.push_str(&line.to_lowercase());
+            }
+            ret.push_str(&format!("{}\n", instruction));
+            file_address += instruction.len();
+        }
+        ret
+    }
+
+    pub(in crate::app) fn copy_selection_as_disassembly(&mut self) {
+        if let Some((start, end)) = self.get_selection() {
+            let text = self
+                .settings
+                .app
+                .line_ending
+                .apply(&self.disassemble_range(start, end));
+            self.copy_to_clipboard(&text);
+        } else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+        }
+    }
+
+    /// Shows the assembly view at the instruction that covers the cursor,
+    /// the cursor is moved to the first byte of the instruction.
+    pub(in crate::app) fn jump_to_current_instruction(&mut self) {
+        self.set_info_mode(InfoMode::Assembly);
+        if self.info_mode != InfoMode::Assembly {
+            return;
+        }
+        match self.get_current_instruction() {
+            Some(AssemblyLine::Instruction(instruction)) => {
+                let file_address = instruction.file_address as usize;
+                self.jump_to_remembered(file_address, false);
+            }
+            _ => self.log(
+                NotificationLevel::Info,
+                "The cursor is not inside disassembled code.",
+            ),
+        }
+    }
+
+    /// The value of the program counter seen by an instruction while it runs:
+    /// the next instruction on x86, 8 or 4 bytes ahead on ARM and the instruction itself elsewhere,
+    /// files without a header are disassembled as x86-64.
+    fn get_program_counter(&self, instruction: &Instruction) -> u64 {
+        match (&self.header, self.header.architecture()) {
+            (Header::None, _)
+            | (_, Architecture::X86_64 | Architecture::X86_64_X32 | Architecture::I386) => {
+                instruction.ip() + instruction.len() as u64
+            }
+            (Header::Raw(raw), Architecture::Arm) if raw.thumb => (instruction.ip() + 4) & !3,
+            (_, Architecture::Arm) => instruction.ip() + 8,
+            _ => instruction.ip(),
+        }
+    }
+
+    /// Jumps to the address referenced by the memory operand of the instruction at the cursor,
+    /// the address is translated to a file offset like a pointer.
+    pub(in crate::app) fn jump_to_operand_target(&mut self) {
+        let Some(AssemblyLine::Instruction(instruction)) = self.get_current_instruction() else {
+            self.log(
+                NotificationLevel::Warning,
+                "The cursor is not inside disassembled code.",
+            );
+            return;
+        };
+        let instruction = instruction.instruction.clone();
+        let pc = self.get_program_counter(&instruction);
+        match instruction.memory_operand_target(pc) {
+            Ok(target) => match self.resolve_pointer(target) {
+                Some(offset) => {
+                    self.log(
+                        NotificationLevel::Info,
+                        &format!("Operand target: {:#X}", target),
+                    );
+                    self.jump_to_remembered(offset, false);
+                }
+                None => self.log(
+                    NotificationLevel::Warning,
+                    &format!("The address {:#X} is not inside the file.", target),
+                ),
+            },
+            Err(e) => self.log(NotificationLevel::Warning, e),
+        }
+    }
+
+    pub(in crate::app) fn get_assembly_view_scroll(&self, visible_lines: usize) -> usize {
+        if self.assembly_offsets.is_empty() {
+            return 0;
+        }
         let cursor_position = self.get_cursor_position();
         let current_ip = cursor_position
             .global_byte_index
             .min(self.assembly_offsets.len() - 1);
-        let current_scroll = self.assembly_offsets[current_ip];
+        let folded_lines = self.get_folded_lines();
+        let current_scroll =
+            Self::assembly_line_to_row(self.assembly_offsets[current_ip], &folded_lines);
 
-        let visible_lines = self.screen_size.1 - self.vertical_margin;
         let center_of_view = visible_lines / 2;
         let view_scroll = (current_scroll as isize - center_of_view as isize).clamp(
             0,
-            (self.assembly_instructions.len() as isize - visible_lines as isize).max(0),
+            (self.get_assembly_view_rows(&folded_lines) as isize - visible_lines as isize).max(0),
         );
 
         view_scroll as usize
@@ -297,11 +524,17 @@ impl App {
         &self.assembly_instructions[current_instruction_index]
     }
 
+    pub(in crate::app) fn get_instruction_at_checked(&self, index: usize) -> Option<&AssemblyLine> {
+        let current_instruction_index = *self.assembly_offsets.get(index)?;
+        self.assembly_instructions.get(current_instruction_index)
+    }
+
     pub(in crate::app) fn edit_assembly(&mut self, modifyied_bytes: usize) {
         let current_instruction = self.get_current_instruction();
-        if let Some(current_instruction) = current_instruction {
-            let from_byte = current_instruction.file_address() as usize;
-            let virtual_address = current_instruction.virtual_address();
+        // code that could not be disassembled is not disassembled again while editing
+        if let Some(AssemblyLine::Instruction(current_instruction)) = current_instruction {
+            let from_byte = current_instruction.file_address as usize;
+            let virtual_address = current_instruction.instruction.ip();
             let text_section = self.header.get_text_section();
             let (is_inside_text_section, maximum_code_byte) =
                 if let Some(text_section) = text_section {
@@ -309,15 +542,25 @@ impl App {
                         from_byte >= text_section.file_offset as usize
                             && from_byte
                                 < text_section.file_offset as usize + text_section.size as usize,
-                        text_section.file_offset as usize + text_section.size as usize,
+                        (text_section.file_offset as usize + text_section.size as usize)
+                            .min(self.data.len()),
                     )
                 } else {
-                    (true, self.data.len())
+                    (from_byte >= self.disassembly_base, self.data.len())
                 };
             if !is_inside_text_section {
                 return;
             }
-            let decoder = self.header.get_decoder().expect("Failed to create decoder");
+            let decoder = match self.header.get_decoder() {
+                Ok(decoder) => decoder,
+                Err(e) => {
+                    self.log(
+                        NotificationLevel::Error,
+                        &format!("Failed to create decoder: {}", e),
+                    );
+                    return;
+                }
+            };
             let mut offsets = Vec::new();
             let mut instructions = Vec::new();
             let mut to_byte = self.data.len();
@@ -332,9 +575,10 @@ impl App {
                     break;
                 }
                 let bytes = &self.data.bytes()[current_byte..maximum_code_byte];
-                let decoded = decoder
-                    .disasm_count(bytes, virtual_address + ip_offset, 1)
-                    .expect("Failed to disassemble");
+                let decoded = match decoder.disasm_count(bytes, virtual_address + ip_offset, 1) {
+                    Ok(decoded) => decoded,
+                    Err(_) => break,
+                };
                 if decoded.len() == 0 {
                     break;
                 }
@@ -421,6 +665,9 @@ impl App {
     }
 
     pub(in crate::app) fn parse_header(&mut self) -> Header {
+        if let Some(arch_override) = self.arch_override.or(self.file_arch_override) {
+            return Header::Raw(arch_override);
+        }
         let mut app_context = get_app_context!(self);
         match self.plugin_manager.try_parse_header(&mut app_context) {
             Some(header) => Header::CustomHeader(header),
@@ -433,7 +680,12 @@ impl App {
 
 #[cfg(test)]
 mod test {
-    use std::vec;
+    use std::{collections::HashMap, vec};
+
+    use crate::{
+        app::files::filesystem::FileSystem,
+        headers::{bitness::Bitness, custom_header::CustomHeader},
+    };
 
     use super::*;
     #[test]
@@ -454,7 +706,7 @@ mod test {
             &ColorSettings::get_default_dark_theme(),
             0,
             &Header::None,
-            0,
+            AddressFormat::default(),
         );
 
         let contains_mnemonic = line.spans.iter().any(|span| span.content.contains("mov"));
@@ -488,13 +740,14 @@ mod test {
             file_address,
             virtual_address,
             size: section_size,
+            malformed: false,
         });
 
         let line = al.to_line(
             &ColorSettings::get_default_dark_theme(),
             0,
             &Header::None,
-            0,
+            AddressFormat::default(),
         );
 
         let contains_section_name = line.spans.iter().any(|span| span.content.contains(".text"));
@@ -609,6 +862,162 @@ mod test {
         assert!(text_found);
     }
 
+    #[test]
+    fn test_malformed_sections() {
+        let section = |name: &str, file_offset, size| Section {
+            name: name.to_string(),
+            virtual_address: file_offset,
+            file_offset,
+            size,
+        };
+        let header = Header::CustomHeader(CustomHeader {
+            bitness: Bitness::Bit64,
+            entry: 0,
+            endianness: object::Endianness::Little,
+            architecture: object::Architecture::X86_64,
+            sections: vec![
+                section(".text", 0, 6),
+                section(".empty", 4, 0),
+                section(".data", 4, 8),
+                section(".past_end", 0x100, 0x10),
+            ],
+            symbols: HashMap::new(),
+            symbols_by_name: HashMap::new(),
+        });
+        let bytes = vec![0x48, 0x89, 0xd8, 0x48, 0x89, 0xc1, 0x90, 0x90, 0x90];
+        let (offsets, lines, errors) = App::sections_from_bytes(&bytes, &header, 0);
+        assert!(errors.is_empty());
+        assert_eq!(offsets.len(), bytes.len());
+        let sections = lines
+            .iter()
+            .filter_map(|line| match line {
+                AssemblyLine::SectionTag(section) => Some(section),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sections.len(), 4);
+        assert!(!sections[0].malformed);
+        assert!(sections[1].malformed);
+        assert!(sections[2].malformed);
+        assert!(sections[3].malformed);
+        assert_eq!(sections[3].file_address, bytes.len() as u64);
+        assert_eq!(sections[3].size, 0);
+    }
+
+    #[test]
+    fn test_malformed_headers() {
+        let elf = include_bytes!("../../../test/elf.bin");
+        let filesystem = FileSystem::new_local(".").unwrap();
+        for len in [0, 4, 0x40, 0x100, elf.len() / 2] {
+            let bytes = &elf[..len];
+            let header = Header::parse_header(bytes, "./elf.bin", &filesystem);
+            let (offsets, lines, _) = App::sections_from_bytes(bytes, &header, 0);
+            assert_eq!(offsets.len(), bytes.len());
+            assert!(offsets.iter().all(|&offset| offset < lines.len()));
+        }
+        let mut garbage = elf[..0x40].to_vec();
+        garbage.extend((0..0x100).map(|i| (i * 37 % 256) as u8));
+        let header = Header::parse_header(&garbage, "./elf.bin", &filesystem);
+        let (offsets, _, _) = App::sections_from_bytes(&garbage, &header, 0);
+        assert_eq!(offsets.len(), garbage.len());
+
+        let header = Header::None;
+        assert!(App::assembly_from_section(&[0x90; 4], &header, 0, 2, 4, 0).is_err());
+    }
+
+    #[test]
+    fn test_disassembly_base() {
+        let data = vec![0x00, 0x48, 0x89, 0xd8, 0x48, 0x89, 0xc1];
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.jump_to(1, false);
+        app.set_disassembly_base_to_cursor();
+        assert_eq!(app.disassembly_base, 1);
+        match &app.assembly_instructions[0] {
+            AssemblyLine::SectionTag(section) => {
+                assert_eq!(section.name, "Unknown");
+                assert_eq!(section.size, 1);
+            }
+            _ => panic!("The first byte should be outside of the code"),
+        }
+        let instructions = app
+            .assembly_instructions
+            .iter()
+            .filter_map(|line| match line {
+                AssemblyLine::Instruction(instruction) => Some(instruction.instruction.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(instructions.len(), 2);
+        assert!(instructions[0].contains("mov rax, rbx"));
+        assert!(instructions[1].contains("mov rcx, rax"));
+
+        app.jump_to(5, false);
+        app.jump_to_current_instruction();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+        assert_eq!(app.get_cursor_position().global_byte_index, 4);
+        app.jump_to(0, false);
+        app.jump_to_current_instruction();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+    }
+
+    #[test]
+    fn test_jump_to_operand_target() {
+        // mov rax, qword ptr [rip + 0x10]
+        let mut data = vec![0x48, 0x8B, 0x05, 0x10, 0x00, 0x00, 0x00];
+        data.resize(0x20, 0);
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.jump_to_operand_target();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x17);
+        // add byte ptr [rax], al
+        app.jump_to_operand_target();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x17);
+
+        let instruction = |operands: &str| Instruction {
+            mnemonic: "ldr".to_string(),
+            operands: operands.to_string(),
+            virtual_address: 0x100,
+            bytes: vec![0; 4],
+        };
+        assert_eq!(
+            instruction("r0, [pc, #-0x10]").memory_operand_target(0x108),
+            Ok(0xF8)
+        );
+        assert_eq!(
+            instruction("eax, dword ptr [0x1234]").memory_operand_target(0x104),
+            Ok(0x1234)
+        );
+        assert!(instruction("rax, qword ptr fs:[0x28]")
+            .memory_operand_target(0x104)
+            .is_err());
+        assert!(instruction("rax, rbx")
+            .memory_operand_target(0x104)
+            .is_err());
+    }
+
+    #[test]
+    fn test_disassemble_range() {
+        let data = vec![0x48, 0x89, 0xd8, 0x48, 0x89, 0xc1, 0x48, 0x89, 0xc0];
+        let app = App::mockup(data);
+        let text = app.disassemble_range(3, 9);
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("3 "));
+        assert!(lines[0].contains("48 89 C1"));
+        assert!(lines[0].contains("mov rcx, rax"));
+        assert!(lines[1].starts_with("6 "));
+        assert!(lines[1].contains("mov rax, rax"));
+
+        let mut app = app;
+        app.settings.app.uppercase_hex = false;
+        let text = app.disassemble_range(3, 6);
+        assert!(text.contains("48 89 c1"));
+
+        let text = app.disassemble_range(1, 3);
+        assert!(text.starts_with("; the range starts in the middle of the instruction at 0"));
+    }
+
     #[test]
     fn test_bad_instruction() {
         let data = vec![0x06, 0x0e, 0x07];
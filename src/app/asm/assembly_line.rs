@@ -5,7 +5,9 @@ use crate::{
     headers::Header,
 };
 
-use super::{instruction_tag::InstructionTag, section_tag::SectionTag};
+use super::{
+    address_format::AddressFormat, instruction_tag::InstructionTag, section_tag::SectionTag,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AssemblyLine {
@@ -47,7 +49,7 @@ impl AssemblyLine {
         color_settings: &ColorSettings,
         current_byte_index: usize,
         header: &Header,
-        address_min_width: usize,
+        address_format: AddressFormat,
     ) -> Line {
         match self {
             AssemblyLine::Instruction(instruction) => {
@@ -59,7 +61,7 @@ impl AssemblyLine {
                     instruction,
                     selected,
                     header,
-                    address_min_width,
+                    address_format,
                 )
             }
             AssemblyLine::SectionTag(section) => {
@@ -72,16 +74,23 @@ impl AssemblyLine {
                     color_settings.assembly_address
                 };
                 line.spans.push(Span::styled(
-                    format!("{:>address_min_width$X}", section.file_address),
+                    address_format.column(section.file_address, section.virtual_address),
                     address_style,
                 ));
                 line.spans.push(Span::raw(" "));
+                if section.malformed {
+                    line.spans.push(Span::styled(
+                        format!("[{} ({}B) malformed]", section.name, section.size),
+                        color_settings.assembly_bad,
+                    ));
+                } else {
+                    line.spans.push(Span::styled(
+                        format!("[{} ({}B)]", section.name, section.size),
+                        color_settings.assembly_section,
+                    ));
+                }
                 line.spans.push(Span::styled(
-                    format!("[{} ({}B)]", section.name, section.size),
-                    color_settings.assembly_section,
-                ));
-                line.spans.push(Span::styled(
-                    format!(" @{:X}", section.virtual_address),
+                    address_format.suffix(section.file_address, section.virtual_address),
                     color_settings.assembly_virtual_address,
                 ));
                 line
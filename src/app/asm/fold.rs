@@ -0,0 +1,269 @@
+use ratatui::text::{Line, Span};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{log::NotificationLevel, App};
+
+use super::{address_format::AddressFormat, assembly_line::AssemblyLine};
+
+/// A range of code collapsed to a single line in the assembly view, `end` is excluded.
+/// Folds are stored as file addresses so that they are kept when the file is disassembled again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fold {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Fold {
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+impl App {
+    /// Returns true if the instruction is the last one of a basic block.
+    fn ends_basic_block(mnemonic: &str) -> bool {
+        mnemonic.starts_with('j')
+            || mnemonic.starts_with("ret")
+            || matches!(mnemonic, "b" | "bl" | "bx" | "blx" | "br" | "blr" | "hlt")
+            || mnemonic.starts_with("b.")
+    }
+
+    fn line_starts_function(&self, line: &AssemblyLine) -> bool {
+        self.header
+            .get_symbols()
+            .is_some_and(|symbols| symbols.contains_key(&line.virtual_address()))
+    }
+
+    /// Finds the lines of the function that contains the line at `index` using the symbols,
+    /// or the lines of its basic block if the file has no symbols.
+    fn get_foldable_lines(&self, index: usize) -> (usize, usize) {
+        let lines = &self.assembly_instructions;
+        let by_symbol = self
+            .header
+            .get_symbols()
+            .is_some_and(|symbols| !symbols.is_empty());
+        let is_instruction = |i: usize| matches!(lines[i], AssemblyLine::Instruction(_));
+        let ends_block = |i: usize| match &lines[i] {
+            AssemblyLine::Instruction(instruction) => {
+                Self::ends_basic_block(instruction.instruction.mnemonic())
+            }
+            AssemblyLine::SectionTag(_) => true,
+        };
+        let mut first = index;
+        while first > 0 && is_instruction(first - 1) {
+            if by_symbol && self.line_starts_function(&lines[first]) {
+                break;
+            }
+            if !by_symbol && ends_block(first - 1) {
+                break;
+            }
+            first -= 1;
+        }
+        let mut last = index;
+        while last + 1 < lines.len() && is_instruction(last + 1) {
+            if by_symbol && self.line_starts_function(&lines[last + 1]) {
+                break;
+            }
+            if !by_symbol && ends_block(last) {
+                break;
+            }
+            last += 1;
+        }
+        (first, last)
+    }
+
+    pub(in crate::app) fn get_fold_at(&self, offset: usize) -> Option<&Fold> {
+        self.assembly_folds
+            .iter()
+            .find(|fold| fold.contains(offset))
+    }
+
+    /// Folds the function or the basic block at the cursor, or unfolds it if it is already folded.
+    pub(in crate::app) fn toggle_fold(&mut self) {
+        let offset = self.get_cursor_position().global_byte_index;
+        if let Some(index) = self
+            .assembly_folds
+            .iter()
+            .position(|fold| fold.contains(offset))
+        {
+            let fold = self.assembly_folds.remove(index);
+            self.log(
+                NotificationLevel::Info,
+                &format!("Unfolded {:X}-{:X}", fold.start, fold.end),
+            );
+            return;
+        }
+        let index = match self.assembly_offsets.get(offset) {
+            Some(&index)
+                if matches!(
+                    self.assembly_instructions[index],
+                    AssemblyLine::Instruction(_)
+                ) =>
+            {
+                index
+            }
+            _ => {
+                self.log(NotificationLevel::Warning, "Only code can be folded.");
+                return;
+            }
+        };
+        let (first, last) = self.get_foldable_lines(index);
+        let fold = Fold {
+            start: self.assembly_instructions[first].file_address() as usize,
+            end: self.assembly_instructions[last].file_address() as usize
+                + self.assembly_instructions[last].len(),
+        };
+        self.assembly_folds
+            .retain(|other| other.end <= fold.start || other.start >= fold.end);
+        let position = self
+            .assembly_folds
+            .partition_point(|other| other.start < fold.start);
+        self.assembly_folds.insert(position, fold);
+        self.log(
+            NotificationLevel::Info,
+            &format!("Folded {:X}-{:X}", fold.start, fold.end),
+        );
+    }
+
+    /// The lines of the assembly view hidden by each fold, as inclusive ranges sorted by line.
+    pub(in crate::app) fn get_folded_lines(&self) -> Vec<(usize, usize)> {
+        self.assembly_folds
+            .iter()
+            .filter(|fold| fold.end <= self.assembly_offsets.len() && fold.start < fold.end)
+            .map(|fold| {
+                (
+                    self.assembly_offsets[fold.start],
+                    self.assembly_offsets[fold.end - 1],
+                )
+            })
+            .collect()
+    }
+
+    /// Number of rows of the assembly view, folded ranges take a single row.
+    pub(in crate::app) fn get_assembly_view_rows(&self, folded_lines: &[(usize, usize)]) -> usize {
+        self.assembly_instructions.len()
+            - folded_lines
+                .iter()
+                .map(|(first, last)| last - first)
+                .sum::<usize>()
+    }
+
+    /// Returns the row of the assembly view where the line at `index` is shown.
+    pub(in crate::app) fn assembly_line_to_row(
+        index: usize,
+        folded_lines: &[(usize, usize)],
+    ) -> usize {
+        let mut row = index;
+        for &(first, last) in folded_lines {
+            if index <= first {
+                break;
+            }
+            row -= index.min(last) - first;
+        }
+        row
+    }
+
+    /// Returns the index of the line shown at `row` of the assembly view,
+    /// and the last line of the fold if the row is a folded range.
+    pub(in crate::app) fn assembly_row_to_line(
+        row: usize,
+        folded_lines: &[(usize, usize)],
+    ) -> (usize, Option<usize>) {
+        let mut index = row;
+        for &(first, last) in folded_lines {
+            if index < first {
+                break;
+            }
+            if index == first {
+                return (index, Some(last));
+            }
+            index += last - first;
+        }
+        (index, None)
+    }
+
+    /// Builds the single line that replaces the folded lines from `first` to `last`.
+    pub(in crate::app) fn folded_lines_to_line(
+        &self,
+        first: usize,
+        last: usize,
+        address_format: AddressFormat,
+    ) -> Line<'static> {
+        let color_settings = &self.settings.color;
+        let first_line = &self.assembly_instructions[first];
+        let start = first_line.file_address() as usize;
+        let end = self.assembly_instructions[last].file_address() as usize
+            + self.assembly_instructions[last].len();
+        let cursor = self.get_cursor_position().global_byte_index;
+        let mut line = Line::default();
+        line.spans.push(Span::styled(
+            address_format.column(start as u64, first_line.virtual_address()),
+            if start <= cursor && cursor < end {
+                color_settings.assembly_selected
+            } else {
+                color_settings.assembly_address
+            },
+        ));
+        line.spans.push(Span::raw(" "));
+        line.spans.push(Span::styled(
+            format!("[{} instructions folded]", last - first + 1),
+            color_settings.assembly_section,
+        ));
+        if let Some(symbol) = self
+            .header
+            .get_symbols()
+            .and_then(|symbols| symbols.get(&first_line.virtual_address()))
+        {
+            line.spans.push(Span::raw(" "));
+            line.spans.push(Span::styled(
+                format!("<{}>", symbol),
+                color_settings.assembly_symbol,
+            ));
+        }
+        line.spans.push(Span::styled(
+            address_format.suffix(start as u64, first_line.virtual_address()),
+            color_settings.assembly_virtual_address,
+        ));
+        line
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fold() {
+        // mov rax, rbx; jmp rax; mov rcx, rax; ret; nop
+        let data = vec![0x48, 0x89, 0xd8, 0xff, 0xe0, 0x48, 0x89, 0xc1, 0xc3, 0x90];
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.jump_to(5, false);
+        app.toggle_fold();
+        assert_eq!(app.assembly_folds, vec![Fold { start: 5, end: 9 }]);
+
+        let folded_lines = app.get_folded_lines();
+        // the first line is the section tag
+        assert_eq!(folded_lines, vec![(3, 4)]);
+        assert_eq!(app.get_assembly_view_rows(&folded_lines), 5);
+        assert_eq!(App::assembly_row_to_line(3, &folded_lines), (3, Some(4)));
+        assert_eq!(App::assembly_row_to_line(4, &folded_lines), (5, None));
+        assert_eq!(App::assembly_line_to_row(4, &folded_lines), 3);
+        assert_eq!(App::assembly_line_to_row(5, &folded_lines), 4);
+
+        // folds are kept when the file is disassembled again
+        app.disassemble();
+        assert_eq!(app.get_folded_lines(), vec![(3, 4)]);
+
+        app.jump_to(0, false);
+        app.move_cursor_to_near_instruction(2);
+        assert_eq!(app.get_cursor_position().global_byte_index, 5);
+        app.move_cursor_to_near_instruction(1);
+        assert_eq!(app.get_cursor_position().global_byte_index, 9);
+        app.move_cursor_to_near_instruction(-1);
+        assert_eq!(app.get_cursor_position().global_byte_index, 5);
+
+        app.toggle_fold();
+        assert!(app.assembly_folds.is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::app::{
+    asm::assembly_line::AssemblyLine, log::NotificationLevel, popup::popup_state::PopupState, App,
+};
+
+/// Where the assembly goto jumps, numbers are in hexadecimal with an optional `0x` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssemblyTarget {
+    /// A virtual address.
+    Absolute(u64),
+    /// A number of bytes after or before the virtual address of the current instruction.
+    Relative(i64),
+}
+
+impl AssemblyTarget {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let parse_number = |number: &str| {
+            let number = number.trim();
+            let number = number
+                .strip_prefix("0x")
+                .or_else(|| number.strip_prefix("0X"))
+                .unwrap_or(number);
+            u64::from_str_radix(number, 16).map_err(|_| format!("Invalid address: {}", text))
+        };
+        if let Some(offset) = text.strip_prefix('+') {
+            let offset = i64::try_from(parse_number(offset)?).map_err(|e| e.to_string())?;
+            Ok(Self::Relative(offset))
+        } else if let Some(offset) = text.strip_prefix('-') {
+            let offset = i64::try_from(parse_number(offset)?).map_err(|e| e.to_string())?;
+            Ok(Self::Relative(-offset))
+        } else {
+            Ok(Self::Absolute(parse_number(text)?))
+        }
+    }
+}
+
+impl App {
+    /// Moves the cursor to the instruction at a virtual address, or at a distance from the current one.
+    /// If no instruction starts there, the cursor goes to the instruction that contains the address
+    /// or, if there is none, to the file offset of the address.
+    pub(in crate::app) fn jump_to_assembly_target(&mut self, text: &str) {
+        let target = match AssemblyTarget::parse(text) {
+            Ok(target) => target,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return;
+            }
+        };
+        let virtual_address = match target {
+            AssemblyTarget::Absolute(address) => Some(address),
+            AssemblyTarget::Relative(offset) => self
+                .get_current_instruction()
+                .and_then(|instruction| instruction.virtual_address().checked_add_signed(offset)),
+        };
+        let Some(virtual_address) = virtual_address else {
+            self.log(NotificationLevel::Error, "The address is out of range.");
+            return;
+        };
+        let instruction = self
+            .assembly_instructions
+            .iter()
+            .find_map(|line| match line {
+                AssemblyLine::Instruction(instruction)
+                    if instruction.instruction.ip() <= virtual_address
+                        && virtual_address
+                            < instruction.instruction.ip()
+                                + instruction.instruction.len() as u64 =>
+                {
+                    Some((instruction.instruction.ip(), instruction.file_address))
+                }
+                _ => None,
+            });
+        match instruction {
+            Some((ip, file_address)) => {
+                if ip != virtual_address {
+                    self.log(
+                        NotificationLevel::Warning,
+                        &format!(
+                            "No instruction starts at {:X}, moved to the one at {:X}",
+                            virtual_address, ip
+                        ),
+                    );
+                }
+                self.jump_to_remembered(file_address as usize, false);
+            }
+            None => match self.header.virtual_to_physical_address(virtual_address) {
+                Some(file_address) => {
+                    self.log(
+                        NotificationLevel::Warning,
+                        &format!(
+                            "No instruction at {:X}, moved to its file offset {:X}",
+                            virtual_address, file_address
+                        ),
+                    );
+                    self.jump_to_remembered(file_address as usize, false);
+                }
+                None => self.log(
+                    NotificationLevel::Error,
+                    &format!("Virtual address {:X} not found", virtual_address),
+                ),
+            },
+        }
+    }
+
+    pub(in crate::app) fn request_popup_assembly_goto(&mut self) {
+        if self.assembly_instructions.is_empty() {
+            self.log(NotificationLevel::Warning, "The file is not disassembled.");
+            return;
+        }
+        self.popup = Some(PopupState::AssemblyGoto {
+            text: String::new(),
+            cursor: 0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_assembly_goto() {
+        assert_eq!(
+            AssemblyTarget::parse("0x401000"),
+            Ok(AssemblyTarget::Absolute(0x401000))
+        );
+        assert_eq!(
+            AssemblyTarget::parse("- 1F"),
+            Ok(AssemblyTarget::Relative(-0x1F))
+        );
+        assert!(AssemblyTarget::parse("+cursor").is_err());
+
+        // mov rax, rbx; jmp rax; mov rcx, rax; ret; nop
+        let data = vec![0x48, 0x89, 0xd8, 0xff, 0xe0, 0x48, 0x89, 0xc1, 0xc3, 0x90];
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.jump_to_assembly_target("+5");
+        assert_eq!(app.get_cursor_position().global_byte_index, 5);
+        app.jump_to_assembly_target("-2");
+        assert_eq!(app.get_cursor_position().global_byte_index, 3);
+        app.jump_to_assembly_target("8");
+        assert_eq!(app.get_cursor_position().global_byte_index, 8);
+        app.jump_to_assembly_target("6");
+        assert_eq!(app.get_cursor_position().global_byte_index, 5);
+        app.jump_to_assembly_target("100");
+        assert_eq!(app.get_cursor_position().global_byte_index, 5);
+        app.jump_to_assembly_target("-6");
+        assert_eq!(app.get_cursor_position().global_byte_index, 5);
+    }
+}
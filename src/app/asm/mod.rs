@@ -1,4 +1,7 @@
+pub mod address_format;
 pub mod assembly;
 pub mod assembly_line;
+pub mod fold;
+pub mod goto;
 pub mod instruction_tag;
 pub mod section_tag;
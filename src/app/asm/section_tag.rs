@@ -4,4 +4,5 @@ pub struct SectionTag {
     pub file_address: u64,
     pub virtual_address: u64,
     pub size: usize,
+    pub malformed: bool,
 }
@@ -0,0 +1,122 @@
+use std::fmt::Display;
+
+use ratatui::text::Text;
+
+use super::{settings::bom_display::BomDisplay, App};
+
+/// A byte order mark at the start of a text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bom {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Bom {
+    /// Detects the mark at the start of `bytes`, UTF-32 is checked first
+    /// because its little endian mark starts like the UTF-16 one.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        [
+            Self::Utf32Le,
+            Self::Utf32Be,
+            Self::Utf8,
+            Self::Utf16Le,
+            Self::Utf16Be,
+        ]
+        .into_iter()
+        .find(|bom| bytes.starts_with(bom.bytes()))
+    }
+
+    pub fn bytes(&self) -> &'static [u8] {
+        match self {
+            Self::Utf8 => &[0xEF, 0xBB, 0xBF],
+            Self::Utf16Le => &[0xFF, 0xFE],
+            Self::Utf16Be => &[0xFE, 0xFF],
+            Self::Utf32Le => &[0xFF, 0xFE, 0x00, 0x00],
+            Self::Utf32Be => &[0x00, 0x00, 0xFE, 0xFF],
+        }
+    }
+}
+
+impl Display for Bom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Utf8 => write!(f, "UTF-8"),
+            Self::Utf16Le => write!(f, "UTF-16 LE"),
+            Self::Utf16Be => write!(f, "UTF-16 BE"),
+            Self::Utf32Le => write!(f, "UTF-32 LE"),
+            Self::Utf32Be => write!(f, "UTF-32 BE"),
+        }
+    }
+}
+
+impl App {
+    /// Hides or highlights the byte order mark in a text view that starts at `start_byte`,
+    /// the cursor and the selection keep their style.
+    pub(super) fn style_bom(&self, text: &mut Text, start_byte: usize) {
+        if start_byte != 0 || self.settings.app.bom_display == BomDisplay::Show {
+            return;
+        }
+        let Some(bom) = Bom::detect(self.data.bytes()) else {
+            return;
+        };
+        let Some(line) = text.lines.first_mut() else {
+            return;
+        };
+        // every byte is rendered as its character followed by the spacing
+        for (&byte, span) in bom.bytes().iter().zip(line.spans.iter_mut().step_by(2)) {
+            match self.settings.app.bom_display {
+                BomDisplay::Show => {}
+                BomDisplay::Hide => {
+                    span.content = " ".repeat(span.content.chars().count()).into();
+                }
+                BomDisplay::Highlight => {
+                    if span.style == Self::get_style_for_byte(&self.settings.color, byte) {
+                        span.style = self.settings.color.text_bom;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bom() {
+        assert_eq!(Bom::detect(&[0xEF, 0xBB, 0xBF, 0x41]), Some(Bom::Utf8));
+        assert_eq!(Bom::detect(&[0xFF, 0xFE, 0x41, 0x00]), Some(Bom::Utf16Le));
+        assert_eq!(Bom::detect(&[0xFF, 0xFE, 0x00, 0x00]), Some(Bom::Utf32Le));
+        assert_eq!(Bom::detect(&[0xFE, 0xFF]), Some(Bom::Utf16Be));
+        assert_eq!(Bom::detect(b"text"), None);
+
+        let mut data = vec![0xEF, 0xBB, 0xBF];
+        data.extend(b"Hello, world!");
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.jump_to(4, false);
+        let text_view = app.get_text_view(0, 1);
+        assert_ne!(text_view.lines[0].spans[2].content, " ");
+
+        app.settings.app.bom_display = BomDisplay::Hide;
+        let text_view = app.get_text_view(0, 1);
+        assert_eq!(text_view.lines[0].spans[0].content.trim(), "");
+        assert_eq!(text_view.lines[0].spans[4].content.trim(), "");
+        assert_eq!(text_view.lines[0].spans[6].content, "H");
+
+        app.settings.app.bom_display = BomDisplay::Highlight;
+        let text_view = app.get_text_view(0, 1);
+        assert_eq!(
+            text_view.lines[0].spans[2].style,
+            app.settings.color.text_bom
+        );
+        assert_ne!(
+            text_view.lines[0].spans[6].style,
+            app.settings.color.text_bom
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use ratatui::text::{Line, Span};
+
+use super::{log::NotificationLevel, settings::color_settings::ColorSettings, App};
+
+/// A minimal evaluator for offset arithmetic, it supports `+`, `-`, `*`, parentheses,
+/// decimal literals and hexadecimal literals starting with `0x`.
+struct Calculator<'a> {
+    expression: &'a [u8],
+    position: usize,
+}
+
+impl Calculator<'_> {
+    fn skip_spaces(&mut self) {
+        while self
+            .expression
+            .get(self.position)
+            .is_some_and(|c| c.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_spaces();
+        self.expression.get(self.position).copied()
+    }
+
+    fn sum(&mut self) -> Result<i128, String> {
+        let mut value = self.product()?;
+        while let Some(operator @ (b'+' | b'-')) = self.peek() {
+            self.position += 1;
+            let rhs = self.product()?;
+            value = if operator == b'+' {
+                value + rhs
+            } else {
+                value - rhs
+            };
+            Self::check_range(value)?;
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<i128, String> {
+        let mut value = self.term()?;
+        while let Some(b'*') = self.peek() {
+            self.position += 1;
+            value = value
+                .checked_mul(self.term()?)
+                .ok_or("The result is too large")?;
+            Self::check_range(value)?;
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<i128, String> {
+        match self.peek() {
+            Some(b'(') => {
+                self.position += 1;
+                let value = self.sum()?;
+                if self.peek() != Some(b')') {
+                    return Err(format!("Expected \")\" at {}", self.position));
+                }
+                self.position += 1;
+                Ok(value)
+            }
+            Some(b'-') => {
+                self.position += 1;
+                Ok(-self.term()?)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.position;
+                while self
+                    .expression
+                    .get(self.position)
+                    .is_some_and(|c| c.is_ascii_alphanumeric())
+                {
+                    self.position += 1;
+                }
+                let literal = std::str::from_utf8(&self.expression[start..self.position])
+                    .expect("The literal is ASCII");
+                let value = match literal
+                    .strip_prefix("0x")
+                    .or_else(|| literal.strip_prefix("0X"))
+                {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => literal.parse::<u64>(),
+                };
+                value
+                    .map(|value| value as i128)
+                    .map_err(|_| format!("Invalid number \"{}\"", literal))
+            }
+            Some(c) => Err(format!("Unexpected \"{}\" at {}", c as char, self.position)),
+            None => Err("Unexpected end of expression".into()),
+        }
+    }
+
+    fn check_range(value: i128) -> Result<(), String> {
+        if value.unsigned_abs() > u64::MAX as u128 {
+            Err("The result is too large".into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl App {
+    /// Evaluates an offset expression such as `0x1000 + 4*16`, the result can't be negative.
+    pub(super) fn evaluate_offset(expression: &str) -> Result<u64, String> {
+        let mut calculator = Calculator {
+            expression: expression.as_bytes(),
+            position: 0,
+        };
+        let value = calculator.sum()?;
+        if let Some(c) = calculator.peek() {
+            return Err(format!(
+                "Unexpected \"{}\" at {}",
+                c as char, calculator.position
+            ));
+        }
+        u64::try_from(value).map_err(|_| "The result is negative".into())
+    }
+
+    /// The line of the calculator popup that shows the result or why the expression is invalid.
+    pub(super) fn get_calculator_result(
+        color_settings: &ColorSettings,
+        expression: &str,
+    ) -> Line<'static> {
+        if expression.trim().is_empty() {
+            return Line::styled("Result", color_settings.placeholder);
+        }
+        match Self::evaluate_offset(expression) {
+            Ok(value) => Line::from(vec![Span::styled(
+                format!("= {} = {:#X}", value, value),
+                color_settings.menu_text,
+            )]),
+            Err(e) => Line::styled(e, color_settings.log_error),
+        }
+    }
+
+    pub(super) fn jump_to_calculated_offset(&mut self, expression: &str) -> bool {
+        match Self::evaluate_offset(expression) {
+            Ok(offset) if (offset as usize) < self.data.len() => {
+                self.jump_to_remembered(offset as usize, false);
+                true
+            }
+            Ok(offset) => {
+                self.log(
+                    NotificationLevel::Warning,
+                    &format!("{:#X} is past the end of the file", offset),
+                );
+                false
+            }
+            Err(e) => {
+                self.log(NotificationLevel::Warning, &e);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_offset() {
+        assert_eq!(App::evaluate_offset("0x1000 + 4*16"), Ok(0x1040));
+        assert_eq!(App::evaluate_offset("(1 + 2) * 3 - 0X4"), Ok(5));
+        assert_eq!(App::evaluate_offset(" 10 - -2 "), Ok(12));
+        assert!(App::evaluate_offset("1 - 2").is_err());
+        assert!(App::evaluate_offset("1 +").is_err());
+        assert!(App::evaluate_offset("(1 + 2").is_err());
+        assert!(App::evaluate_offset("0xZZ").is_err());
+        assert!(App::evaluate_offset("1 / 2").is_err());
+        assert!(App::evaluate_offset("0xFFFFFFFFFFFFFFFF * 0xFFFFFFFFFFFFFFFF").is_err());
+
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.resize_to_size(80, 24);
+        assert!(app.jump_to_calculated_offset("0x10 * 2 + 1"));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x21);
+        assert!(!app.jump_to_calculated_offset("0x100"));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x21);
+    }
+}
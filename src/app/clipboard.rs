@@ -0,0 +1,172 @@
+use crossterm::{execute, style::Print};
+
+use super::{log::NotificationLevel, settings::copy_format::CopyFormat, App};
+
+impl App {
+    /// Copies the text to the system clipboard using the OSC 52 escape sequence,
+    /// this works with most terminals, even over SSH.
+    pub(super) fn copy_to_clipboard(&mut self, text: &str) {
+        let characters = format!("{} characters", text.chars().count());
+        self.copy_bytes_to_clipboard(text.as_bytes(), &characters);
+    }
+
+    /// Like [`App::copy_to_clipboard`] for bytes that might not be valid text,
+    /// `description` tells what was copied in the log.
+    fn copy_bytes_to_clipboard(&mut self, bytes: &[u8], description: &str) {
+        let sequence = format!("\x1b]52;c;{}\x07", Self::base64_encode(bytes));
+        if let Err(e) = execute!(std::io::stdout(), Print(sequence)) {
+            self.log(
+                NotificationLevel::Error,
+                &format!("Could not copy to the clipboard: {e}"),
+            );
+        } else {
+            self.log(
+                NotificationLevel::Info,
+                &format!("Copied {} to the clipboard", description),
+            );
+        }
+    }
+
+    /// Copies the selection in the format of the `copy_format` setting,
+    /// or in its alternate format if `alternate` is true.
+    pub(super) fn copy_selection(&mut self, alternate: bool) {
+        let Some(bytes) = self.get_selection_bytes().map(<[u8]>::to_vec) else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+            return;
+        };
+        let format = if alternate {
+            self.settings.app.copy_format.alternate()
+        } else {
+            self.settings.app.copy_format
+        };
+        match format {
+            CopyFormat::Hex => {
+                let text = Self::bytes_to_hex_string(&bytes, self.settings.app.uppercase_hex);
+                self.copy_to_clipboard(&text);
+            }
+            CopyFormat::Raw => {
+                self.copy_bytes_to_clipboard(&bytes, &format!("{} bytes", bytes.len()));
+            }
+            CopyFormat::Escaped => {
+                let text = Self::bytes_to_escaped_string(&bytes);
+                self.copy_to_clipboard(&text);
+            }
+        }
+    }
+
+    pub(super) fn bytes_to_hex_string(bytes: &[u8], uppercase: bool) -> String {
+        bytes
+            .iter()
+            .map(|byte| {
+                if uppercase {
+                    format!("{:02X}", byte)
+                } else {
+                    format!("{:02x}", byte)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Escapes the bytes like the content of a C string literal, a hex digit that follows
+    /// a `\x` escape is escaped too so that it is not read as part of the escape.
+    pub(super) fn bytes_to_escaped_string(bytes: &[u8]) -> String {
+        let mut ret = String::with_capacity(bytes.len());
+        let mut after_hex_escape = false;
+        for &byte in bytes {
+            after_hex_escape = match byte {
+                b'\n' => {
+                    ret.push_str("\\n");
+                    false
+                }
+                b'\r' => {
+                    ret.push_str("\\r");
+                    false
+                }
+                b'\t' => {
+                    ret.push_str("\\t");
+                    false
+                }
+                b'\\' => {
+                    ret.push_str("\\\\");
+                    false
+                }
+                b'"' => {
+                    ret.push_str("\\\"");
+                    false
+                }
+                b' '..=b'~' if !(after_hex_escape && byte.is_ascii_hexdigit()) => {
+                    ret.push(byte as char);
+                    false
+                }
+                _ => {
+                    ret.push_str(&format!("\\x{:02x}", byte));
+                    true
+                }
+            };
+        }
+        ret
+    }
+
+    pub(super) fn base64_encode(bytes: &[u8]) -> String {
+        const SYMBOLS: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut ret = String::with_capacity(bytes.len().div_ceil(3) * 4);
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).cloned().unwrap_or(0),
+                chunk.get(2).cloned().unwrap_or(0),
+            ];
+            let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    ret.push(SYMBOLS[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+                } else {
+                    ret.push('=');
+                }
+            }
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(App::base64_encode(b""), "");
+        assert_eq!(App::base64_encode(b"f"), "Zg==");
+        assert_eq!(App::base64_encode(b"fo"), "Zm8=");
+        assert_eq!(App::base64_encode(b"foo"), "Zm9v");
+        assert_eq!(App::base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(App::base64_encode(&[0xff, 0x00, 0x80]), "/wCA");
+    }
+
+    #[test]
+    fn test_copy_formats() {
+        assert_eq!(
+            App::bytes_to_hex_string(&[0xDE, 0xAD, 0x01], true),
+            "DE AD 01"
+        );
+        assert_eq!(
+            App::bytes_to_hex_string(&[0xDE, 0xAD, 0x01], false),
+            "de ad 01"
+        );
+        assert_eq!(
+            App::bytes_to_escaped_string(b"AB\0C\n\"\\\xFFz"),
+            "AB\\x00\\x43\\n\\\"\\\\\\xffz"
+        );
+        assert_eq!(CopyFormat::Hex.alternate(), CopyFormat::Escaped);
+        assert_eq!(CopyFormat::Raw.alternate(), CopyFormat::Hex);
+
+        let mut app = App::mockup(vec![0; 4]);
+        app.copy_selection(false);
+        assert!(app
+            .logger
+            .iter()
+            .any(|message| message.message == "Nothing is selected."));
+    }
+}
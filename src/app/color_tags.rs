@@ -0,0 +1,156 @@
+use ratatui::{style::Style, text::Text};
+
+use super::App;
+
+/// A range of bytes colored by a plugin, `end` is excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorTag {
+    pub start: usize,
+    pub end: usize,
+    pub style: Style,
+}
+
+impl App {
+    pub(super) fn toggle_highlight_equal_bytes(&mut self) {
+        self.highlight_equal_bytes = !self.highlight_equal_bytes;
+    }
+
+    /// Returns the style of the most recently set color tag that contains `offset`.
+    pub(super) fn get_color_tag_at(&self, offset: usize) -> Option<Style> {
+        self.color_tags
+            .iter()
+            .rev()
+            .find(|tag| tag.start <= offset && offset < tag.end)
+            .map(|tag| tag.style)
+    }
+
+    /// Applies the styles of the fields, of the color tags, of the pinned byte and of the bytes
+    /// equal to the one at the cursor to the bytes of a view, only the visible bytes are checked.
+    /// Bytes that are already highlighted (cursor, selection, instruction) are left as they are.
+    /// `column_highlight` must be the same used to build the view.
+    pub(super) fn style_annotations(
+        &self,
+        text: &mut Text,
+        start_byte: usize,
+        spans_per_byte: usize,
+        column_highlight: usize,
+    ) {
+        let cursor = self.get_cursor_position().global_byte_index;
+        let equal_byte = if self.highlight_equal_bytes {
+            self.data.get(cursor)
+        } else {
+            None
+        };
+        if self.fields.is_empty()
+            && self.color_tags.is_empty()
+            && equal_byte.is_none()
+            && self.pinned.is_none()
+            && self.selection_differences.is_empty()
+        {
+            return;
+        }
+        let bytes_per_row = self.blocks_per_row * self.block_size;
+        for (row, line) in text.lines.iter_mut().enumerate() {
+            // every byte is rendered as its digits or character followed by the spacing
+            for (column, spans) in line.spans.chunks_mut(spans_per_byte).enumerate() {
+                let index = start_byte + row * bytes_per_row + column;
+                let field_style = self
+                    .get_field_at(index)
+                    .map(|_| self.settings.color.hex_field);
+                let tag_style = self.get_color_tag_at(index);
+                let equal_style = equal_byte
+                    .filter(|&byte| index != cursor && self.data.get(index) == Some(byte))
+                    .map(|_| self.settings.color.hex_equal);
+                let pinned_style = self
+                    .pinned
+                    .filter(|&pinned| pinned == index)
+                    .map(|_| self.settings.color.hex_pinned);
+                let difference_style = self
+                    .selection_differences
+                    .binary_search(&index)
+                    .ok()
+                    .map(|_| self.settings.color.hex_difference);
+                if field_style.is_none()
+                    && tag_style.is_none()
+                    && equal_style.is_none()
+                    && pinned_style.is_none()
+                    && difference_style.is_none()
+                {
+                    continue;
+                }
+                let byte_style = Self::get_base_style_for_byte(
+                    &self.settings.color,
+                    self.data.bytes()[index],
+                    column,
+                    column_highlight,
+                );
+                let style = byte_style
+                    .patch(equal_style.unwrap_or_default())
+                    .patch(field_style.unwrap_or_default())
+                    .patch(tag_style.unwrap_or_default())
+                    .patch(difference_style.unwrap_or_default())
+                    .patch(pinned_style.unwrap_or_default());
+                for span in spans.iter_mut().take(spans_per_byte - 1) {
+                    if span.style == byte_style {
+                        span.style = style;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::style::Color;
+
+    use super::*;
+
+    #[test]
+    fn test_color_tags() {
+        let mut app = App::mockup((0..0x40).collect());
+        // wide enough to show the first 16 bytes in one row
+        app.resize_to_size(120, 24);
+        let red = Style::default().bg(Color::Red);
+        let blue = Style::default().bg(Color::Blue);
+        app.color_tags.push(ColorTag {
+            start: 4,
+            end: 8,
+            style: red,
+        });
+        app.color_tags.push(ColorTag {
+            start: 6,
+            end: 10,
+            style: blue,
+        });
+        assert_eq!(app.get_color_tag_at(3), None);
+        assert_eq!(app.get_color_tag_at(4), Some(red));
+        assert_eq!(app.get_color_tag_at(7), Some(blue));
+        assert_eq!(app.get_color_tag_at(10), None);
+
+        let hex_view = app.get_hex_view(0, 1);
+        // the cursor is on the first byte, every byte is made of 3 spans
+        assert_eq!(hex_view.lines[0].spans[4 * 3].style.bg, Some(Color::Red));
+        assert_eq!(hex_view.lines[0].spans[7 * 3].style.bg, Some(Color::Blue));
+        assert_ne!(hex_view.lines[0].spans[10 * 3].style.bg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_highlight_equal_bytes() {
+        let mut app = App::mockup(vec![1, 2, 1, 3, 1, 2]);
+        app.resize_to_size(80, 24);
+        let equal = app.settings.color.hex_equal.bg;
+        let hex_view = app.get_hex_view(0, 1);
+        assert_ne!(hex_view.lines[0].spans[2 * 3].style.bg, equal);
+
+        app.toggle_highlight_equal_bytes();
+        let hex_view = app.get_hex_view(0, 1);
+        // the cursor keeps its own style
+        assert_ne!(hex_view.lines[0].spans[0].style.bg, equal);
+        assert_eq!(hex_view.lines[0].spans[2 * 3].style.bg, equal);
+        assert_eq!(hex_view.lines[0].spans[4 * 3].style.bg, equal);
+        assert_ne!(hex_view.lines[0].spans[3].style.bg, equal);
+        let text_view = app.get_text_view(0, 1);
+        assert_eq!(text_view.lines[0].spans[2 * 2].style.bg, equal);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::app::{log::NotificationLevel, App};
+
+use super::command_info::CommandInfo;
+
+impl App {
+    /// Returns the command an alias stands for, or the command itself if it is not an alias.
+    pub(in crate::app) fn resolve_command_alias(&self, command: &str) -> String {
+        self.settings
+            .app
+            .command_aliases
+            .get(command)
+            .cloned()
+            .unwrap_or_else(|| command.to_string())
+    }
+
+    /// Warns about the aliases that hide a command with the same name,
+    /// and about the ones whose command does not exist.
+    pub(in crate::app) fn check_command_aliases(&mut self) {
+        let commands = CommandInfo::full_list_of_commands(&self.plugin_manager);
+        let is_command = |name: &str| commands.iter().any(|info| info.command == name);
+        let mut warnings = Vec::new();
+        for (alias, command) in self.settings.app.command_aliases.iter() {
+            if is_command(alias) {
+                warnings.push(format!(
+                    "The alias \"{}\" hides the command with the same name",
+                    alias
+                ));
+            }
+            if !is_command(command) {
+                warnings.push(format!(
+                    "The alias \"{}\" stands for \"{}\", which is not a command",
+                    alias, command
+                ));
+            }
+        }
+        for warning in warnings {
+            self.log(NotificationLevel::Warning, &warning);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_command_aliases() {
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.settings.app.command_aliases = [("ch", "copyhex"), ("save", "undo"), ("x", "none")]
+            .into_iter()
+            .map(|(alias, command)| (alias.to_string(), command.to_string()))
+            .collect();
+        app.logger.clear();
+        app.check_command_aliases();
+        assert_eq!(app.logger.len(), 2);
+
+        let commands = app.find_commands("ch");
+        assert_eq!(commands[0].command, "ch");
+        let saves = app
+            .find_commands("save")
+            .into_iter()
+            .filter(|info| info.command == "save")
+            .collect::<Vec<_>>();
+        assert_eq!(saves.len(), 1);
+        assert_eq!(saves[0].description, "Alias of \"undo\".");
+
+        assert_eq!(app.resolve_command_alias("save"), "undo");
+        assert_eq!(app.resolve_command_alias("saveas"), "saveas");
+    }
+}
@@ -25,16 +25,137 @@ impl CommandInfo {
             CommandInfo::new("saveas", "Save the current file as a new file."),
             CommandInfo::new("help", "Display the help page."),
             CommandInfo::new("open", "Open a file."),
+            CommandInfo::new("recent", "Browse a recently visited directory."),
+            CommandInfo::new(
+                "saveprofile",
+                "Save the app settings, the colors and the view as a named profile.",
+            ),
+            CommandInfo::new("profile", "Apply a saved profile."),
             CommandInfo::new("log", "Open the log."),
             CommandInfo::new("run", "Run a command."),
             CommandInfo::new("ftext", "Find text."),
+            CommandInfo::new(
+                "fhex",
+                "Find a sequence of bytes written in hex, such as \"de ad be ef\".",
+            ),
             CommandInfo::new("fsym", "Find a symbol."),
             CommandInfo::new("text", "Insert text."),
             CommandInfo::new("patch", "Patch assembly."),
             CommandInfo::new("jump", "Jump to address."),
+            CommandInfo::new(
+                "asmgoto",
+                "Jump to the instruction at a virtual address, or at +/- a hex offset from the current one.",
+            ),
+            CommandInfo::new(
+                "calc",
+                "Compute an offset from an expression and jump to it.",
+            ),
+            CommandInfo::new("fill", "Fill with a repeated pattern."),
+            CommandInfo::new("nth", "Jump to the Nth occurrence of a byte."),
+            CommandInfo::new(
+                "duplicate",
+                "Insert a copy of the selection right after it.",
+            ),
+            CommandInfo::new(
+                "editor",
+                "Edit the selection in an external editor, the length of the selection can change.",
+            ),
+            CommandInfo::new(
+                "value",
+                "Write a number as bytes of a type, such as \"0x1234 u32 be\" or \"now u32\" for the current Unix time.",
+            ),
+            CommandInfo::new("overlay", "Jump to the data after the last section."),
+            CommandInfo::new("sections", "List the sections of the file and jump to one."),
+            CommandInfo::new(
+                "top",
+                "Scroll so that the row of the cursor is at the top, or the row of the offset after the command, such as \"top 0x1000\".",
+            ),
+            CommandInfo::new("row", "Scroll to a row of the hex view, counting from 1."),
+            CommandInfo::new(
+                "textoffset",
+                "Jump to the offset written as text at the cursor.",
+            ),
             CommandInfo::new("view", "Switch between text and assembly."),
+            CommandInfo::new("revert", "Discard all the changes."),
+            CommandInfo::new(
+                "asmbase",
+                "Start the disassembly at the cursor, for files without sections.",
+            ),
             CommandInfo::new("undo", "Undo the last change."),
             CommandInfo::new("redo", "Redo the last change."),
+            CommandInfo::new(
+                "history",
+                "Browse the changes and undo or redo up to one of them.",
+            ),
+            CommandInfo::new(
+                "extract",
+                "Save the selection as a new file and open it.",
+            ),
+            CommandInfo::new(
+                "export",
+                "Save the selection, or the rows shown, as a text or HTML hex dump.",
+            ),
+            CommandInfo::new(
+                "compare",
+                "Compare the bytes at the cursor with a file.",
+            ),
+            CommandInfo::new(
+                "cmpsel",
+                "Mark the selection, then compare it with another selection of the same size.",
+            ),
+            CommandInfo::new(
+                "savepatch",
+                "Save the bytes changed since the last save as a patch file.",
+            ),
+            CommandInfo::new(
+                "applypatch",
+                "Apply a patch file, if the original bytes match.",
+            ),
+            CommandInfo::new("disasm", "Toggle the disassembly of the file."),
+            CommandInfo::new(
+                "field",
+                "Name the selected bytes as a field with a type.",
+            ),
+            CommandInfo::new("fields", "List the fields of the file."),
+            CommandInfo::new(
+                "pointers",
+                "List the targets of the pointers in the selection.",
+            ),
+            CommandInfo::new("strings", "List the printable strings in the file."),
+            CommandInfo::new(
+                "exportstrings",
+                "Save the printable strings in the file with their offsets.",
+            ),
+            CommandInfo::new(
+                "cstruct",
+                "Copy the fields as a C struct definition to the clipboard.",
+            ),
+            CommandInfo::new(
+                "pane",
+                "Show a file next to the hex editor, scrolling together.",
+            ),
+            CommandInfo::new("closepane", "Close the second pane."),
+            CommandInfo::new(
+                "swappane",
+                "Edit the file in the second pane instead of the current one.",
+            ),
+            CommandInfo::new(
+                "pull",
+                "Copy the selected bytes from the second pane to the current file.",
+            ),
+            CommandInfo::new(
+                "follow",
+                "Toggle reloading the file when it changes on disk.",
+            ),
+            CommandInfo::new(
+                "copyasm",
+                "Copy the disassembly of the selection to the clipboard.",
+            ),
+            CommandInfo::new(
+                "copyhex",
+                "Copy the selection as a hex dump to the clipboard.",
+            ),
+            CommandInfo::new("copyc", "Copy the selection as a C array to the clipboard."),
         ]
     }
 
@@ -1,2 +1,3 @@
+pub mod aliases;
 pub mod command_info;
 pub mod run_command;
@@ -1,7 +1,11 @@
 use std::error::Error;
 
+use ratatui::{backend::Backend, Terminal};
+
 use crate::{
     app::{
+        focus::Focus,
+        history::change::Change,
         info_mode::InfoMode,
         log::NotificationLevel,
         popup::{
@@ -18,20 +22,42 @@ use super::command_info::CommandInfo;
 impl App {
     pub(in crate::app) fn find_commands(&mut self, command: &str) -> Vec<CommandInfo> {
         let mut commands = CommandInfo::full_list_of_commands(&self.plugin_manager);
+        // an alias hides the command with the same name
+        let aliases = &self.settings.app.command_aliases;
+        commands.retain(|c| !aliases.contains_key(&c.command));
+        commands.extend(
+            aliases.iter().map(|(alias, command)| {
+                CommandInfo::new(alias, format!("Alias of \"{}\".", command))
+            }),
+        );
         //commands.retain(|c| c.command.contains(command));
         fuzzy_search_in_place(command, &mut commands);
         commands
     }
 
-    pub(in crate::app) fn run_command(
+    /// Splits the text of the run popup into the command, that is the first word,
+    /// and the argument that follows it.
+    pub(in crate::app) fn split_command_argument(command: &str) -> (&str, &str) {
+        let command = command.trim();
+        command
+            .split_once(char::is_whitespace)
+            .map_or((command, ""), |(command, argument)| {
+                (command, argument.trim())
+            })
+    }
+
+    pub(in crate::app) fn run_command<B: Backend>(
         &mut self,
         command: &str,
         scroll: usize,
+        terminal: &mut Terminal<B>,
     ) -> Result<(), Box<dyn Error>> {
+        let (command, argument) = Self::split_command_argument(command);
         let command_opt = self.find_commands(command).into_iter().nth(scroll);
         let command_info = command_opt.expect("Scroll out of bounds for run_command.");
         self.popup = None;
-        match command_info.command.as_str() {
+        let command = self.resolve_command_alias(&command_info.command);
+        match command.as_str() {
             "quit" => {
                 self.quit(None)?;
             }
@@ -42,9 +68,7 @@ impl App {
                 self.quit(Some(true))?;
             }
             "save" => {
-                if self.data.dirty() {
-                    self.save_file()?;
-                }
+                self.save_file()?;
             }
             "saveas" => {
                 self.request_popup_save_as();
@@ -64,6 +88,9 @@ impl App {
             "ftext" => {
                 self.request_popup_find_text();
             }
+            "fhex" => {
+                self.request_popup_find_hex();
+            }
             "fsym" => {
                 self.request_popup_find_symbol();
             }
@@ -76,24 +103,168 @@ impl App {
             "jump" => {
                 self.request_popup_jump();
             }
+            "asmgoto" => {
+                self.request_popup_assembly_goto();
+            }
+            "calc" => {
+                self.request_popup_calculator();
+            }
+            "overlay" => {
+                self.jump_to_overlay();
+            }
+            "sections" => {
+                self.request_popup_sections();
+            }
+            "top" => {
+                // the offset after the command is evaluated like in the calculator popup
+                if argument.is_empty() || self.jump_to_calculated_offset(argument) {
+                    self.scroll_cursor_to_top();
+                }
+            }
+            "row" => {
+                self.request_popup_goto_row();
+            }
+            "textoffset" => {
+                self.jump_to_text_offset();
+            }
             "view" => {
                 self.request_view_change();
             }
+            "revert" => {
+                self.request_popup_revert();
+            }
+            "asmbase" => {
+                self.set_disassembly_base_to_cursor();
+            }
             "undo" => {
                 self.undo();
             }
             "redo" => {
                 self.redo();
             }
+            "history" => {
+                self.request_popup_history();
+            }
+            "copyasm" => {
+                self.copy_selection_as_disassembly();
+            }
+            "copyhex" => {
+                self.copy_selection_as_hexdump();
+            }
+            "copyc" => {
+                self.copy_selection_as_c_array();
+            }
+            "extract" => {
+                self.request_popup_extract();
+            }
+            "export" => {
+                self.request_popup_export_hexdump();
+            }
+            "compare" => {
+                self.request_popup_compare();
+            }
+            "cmpsel" => {
+                self.compare_selections();
+            }
+            "savepatch" => {
+                self.request_popup_save_patch();
+            }
+            "applypatch" => {
+                self.request_popup_apply_patch();
+            }
+            "disasm" => {
+                self.toggle_disassembly();
+            }
+            "field" => {
+                self.request_popup_add_field();
+            }
+            "fields" => {
+                self.request_popup_fields();
+            }
+            "pointers" => {
+                self.request_popup_pointers();
+            }
+            "strings" => {
+                self.request_popup_strings();
+            }
+            "exportstrings" => {
+                self.request_popup_export_strings();
+            }
+            "recent" => {
+                self.request_popup_recent_dirs();
+            }
+            "saveprofile" => {
+                self.request_popup_save_profile();
+            }
+            "profile" => {
+                self.request_popup_profiles();
+            }
+            "cstruct" => {
+                self.copy_fields_as_c_struct();
+            }
+            "pane" => {
+                self.request_popup_open_pane();
+            }
+            "closepane" => {
+                self.close_pane();
+            }
+            "swappane" => {
+                self.swap_panes()?;
+            }
+            "pull" => {
+                self.copy_from_pane();
+            }
+            "fill" => {
+                self.request_popup_fill();
+            }
+            "nth" => {
+                self.request_popup_occurrence();
+            }
+            "duplicate" => {
+                self.request_duplicate_selection();
+            }
+            "editor" => {
+                self.edit_selection_in_editor(terminal);
+            }
+            "value" => {
+                self.request_popup_write_value();
+            }
+            "follow" => {
+                self.toggle_follow();
+            }
             any_other_command => {
-                let mut app_context = get_app_context!(self);
-                self.plugin_manager
-                    .run_command(any_other_command, &mut app_context)?;
+                self.pending_plugin_command = Some(any_other_command.to_string());
             }
         }
         Ok(())
     }
 
+    /// Runs the plugin command chosen in the run popup after the screen was drawn without it,
+    /// the statuses of the plugin are printed over that screen.
+    pub(in crate::app) fn run_pending_plugin_command<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        let Some(command) = self.pending_plugin_command.take() else {
+            return Ok(());
+        };
+        let color_settings = self.settings.color.clone();
+        let mut printed = false;
+        let mut app_context = get_app_context!(self);
+        let result = self
+            .plugin_manager
+            .run_command(&command, &mut app_context, &mut |status| {
+                printed = true;
+                Self::print_status_over(&color_settings, status, terminal)
+            });
+        // the status bar written by the plugin is not known to the terminal,
+        // so the next frame is drawn from scratch
+        if printed {
+            terminal.clear()?;
+        }
+        Ok(result?)
+    }
+
     pub(in crate::app) fn quit(&mut self, save: Option<bool>) -> Result<(), Box<dyn Error>> {
         match save {
             Some(true) => {
@@ -101,18 +272,18 @@ impl App {
                 if self.data.dirty() {
                     self.save_file()?;
                 }
-                self.needs_to_exit = true;
+                self.request_exit();
             }
             Some(false) => {
                 self.log(NotificationLevel::Debug, "Quitting without saving...");
-                self.needs_to_exit = true;
+                self.request_exit();
             }
             None => {
                 self.log(NotificationLevel::Debug, "Quitting...");
                 if self.data.dirty() {
                     self.log(NotificationLevel::Warning, "You have unsaved changes.")
                 } else {
-                    self.needs_to_exit = true;
+                    self.request_exit();
                 }
             }
         }
@@ -123,13 +294,31 @@ impl App {
         if self.data.dirty() {
             self.popup = Some(PopupState::QuitDirtySave(SimpleChoice::Cancel));
         } else {
+            self.request_exit();
+        }
+    }
+
+    /// Exits the program, unless some plugin has unsaved data,
+    /// in that case a popup asks for confirmation.
+    pub(in crate::app) fn request_exit(&mut self) {
+        let mut app_context = get_app_context!(self);
+        let messages = self.plugin_manager.get_unsaved_data(&mut app_context);
+        if messages.is_empty() {
+            self.popup = None;
             self.needs_to_exit = true;
+        } else {
+            self.popup = Some(PopupState::QuitPluginData {
+                messages,
+                choice: BinaryChoice::No,
+            });
         }
     }
 
     pub(in crate::app) fn request_save(&mut self) {
         if self.data.dirty() {
             self.popup = Some(PopupState::Save(BinaryChoice::No));
+        } else if let Err(e) = self.save_file() {
+            self.log(NotificationLevel::Error, &e.to_string());
         }
     }
 
@@ -137,7 +326,7 @@ impl App {
         if self.data.dirty() {
             self.popup = Some(PopupState::SaveAndQuit(BinaryChoice::No));
         } else {
-            self.needs_to_exit = true;
+            self.request_exit();
         }
     }
 
@@ -147,6 +336,8 @@ impl App {
             &mut new_popup,
             &self.get_current_dir(),
             &mut self.filesystem,
+            self.settings.app.follow_symlinks,
+            &self.recent_dirs,
         )?;
         self.popup = new_popup;
         Ok(())
@@ -158,6 +349,92 @@ impl App {
         self.popup = Some(PopupState::SaveAs { path, cursor });
     }
 
+    pub(in crate::app) fn request_popup_export_hexdump(&mut self) {
+        let path = format!("{}.txt", self.filesystem.pwd());
+        let cursor = path.len();
+        self.popup = Some(PopupState::ExportHexdump { path, cursor });
+    }
+
+    pub(in crate::app) fn request_popup_export_strings(&mut self) {
+        let path = format!("{}.strings.txt", self.filesystem.pwd());
+        let cursor = path.len();
+        self.popup = Some(PopupState::ExportStrings { path, cursor });
+    }
+
+    pub(in crate::app) fn request_popup_extract(&mut self) {
+        if let Some((start, end)) = self.get_selection() {
+            let path = format!("{}.{:X}-{:X}", self.filesystem.pwd(), start, end);
+            let cursor = path.len();
+            self.popup = Some(PopupState::ExtractAs { path, cursor });
+        } else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+        }
+    }
+
+    pub(in crate::app) fn request_popup_compare(&mut self) {
+        let mut path = self.get_current_dir();
+        if !path.ends_with(self.filesystem.separator()) {
+            path.push(self.filesystem.separator());
+        }
+        let cursor = path.len();
+        self.popup = Some(PopupState::CompareWith { path, cursor });
+    }
+
+    pub(in crate::app) fn request_popup_save_patch(&mut self) {
+        if self.data.changed_bytes() == 0 {
+            self.log(NotificationLevel::Warning, "There are no changes to save.");
+            return;
+        }
+        let path = format!("{}.patch", self.filesystem.pwd());
+        let cursor = path.len();
+        self.popup = Some(PopupState::SavePatch { path, cursor });
+    }
+
+    pub(in crate::app) fn request_popup_apply_patch(&mut self) {
+        let path = format!("{}.patch", self.filesystem.pwd());
+        let cursor = path.len();
+        self.popup = Some(PopupState::ApplyPatch { path, cursor });
+    }
+
+    pub(in crate::app) fn request_popup_add_field(&mut self) {
+        self.popup = Some(PopupState::AddField {
+            text: String::new(),
+            cursor: 0,
+        });
+    }
+
+    pub(in crate::app) fn request_popup_fields(&mut self) {
+        if self.fields.is_empty() {
+            self.log(NotificationLevel::Info, "There are no fields.");
+        } else {
+            // the field under the cursor is selected if there is one
+            let offset = self.get_cursor_position().global_byte_index;
+            let selected = self
+                .fields
+                .iter()
+                .position(|field| field.offset <= offset && offset < field.end())
+                .unwrap_or(0);
+            self.popup = Some(PopupState::Fields(selected));
+        }
+    }
+
+    pub(in crate::app) fn request_popup_open_pane(&mut self) {
+        let mut path = self.get_current_dir();
+        if !path.ends_with(self.filesystem.separator()) {
+            path.push(self.filesystem.separator());
+        }
+        let cursor = path.len();
+        self.popup = Some(PopupState::OpenPane { path, cursor });
+    }
+
+    pub(in crate::app) fn request_popup_revert(&mut self) {
+        if self.data.dirty() {
+            self.popup = Some(PopupState::Revert(BinaryChoice::No));
+        } else {
+            self.log(NotificationLevel::Info, "There are no changes to revert.");
+        }
+    }
+
     pub(in crate::app) fn request_popup_help(&mut self) {
         self.popup = Some(PopupState::Help(0));
     }
@@ -185,9 +462,24 @@ impl App {
         });
     }
 
+    pub(in crate::app) fn request_popup_sections(&mut self) {
+        let results = self.find_sections("");
+        if results.is_empty() {
+            self.log(NotificationLevel::Warning, "The file has no sections.");
+            return;
+        }
+        self.popup = Some(PopupState::Sections {
+            filter: String::new(),
+            cursor: 0,
+            results,
+            scroll: 0,
+        });
+    }
+
     pub(in crate::app) fn request_popup_find_text(&mut self) {
         self.popup = Some(PopupState::FindText {
             text: self.text_last_searched_string.clone(),
+            encoding: self.text_last_searched_encoding,
             cursor: 0,
         });
     }
@@ -199,6 +491,15 @@ impl App {
         });
     }
 
+    pub(in crate::app) fn request_popup_fill(&mut self) {
+        // when something is selected the length defaults to the size of the selection
+        let text = match self.get_selection() {
+            Some((start, end)) => format!(" * {}", end - start),
+            None => String::new(),
+        };
+        self.popup = Some(PopupState::Fill { text, cursor: 0 });
+    }
+
     pub(in crate::app) fn request_popup_patch(&mut self) {
         self.popup = Some(PopupState::Patch {
             assembly: String::new(),
@@ -214,47 +515,232 @@ impl App {
         });
     }
 
+    pub(in crate::app) fn request_popup_write_value(&mut self) {
+        self.popup = Some(PopupState::WriteValue {
+            text: String::new(),
+            cursor: 0,
+        });
+    }
+
+    pub(in crate::app) fn request_popup_calculator(&mut self) {
+        self.popup = Some(PopupState::Calculator {
+            text: String::new(),
+            cursor: 0,
+        });
+    }
+
+    pub(in crate::app) fn get_available_info_modes(&self) -> Vec<InfoMode> {
+        let disassembly = self.is_disassembly_enabled();
+        self.settings
+            .app
+            .info_modes
+            .iter()
+            .copied()
+            .filter(|info_mode| disassembly || *info_mode != InfoMode::Assembly)
+            .collect()
+    }
+
+    /// Returns the view shown below the current one when the info panel is split,
+    /// that is the next view in the cycle that is different from the current one.
+    pub(in crate::app) fn get_split_info_mode(&self) -> Option<InfoMode> {
+        if !self.settings.app.split_info_view {
+            return None;
+        }
+        let info_modes = self.get_available_info_modes();
+        let current_index = info_modes
+            .iter()
+            .position(|info_mode| *info_mode == self.info_mode)
+            .unwrap_or(0);
+        info_modes
+            .iter()
+            .cycle()
+            .skip(current_index + 1)
+            .take(info_modes.len())
+            .find(|info_mode| **info_mode != self.info_mode)
+            .copied()
+    }
+
+    pub(in crate::app) fn toggle_split_view(&mut self) {
+        self.settings.app.split_info_view = !self.settings.app.split_info_view;
+        if !self.settings.app.split_info_view {
+            return;
+        }
+        match self.get_split_info_mode() {
+            // the file might have been opened while the assembly view was not needed
+            Some(InfoMode::Assembly) => {
+                if self.assembly_offsets.is_empty() && !self.data.is_empty() {
+                    self.disassemble();
+                }
+            }
+            Some(_) => {}
+            None => self.log(NotificationLevel::Warning, "There is no other view to show."),
+        }
+    }
+
     pub(in crate::app) fn request_view_change(&mut self) {
-        match self.info_mode {
-            InfoMode::Text => {
-                self.info_mode = InfoMode::Assembly;
+        let info_modes = self.get_available_info_modes();
+        if info_modes.is_empty() {
+            self.log(NotificationLevel::Warning, "No views to cycle through.");
+            return;
+        }
+        let next_index = info_modes
+            .iter()
+            .position(|info_mode| *info_mode == self.info_mode)
+            .map(|index| (index + 1) % info_modes.len())
+            .unwrap_or(0);
+        self.set_info_mode(info_modes[next_index]);
+    }
+
+    pub(in crate::app) fn set_info_mode(&mut self, info_mode: InfoMode) {
+        if info_mode == InfoMode::Assembly {
+            if !self.is_disassembly_enabled() {
+                self.log(NotificationLevel::Warning, "Disassembly is disabled.");
+                return;
             }
-            InfoMode::Assembly => {
-                self.info_mode = InfoMode::Text;
+            // the file might have been opened while disassembly was disabled
+            if self.assembly_offsets.is_empty() && !self.data.is_empty() {
+                self.disassemble();
             }
         }
+        if info_mode != self.info_mode {
+            self.previous_info_mode = Some(self.info_mode);
+        }
+        if info_mode != InfoMode::Text {
+            self.focus = Focus::Hex;
+        }
+        self.info_mode = info_mode;
     }
 
-    pub(in crate::app) fn undo(&mut self) {
-        if let Some(change) = self.data.undo().cloned() {
-            let instruction_offset = self.get_instruction_at(change.offset()).file_address();
+    /// Switches back to the view that was shown before the current one.
+    pub(in crate::app) fn switch_to_previous_view(&mut self) {
+        match self.previous_info_mode {
+            Some(info_mode) => self.set_info_mode(info_mode),
+            None => self.log(NotificationLevel::Warning, "There is no previous view."),
+        }
+    }
+
+    /// Updates the view after a change was undone or redone.
+    fn refresh_after_history_change(&mut self, change: &Change) {
+        if change.size_delta() != 0 {
+            self.refresh_after_insertion();
+        } else if let Some(instruction_address) = self
+            .get_instruction_at_checked(change.offset())
+            .map(|instruction| instruction.file_address())
+        {
             let instruction_offset = change
                 .offset()
-                .checked_sub(instruction_offset as usize)
+                .checked_sub(instruction_address as usize)
                 .unwrap();
             self.edit_assembly(change.offset() + instruction_offset);
-        } else {
-            self.log(NotificationLevel::Warning, "Nothing to undo.")
         }
     }
 
+    /// Undoes the last change, the changes joined to it are undone too.
+    pub(in crate::app) fn undo(&mut self) {
+        let Some(mut change) = self.data.undo().cloned() else {
+            self.log(NotificationLevel::Warning, "Nothing to undo.");
+            return;
+        };
+        self.refresh_after_history_change(&change);
+        while change.is_joined() {
+            let Some(previous) = self.data.undo().cloned() else {
+                break;
+            };
+            self.refresh_after_history_change(&previous);
+            change = previous;
+        }
+        self.scroll_to_edit(change.offset(), true);
+    }
+
+    /// Redoes the next change, the changes joined to it are redone too.
     pub(in crate::app) fn redo(&mut self) {
-        if let Some(change) = self.data.redo().cloned() {
-            let instruction_offset = self.get_instruction_at(change.offset()).file_address();
-            let instruction_offset = change
-                .offset()
-                .checked_sub(instruction_offset as usize)
-                .unwrap();
-            self.edit_assembly(change.offset() + instruction_offset);
+        let Some(change) = self.data.redo().cloned() else {
+            self.log(NotificationLevel::Warning, "Nothing to redo.");
+            return;
+        };
+        self.refresh_after_history_change(&change);
+        while self.data.history().is_next_joined() {
+            if let Some(next) = self.data.redo().cloned() {
+                self.refresh_after_history_change(&next);
+            }
+        }
+        self.scroll_to_edit(change.offset(), true);
+    }
+
+    /// Undoes or redoes the changes until the first `index` changes of the history are applied.
+    /// Joined changes are undone together, so an index in the middle of them moves to their end.
+    pub(in crate::app) fn move_in_history(&mut self, index: usize) {
+        let changes = self.data.history().changes();
+        let mut index = index.min(changes.len());
+        while changes.get(index).is_some_and(Change::is_joined) {
+            index += 1;
+        }
+        while self.data.history().current() > index {
+            self.undo();
+        }
+        while self.data.history().current() < index {
+            self.redo();
+        }
+    }
+
+    /// Describes the state of the data after the first `index` changes of the history,
+    /// a change joined to the one before it is marked with `↳`.
+    pub(in crate::app) fn get_history_entry(&self, index: usize) -> String {
+        let Some(change) = index
+            .checked_sub(1)
+            .and_then(|i| self.data.history().changes().get(i))
+        else {
+            return if self.data.history_truncated() {
+                "Oldest kept state".to_string()
+            } else {
+                "Original data".to_string()
+            };
+        };
+        let format_bytes = |bytes: &[u8]| {
+            let mut text = bytes
+                .iter()
+                .take(8)
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if bytes.len() > 8 {
+                text.push_str(" …");
+            }
+            text
+        };
+        let joined = if change.is_joined() { "↳ " } else { "" };
+        if change.old().is_empty() {
+            format!(
+                "{}: {}{:X} insert {}",
+                index,
+                joined,
+                change.offset(),
+                format_bytes(change.new_bytes())
+            )
         } else {
-            self.log(NotificationLevel::Warning, "Nothing to redo.")
+            format!(
+                "{}: {}{:X} {} → {}",
+                index,
+                joined,
+                change.offset(),
+                format_bytes(change.old()),
+                format_bytes(change.new_bytes())
+            )
         }
     }
+
+    pub(in crate::app) fn request_popup_history(&mut self) {
+        if self.data.history().changes().is_empty() {
+            self.log(NotificationLevel::Warning, "The history is empty.");
+            return;
+        }
+        self.popup = Some(PopupState::History(self.data.history().current()));
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::app::asm::assembly_line::AssemblyLine;
+    use crate::app::{asm::assembly_line::AssemblyLine, plugins::plugin_manager::PluginManager};
 
     use super::*;
 
@@ -284,4 +770,153 @@ mod test {
             panic!("Expected an instruction.")
         }
     }
+
+    #[test]
+    fn test_plugin_command_status() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("progress.lua"),
+            "
+            function init(context)
+                context.add_command(\"progress\", \"Shows the progress\")
+            end
+
+            function progress(context)
+                context.set_status(\"Working\")
+            end
+            ",
+        )
+        .unwrap();
+        let mut app = App::mockup(vec![0; 0x100]);
+        let mut app_context = get_app_context!(app);
+        app.plugin_manager = PluginManager::load(Some(dir.path()), &mut app_context).unwrap();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(80, 25)).unwrap();
+        app.draw(&mut terminal).unwrap();
+        let screen = terminal.backend().buffer().clone();
+
+        // only the status bar is written over the screen
+        App::print_status_over(&app.settings.color, "Working", &mut terminal).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |buffer: &ratatui::buffer::Buffer, y| {
+            (0..80).map(|x| buffer[(x, y)].symbol()).collect::<String>()
+        };
+        assert!(row(buffer, 24).contains("Working"));
+        assert_eq!(row(buffer, 0), row(&screen, 0));
+
+        // the command runs after the popup is drawn away
+        app.run_command("progress", 0, &mut terminal).unwrap();
+        assert_eq!(app.pending_plugin_command.as_deref(), Some("progress"));
+        app.run_pending_plugin_command(&mut terminal).unwrap();
+        assert!(app.pending_plugin_command.is_none());
+        assert_eq!(app.logger[app.logger.len() - 1].message, "Working");
+    }
+
+    #[test]
+    fn test_top_command() {
+        assert_eq!(App::split_command_argument(" top "), ("top", ""));
+        assert_eq!(
+            App::split_command_argument("top  0x10 + 4"),
+            ("top", "0x10 + 4")
+        );
+
+        let mut app = App::mockup(vec![0; 0x1000]);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(80, 24)).unwrap();
+        app.resize_to_size(80, 24);
+        let bytes_per_row = app.block_size * app.blocks_per_row;
+        app.run_command("top 0x300", 0, &mut terminal).unwrap();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x300);
+        assert_eq!(app.scroll, 0x300 / bytes_per_row);
+        assert_eq!(app.cursor.1, 0);
+
+        app.run_command("top 0x2000", 0, &mut terminal).unwrap();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x300);
+    }
+
+    #[test]
+    fn test_history_popup() {
+        let mut app = App::mockup(vec![0; 4]);
+        app.request_popup_history();
+        assert!(app.popup.is_none());
+        app.data.set(0, 1).unwrap();
+        app.data.set(1, 2).unwrap();
+        app.data.set(2, 3).unwrap();
+        app.request_popup_history();
+        assert!(matches!(app.popup, Some(PopupState::History(3))));
+        assert_eq!(app.get_history_entry(0), "Original data");
+        assert_eq!(app.get_history_entry(2), "2: 1 00 → 02");
+
+        app.move_in_history(1);
+        assert_eq!(app.data.bytes(), [1, 0, 0, 0]);
+        app.move_in_history(3);
+        assert_eq!(app.data.bytes(), [1, 2, 3, 0]);
+        app.move_in_history(0);
+        assert_eq!(app.data.bytes(), [0; 4]);
+        app.move_in_history(usize::MAX);
+        assert_eq!(app.data.bytes(), [1, 2, 3, 0]);
+
+        // joined changes are listed one by one but applied together
+        app.data.push_changes([(0, vec![4, 5]), (3, vec![6])]);
+        assert_eq!(app.get_history_entry(4), "4: 0 01 02 → 04 05");
+        assert_eq!(app.get_history_entry(5), "5: ↳ 3 00 → 06");
+        app.move_in_history(4);
+        assert_eq!(app.data.bytes(), [4, 5, 3, 6]);
+        app.move_in_history(3);
+        assert_eq!(app.data.bytes(), [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_view_change() {
+        let mut app = App::mockup(vec![0x90; 4]);
+        assert_eq!(app.info_mode, InfoMode::Text);
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Text);
+
+        app.settings.app.info_modes = vec![InfoMode::Assembly];
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+
+        app.settings.app.info_modes = vec![];
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+    }
+
+    #[test]
+    fn test_previous_view() {
+        let mut app = App::mockup(vec![0x90; 4]);
+        app.switch_to_previous_view();
+        assert_eq!(app.info_mode, InfoMode::Text);
+        app.set_info_mode(InfoMode::Assembly);
+        app.switch_to_previous_view();
+        assert_eq!(app.info_mode, InfoMode::Text);
+        app.switch_to_previous_view();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+        // selecting the current view again does not forget the previous one
+        app.set_info_mode(InfoMode::Assembly);
+        app.switch_to_previous_view();
+        assert_eq!(app.info_mode, InfoMode::Text);
+    }
+
+    #[test]
+    fn test_toggle_disassembly() {
+        let mut app = App::mockup(vec![0x90; 4]);
+        app.toggle_disassembly();
+        assert!(app.assembly_instructions.is_empty());
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Text);
+        app.set_info_mode(InfoMode::Assembly);
+        assert_eq!(app.info_mode, InfoMode::Text);
+        app.insert_text("A");
+        assert_eq!(app.data.bytes(), &[b'A', 0x90, 0x90, 0x90]);
+        app.undo();
+        assert_eq!(app.data.bytes(), &[0x90; 4]);
+
+        app.settings.app.disassembly = true;
+        app.request_view_change();
+        assert_eq!(app.info_mode, InfoMode::Assembly);
+        assert!(!app.assembly_instructions.is_empty());
+    }
 }
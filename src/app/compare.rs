@@ -0,0 +1,249 @@
+use std::error::Error;
+
+use super::{log::NotificationLevel, App};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareResult {
+    /// The bytes differ at the given index.
+    Difference(usize),
+    /// All the bytes of the other file match, the data may continue after them.
+    Match(usize),
+    /// The data ends before the other file, all the bytes until then match.
+    DataEnded(usize),
+}
+
+impl CompareResult {
+    pub fn compare(data: &[u8], other: &[u8]) -> Self {
+        match data.iter().zip(other).position(|(a, b)| a != b) {
+            Some(index) => CompareResult::Difference(index),
+            None if data.len() < other.len() => CompareResult::DataEnded(data.len()),
+            None => CompareResult::Match(other.len()),
+        }
+    }
+
+    /// The indices of all the bytes that differ, the longer slice is truncated.
+    pub fn differences(data: &[u8], other: &[u8]) -> Vec<usize> {
+        data.iter()
+            .zip(other)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl App {
+    /// Compares the bytes starting at `at_offset` with the contents of the file at `path`,
+    /// the cursor is moved to the first difference if there is one.
+    pub(super) fn compare_with_file(
+        &mut self,
+        path: &str,
+        at_offset: usize,
+    ) -> Result<CompareResult, Box<dyn Error>> {
+        let other = self.filesystem.read(path)?;
+        let data = self.data.bytes().get(at_offset..).unwrap_or_default();
+        let result = CompareResult::compare(data, &other);
+        match result {
+            CompareResult::Difference(index) => {
+                let message = format!(
+                    "First difference at {:X} after {} matching bytes: {:02X} here, {:02X} in \"{}\"",
+                    at_offset + index,
+                    index,
+                    data[index],
+                    other[index],
+                    path
+                );
+                self.log(NotificationLevel::Warning, &message);
+                self.jump_to_remembered(at_offset + index, false);
+            }
+            CompareResult::Match(len) => {
+                self.log(
+                    NotificationLevel::Info,
+                    &format!("All {} bytes of \"{}\" match at {:X}", len, path, at_offset),
+                );
+            }
+            CompareResult::DataEnded(len) => {
+                self.log(
+                    NotificationLevel::Warning,
+                    &format!(
+                        "The data ends after {} matching bytes, \"{}\" has {} more bytes",
+                        len,
+                        path,
+                        other.len() - len
+                    ),
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    /// The first call remembers the selection, the second one compares it with the remembered
+    /// selection and highlights the bytes that differ in both.
+    /// Calling it without a selection clears the comparison.
+    pub(super) fn compare_selections(&mut self) {
+        let Some((start, end)) = self.get_selection() else {
+            if self.compare_range.is_some() || !self.selection_differences.is_empty() {
+                self.compare_range = None;
+                self.selection_differences.clear();
+                self.log(NotificationLevel::Info, "Cleared the comparison");
+            } else {
+                self.log(NotificationLevel::Warning, "Select the bytes to compare.");
+            }
+            return;
+        };
+        let Some((other_start, other_end)) = self.compare_range.take() else {
+            self.compare_range = Some((start, end));
+            self.selection_differences.clear();
+            self.log(
+                NotificationLevel::Info,
+                &format!(
+                    "Marked {:X}..{:X}, select the other bytes and compare again",
+                    start, end
+                ),
+            );
+            return;
+        };
+        if other_end > self.data.len() {
+            self.log(
+                NotificationLevel::Warning,
+                "The marked bytes are no longer in the file, mark them again.",
+            );
+            return;
+        }
+        if end - start != other_end - other_start {
+            self.log(
+                NotificationLevel::Warning,
+                &format!(
+                    "The selections have different sizes: {} bytes at {:X} and {} bytes at {:X}",
+                    other_end - other_start,
+                    other_start,
+                    end - start,
+                    start
+                ),
+            );
+            return;
+        }
+        let bytes = self.data.bytes();
+        let differences =
+            CompareResult::differences(&bytes[other_start..other_end], &bytes[start..end]);
+        let mut offsets = differences
+            .iter()
+            .flat_map(|&index| [other_start + index, start + index])
+            .collect::<Vec<_>>();
+        offsets.sort_unstable();
+        offsets.dedup();
+        match differences.first() {
+            Some(&index) => self.log(
+                NotificationLevel::Warning,
+                &format!(
+                    "{} of {} bytes differ, the first at {:X} and {:X}",
+                    differences.len(),
+                    end - start,
+                    other_start + index,
+                    start + index
+                ),
+            ),
+            None => self.log(
+                NotificationLevel::Info,
+                &format!("All {} bytes match", end - start),
+            ),
+        }
+        self.selection_differences = offsets;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_compare_bytes() {
+        assert_eq!(
+            CompareResult::compare(&[1, 2, 3], &[1, 2, 4]),
+            CompareResult::Difference(2)
+        );
+        assert_eq!(
+            CompareResult::compare(&[1, 2, 3], &[1, 2]),
+            CompareResult::Match(2)
+        );
+        assert_eq!(
+            CompareResult::compare(&[1, 2], &[1, 2, 3]),
+            CompareResult::DataEnded(2)
+        );
+        assert_eq!(CompareResult::compare(&[], &[]), CompareResult::Match(0));
+    }
+
+    #[test]
+    fn test_compare_with_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[4, 5, 6, 0xFF]).unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        assert_eq!(
+            app.compare_with_file(&path, 4).unwrap(),
+            CompareResult::Difference(3)
+        );
+        assert_eq!(app.get_cursor_position().global_byte_index, 7);
+        assert_eq!(
+            app.compare_with_file(&path, 0x3E).unwrap(),
+            CompareResult::Difference(0)
+        );
+        file.as_file_mut().set_len(3).unwrap();
+        assert_eq!(
+            app.compare_with_file(&path, 4).unwrap(),
+            CompareResult::Match(3)
+        );
+    }
+
+    #[test]
+    fn test_compare_selections() {
+        let mut data = b"ABCDxxABXDyyABCD".to_vec();
+        data.extend([0; 0x10]);
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.compare_selections();
+        assert!(app.compare_range.is_none());
+
+        app.selection_anchor = Some(0);
+        app.jump_to(3, false);
+        app.compare_selections();
+        assert_eq!(app.compare_range, Some((0, 4)));
+
+        app.selection_anchor = Some(6);
+        app.jump_to(10, false);
+        app.compare_selections();
+        assert_eq!(app.compare_range, None);
+        assert!(app.selection_differences.is_empty());
+
+        app.selection_anchor = Some(0);
+        app.jump_to(3, false);
+        app.compare_selections();
+        app.selection_anchor = Some(6);
+        app.jump_to(9, false);
+        app.compare_selections();
+        assert_eq!(app.selection_differences, vec![2, 8]);
+
+        app.selection_anchor = Some(12);
+        app.jump_to(15, false);
+        app.compare_selections();
+        app.selection_anchor = Some(0);
+        app.jump_to(3, false);
+        app.compare_selections();
+        assert!(app.selection_differences.is_empty());
+
+        app.selection_differences = vec![2];
+        app.selection_anchor = None;
+        app.compare_selections();
+        assert!(app.selection_differences.is_empty());
+
+        app.compare_range = Some((0x1E, 0x22));
+        app.selection_anchor = Some(0);
+        app.jump_to(3, false);
+        app.compare_selections();
+        assert!(app.compare_range.is_none());
+        assert!(app.selection_differences.is_empty());
+    }
+}
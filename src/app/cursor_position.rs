@@ -1,4 +1,8 @@
-use super::{data::Data, log::NotificationLevel, App};
+use ratatui::layout::{Position, Rect};
+
+use crate::headers::section::Section;
+
+use super::{data::Data, focus::Focus, log::NotificationLevel, App};
 
 pub struct CursorPosition {
     pub cursor: Option<(u16, u16)>,
@@ -64,6 +68,29 @@ impl App {
             high_byte,
         }
     }
+    /// Where the terminal cursor is shown if the `terminal_cursor` setting is on,
+    /// on the digit of the hex view or on the character of the text view that has the focus.
+    /// Returns `None` while a popup is open.
+    pub(super) fn get_terminal_cursor(&self, hex_view: Rect, info_view: Rect) -> Option<Position> {
+        if !self.settings.app.terminal_cursor || self.popup.is_some() || self.data.is_empty() {
+            return None;
+        }
+        // the views have a border on the top, the hex view also on the left
+        let position = match self.focus {
+            Focus::Hex => Position::new(
+                hex_view.x + 1 + self.cursor.0,
+                hex_view.y + 1 + self.cursor.1,
+            ),
+            Focus::Text => {
+                let position = self.get_cursor_position();
+                // every byte is a character and a space, every block is followed by another space
+                let x = position.line_byte_index * 2 + position.local_block_index;
+                Position::new(info_view.x + x as u16, info_view.y + 1 + self.cursor.1)
+            }
+        };
+        Some(position)
+    }
+
     pub(super) fn get_cursor_position(&self) -> CursorPosition {
         Self::get_cursor_position_no_self(
             &self.data,
@@ -137,7 +164,7 @@ impl App {
                 if let Some(symbol) = symbols.iter().nth(scroll) {
                     let (address, name) = symbol;
                     let log_message = format!("Jumping to symbol {} at {:#X}", name, address);
-                    self.jump_to(*address as usize, true);
+                    self.jump_to_remembered(*address as usize, true);
                     self.log(NotificationLevel::Debug, &log_message);
                 } else {
                     unreachable!("The scroll should not be greater than the number of symbols")
@@ -161,12 +188,108 @@ impl App {
                 NotificationLevel::Debug,
                 &format!("Jumping to symbol {} at {:#X}", name, address),
             );
-            self.jump_to(*address as usize, true);
+            self.jump_to_remembered(*address as usize, true);
         } else {
             unreachable!("The scroll should not be greater than the number of symbols");
         }
     }
 
+    pub(super) fn jump_to_overlay(&mut self) {
+        if let Some((offset, size)) = self.header.get_overlay(self.data.len() as u64) {
+            self.log(
+                NotificationLevel::Info,
+                &format!("Overlay: {} bytes at {:#X}", size, offset),
+            );
+            self.jump_to_remembered(offset as usize, false);
+        } else {
+            self.log(NotificationLevel::Warning, "There is no overlay data.");
+        }
+    }
+
+    /// Returns the non-empty sections that start inside the file, sorted by file offset.
+    fn get_sorted_sections(&self) -> Vec<Section> {
+        let mut sections = self.header.get_sections();
+        sections
+            .retain(|section| section.size > 0 && section.file_offset < self.data.len() as u64);
+        sections.sort_by_key(|section| section.file_offset);
+        sections
+    }
+
+    /// Returns the last section that starts before `offset` and contains it.
+    fn find_section(sections: &[Section], offset: u64) -> Option<&Section> {
+        let candidates = sections.partition_point(|section| section.file_offset <= offset);
+        sections[..candidates]
+            .iter()
+            .rev()
+            .find(|section| offset < section.file_offset.saturating_add(section.size))
+    }
+
+    /// Moves the cursor to the first or the last byte of the section that contains it.
+    pub(super) fn jump_to_section_boundary(&mut self, end: bool) {
+        let sections = self.get_sorted_sections();
+        if sections.is_empty() {
+            self.log(NotificationLevel::Warning, "The file has no sections.");
+            return;
+        }
+        let offset = self.get_cursor_position().global_byte_index as u64;
+        let target = match Self::find_section(&sections, offset) {
+            Some(section) if end => section
+                .file_offset
+                .saturating_add(section.size)
+                .min(self.data.len() as u64)
+                - 1,
+            Some(section) => section.file_offset,
+            None => {
+                self.log(NotificationLevel::Warning, "The cursor is not in a section.");
+                return;
+            }
+        };
+        self.jump_to_remembered(target as usize, false);
+    }
+
+    /// Moves the cursor to the start of the next section or of the one before the current section.
+    pub(super) fn jump_to_adjacent_section(&mut self, next: bool) {
+        let sections = self.get_sorted_sections();
+        if sections.is_empty() {
+            self.log(NotificationLevel::Warning, "The file has no sections.");
+            return;
+        }
+        let offset = self.get_cursor_position().global_byte_index as u64;
+        let target = if next {
+            sections.iter().find(|section| section.file_offset > offset)
+        } else {
+            let current_start = Self::find_section(&sections, offset)
+                .map(|section| section.file_offset)
+                .unwrap_or(offset);
+            sections
+                .iter()
+                .rev()
+                .find(|section| section.file_offset < current_start)
+        };
+        match target {
+            Some(section) => self.jump_to_section(section),
+            None if next => self.log(NotificationLevel::Warning, "There is no next section."),
+            None => self.log(
+                NotificationLevel::Warning,
+                "There is no previous section.",
+            ),
+        }
+    }
+
+    /// Returns the sections whose name contains `filter`, ignoring the case, sorted by file offset.
+    pub(super) fn find_sections(&self, filter: &str) -> Vec<Section> {
+        let filter = filter.to_lowercase();
+        let mut sections = self.get_sorted_sections();
+        sections.retain(|section| section.name.to_lowercase().contains(&filter));
+        sections
+    }
+
+    pub(super) fn jump_to_section(&mut self, section: &Section) {
+        let message = format!("Section: {}", section);
+        self.log(NotificationLevel::Info, &message);
+        self.jump_to_remembered(section.file_offset as usize, false);
+    }
+
     pub(super) fn jump_to_symbol(&mut self, symbol: &str) {
         if let Some(address) = symbol.strip_prefix("0x") {
             if let Ok(address) = usize::from_str_radix(address, 16) {
@@ -174,7 +297,7 @@ impl App {
                     NotificationLevel::Debug,
                     &format!("Jumping to address: {:#X}", address),
                 );
-                self.jump_to(address, false);
+                self.jump_to_remembered(address, false);
             } else {
                 self.log(
                     NotificationLevel::Error,
@@ -187,7 +310,7 @@ impl App {
                     NotificationLevel::Debug,
                     &format!("Jumping to virtual address: {:#X}", address),
                 );
-                self.jump_to(address, true);
+                self.jump_to_remembered(address, true);
             } else {
                 self.log(
                     NotificationLevel::Error,
@@ -199,7 +322,7 @@ impl App {
                 NotificationLevel::Debug,
                 &format!("Jumping to symbol {} at {:#X}", symbol, address),
             );
-            self.jump_to(address as usize, true);
+            self.jump_to_remembered(address as usize, true);
         } else if let Some(address) = self
             .header
             .get_sections()
@@ -211,7 +334,7 @@ impl App {
                 NotificationLevel::Debug,
                 &format!("Jumping to section {} at {:#X}", symbol, address),
             );
-            self.jump_to(address as usize, false);
+            self.jump_to_remembered(address as usize, false);
         } else {
             self.log(
                 NotificationLevel::Error,
@@ -292,6 +415,45 @@ impl App {
         )
     }
 
+    /// Scrolls so that the row of the cursor is the first one shown, the cursor stays on the same byte.
+    /// Near the end of the file the view stops at the last full page.
+    pub(super) fn scroll_cursor_to_top(&mut self) {
+        if self.data.is_empty() || self.screen_size.1 <= self.vertical_margin {
+            return;
+        }
+        let position = self.get_cursor_position();
+        let visible_rows = (self.screen_size.1 - self.vertical_margin) as usize;
+        let bytes_per_row = self.block_size * self.blocks_per_row;
+        let rows = self.data.len().div_ceil(bytes_per_row);
+        let top_row = position.line_index.min(rows.saturating_sub(visible_rows));
+        self.scroll = top_row;
+        self.cursor.1 = (position.line_index - top_row) as u16;
+    }
+
+    /// Moves the cursor back to `offset` after an edit, scrolling only if it would be off-screen,
+    /// plugins can change the scroll while handling the edit, so this keeps the two in sync.
+    /// Does nothing if `auto_scroll` is disabled.
+    pub(super) fn scroll_to_edit(&mut self, offset: usize, high_byte: bool) {
+        if !self.settings.app.auto_scroll
+            || self.data.is_empty()
+            || self.screen_size.1 <= self.vertical_margin
+        {
+            return;
+        }
+        let offset = offset.min(self.data.len() - 1);
+        let visible_rows = (self.screen_size.1 - self.vertical_margin) as usize;
+        let row = offset / (self.block_size * self.blocks_per_row);
+        if row < self.scroll {
+            self.scroll = row;
+        } else if row >= self.scroll + visible_rows {
+            self.scroll = row + 1 - visible_rows;
+        }
+        self.cursor = self
+            .get_expected_cursor_position(offset, high_byte)
+            .cursor
+            .expect("The scroll should be adequate for the cursor to be visible");
+    }
+
     pub(super) fn move_cursor(&mut self, dx: isize, dy: isize, best_effort: bool) {
         if self.screen_size.1 <= self.vertical_margin {
             return;
@@ -381,8 +543,17 @@ impl App {
             next_instruction_index = next_instruction_index.saturating_sub(1);
         }
 
-        let target_address = self.assembly_instructions[next_instruction_index].file_address();
-        self.jump_to(target_address as usize, false);
+        let mut target_address =
+            self.assembly_instructions[next_instruction_index].file_address() as usize;
+        // a folded range is skipped as a whole
+        if let Some(fold) = self.get_fold_at(target_address) {
+            target_address = if instruction_count > 0 && fold.contains(current_offset) {
+                fold.end.min(self.data.len().saturating_sub(1))
+            } else {
+                fold.start
+            };
+        }
+        self.jump_to(target_address, false);
     }
 }
 
@@ -445,6 +616,86 @@ mod test {
         assert_eq!(app.cursor, (0, 0));
     }
 
+    #[test]
+    fn test_scroll_to_edit() {
+        let data = vec![0; 0x1000];
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        let visible_rows = (app.screen_size.1 - app.vertical_margin) as usize;
+        let bytes_per_row = app.block_size * app.blocks_per_row;
+
+        // the last byte of the last visible row
+        let offset = visible_rows * bytes_per_row - 1;
+        app.jump_to(offset, false);
+        app.edit_data('f').unwrap();
+        assert_eq!(app.get_cursor_position().global_byte_index, offset);
+        assert!(app.get_cursor_position().high_byte);
+        assert!((app.cursor.1 as usize) < visible_rows);
+        assert_eq!(app.data.bytes()[offset], 0xF0);
+
+        app.jump_to(0x800, false);
+        app.edit_data('1').unwrap();
+        app.jump_to(0, false);
+        app.undo();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x800);
+        assert!((app.cursor.1 as usize) < visible_rows);
+
+        app.settings.app.auto_scroll = false;
+        app.jump_to(0, false);
+        app.redo();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+    }
+
+    #[test]
+    fn test_scroll_cursor_to_top() {
+        let mut app = App::mockup(vec![0; 0x1000]);
+        app.resize_to_size(80, 24);
+        let visible_rows = (app.screen_size.1 - app.vertical_margin) as usize;
+        let bytes_per_row = app.block_size * app.blocks_per_row;
+        let rows = 0x1000usize.div_ceil(bytes_per_row);
+
+        app.jump_to(bytes_per_row * 3 + 1, false);
+        app.scroll_cursor_to_top();
+        assert_eq!(app.scroll, 3);
+        assert_eq!(app.cursor.1, 0);
+        assert_eq!(
+            app.get_cursor_position().global_byte_index,
+            bytes_per_row * 3 + 1
+        );
+
+        app.jump_to(0x1000 - 1, false);
+        app.jump_to(0x1000 - visible_rows / 2 * bytes_per_row, false);
+        app.scroll_cursor_to_top();
+        assert_eq!(app.scroll, rows - visible_rows);
+        assert_eq!(
+            app.get_cursor_position().global_byte_index,
+            0x1000 - visible_rows / 2 * bytes_per_row
+        );
+    }
+
+    #[test]
+    fn test_terminal_cursor() {
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.resize_to_size(80, 24);
+        let hex_view = Rect::new(10, 0, 26, 23);
+        let info_view = Rect::new(36, 0, 20, 23);
+        app.jump_to(10, false);
+        assert_eq!(app.get_terminal_cursor(hex_view, info_view), None);
+
+        app.settings.app.terminal_cursor = true;
+        assert_eq!(
+            app.get_terminal_cursor(hex_view, info_view),
+            Some(Position::new(17, 2))
+        );
+        app.focus = Focus::Text;
+        assert_eq!(
+            app.get_terminal_cursor(hex_view, info_view),
+            Some(Position::new(40, 2))
+        );
+        app.request_popup_jump();
+        assert_eq!(app.get_terminal_cursor(hex_view, info_view), None);
+    }
+
     #[test]
     fn test_move_with_small_screen() {
         let data = vec![0; 0x100];
@@ -497,4 +748,72 @@ mod test {
         let current_position = app.get_cursor_position().global_byte_index;
         assert_eq!(current_position, 0);
     }
+
+    #[test]
+    fn test_section_navigation() {
+        use std::collections::HashMap;
+
+        use crate::headers::{bitness::Bitness, custom_header::CustomHeader, Header};
+
+        let section = |name: &str, file_offset, size| Section {
+            name: name.to_string(),
+            virtual_address: file_offset,
+            file_offset,
+            size,
+        };
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.resize_to_size(80, 24);
+        app.jump_to(0x10, false);
+        app.jump_to_section_boundary(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+
+        app.header = Header::CustomHeader(CustomHeader {
+            bitness: Bitness::Bit64,
+            entry: 0,
+            endianness: object::Endianness::Little,
+            architecture: object::Architecture::X86_64,
+            sections: vec![
+                section(".data", 0x40, 0x20),
+                section(".text", 0x10, 0x20),
+                section(".bss", 0xF0, 0x100),
+            ],
+            symbols: HashMap::new(),
+            symbols_by_name: HashMap::new(),
+        });
+
+        app.jump_to(0x18, false);
+        app.jump_to_section_boundary(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x2F);
+        app.jump_to_section_boundary(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+        app.jump_to_adjacent_section(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x40);
+        app.jump_to_adjacent_section(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0xF0);
+        app.jump_to_section_boundary(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0xFF);
+        app.jump_to_adjacent_section(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0xFF);
+        app.jump_to_adjacent_section(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x40);
+
+        app.jump_to(0x35, false);
+        app.jump_to_section_boundary(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x35);
+        app.jump_to_adjacent_section(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+
+        let names = |sections: Vec<Section>| {
+            sections
+                .into_iter()
+                .map(|section| section.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(app.find_sections("")), [".text", ".data", ".bss"]);
+        assert_eq!(names(app.find_sections("TA")), [".data"]);
+        assert!(app.find_sections("rodata").is_empty());
+        let data = app.find_sections("data").remove(0);
+        app.jump_to_section(&data);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x40);
+    }
 }
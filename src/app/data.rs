@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::history::{change::Change, History};
 
 #[derive(Debug, Clone, Default)]
@@ -5,14 +7,26 @@ pub struct Data {
     bytes: Vec<u8>,
     history: History,
     dirty: bool,
+    /// The value each modified byte had when the file was last saved.
+    saved_bytes: HashMap<usize, u8>,
+    /// If true every change is refused.
+    read_only: bool,
+    /// The length of the data when the file was last saved.
+    saved_len: usize,
+    /// Where bytes were inserted or removed since the last call to `take_shifts`.
+    shifts: Vec<(usize, isize)>,
 }
 
 impl Data {
     pub fn new(bytes: Vec<u8>, history_limit: usize) -> Self {
         Self {
+            saved_len: bytes.len(),
             bytes,
             history: History::with_limit(history_limit),
             dirty: false,
+            saved_bytes: HashMap::new(),
+            read_only: false,
+            shifts: Vec::new(),
         }
     }
 
@@ -21,11 +35,16 @@ impl Data {
     }
 
     pub fn set(&mut self, i: usize, byte: u8) -> Result<(), mlua::Error> {
+        if self.read_only {
+            return Err(mlua::Error::external("the file is read-only"));
+        }
         match self.bytes.get_mut(i) {
             Some(b) => {
-                self.history.push(Change::new(i, &[*b], &[byte]));
+                let old = *b;
+                self.history.push(Change::new(i, &[old], &[byte]));
                 *b = byte;
                 self.dirty = true;
+                Self::track_changes(&mut self.saved_bytes, &self.bytes, i, &[old]);
                 Ok(())
             }
             None => Err(mlua::Error::external("index out of bounds")),
@@ -36,18 +55,160 @@ impl Data {
         &self.bytes
     }
 
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
     pub fn dirty(&self) -> bool {
         self.dirty
     }
 
     pub fn reset_dirty(&mut self) {
         self.dirty = false;
+        self.saved_bytes.clear();
+        self.saved_len = self.bytes.len();
+    }
+
+    /// Returns the number of bytes that differ from the last saved version,
+    /// inserted bytes count as changed.
+    pub fn changed_bytes(&self) -> usize {
+        self.saved_bytes.len() + self.bytes.len().abs_diff(self.saved_len)
+    }
+
+    /// Returns true if bytes were inserted since the last save.
+    pub fn size_changed(&self) -> bool {
+        self.bytes.len() != self.saved_len
+    }
+
+    /// Returns the offsets of the bytes that differ from the last saved version,
+    /// sorted, with the value they had when the file was saved.
+    pub fn changes(&self) -> Vec<(usize, u8)> {
+        let mut changes = self
+            .saved_bytes
+            .iter()
+            .map(|(&offset, &old)| (offset, old))
+            .collect::<Vec<_>>();
+        changes.sort_unstable();
+        changes
+    }
+
+    /// Updates the saved value of the bytes in `offset..offset + before.len()`,
+    /// `before` contains the values those bytes had before the last change.
+    fn track_changes(
+        saved_bytes: &mut HashMap<usize, u8>,
+        bytes: &[u8],
+        offset: usize,
+        before: &[u8],
+    ) {
+        for (i, &old) in before.iter().enumerate() {
+            let saved = *saved_bytes.entry(offset + i).or_insert(old);
+            if saved == bytes[offset + i] {
+                saved_bytes.remove(&(offset + i));
+            }
+        }
+    }
+
+    /// Returns the places where bytes were inserted or removed since the last call, in order,
+    /// as (offset, delta): `delta` bytes were inserted at `offset`,
+    /// or removed from `offset` if `delta` is negative.
+    pub fn take_shifts(&mut self) -> Vec<(usize, isize)> {
+        std::mem::take(&mut self.shifts)
+    }
+
+    /// Moves the saved values of the bytes after `offset` by `delta` after an insertion,
+    /// or after the inserted bytes are removed if `delta` is negative.
+    fn shift_saved_bytes(saved_bytes: &mut HashMap<usize, u8>, offset: usize, delta: isize) {
+        *saved_bytes = saved_bytes
+            .drain()
+            .filter_map(|(i, old)| {
+                if i < offset {
+                    Some((i, old))
+                } else if delta < 0 && i < offset + delta.unsigned_abs() {
+                    None
+                } else {
+                    Some((i.checked_add_signed(delta)?, old))
+                }
+            })
+            .collect();
+    }
+
+    /// Inserts `new` at `offset` as a single change in the history, the bytes after it are shifted.
+    /// Returns the number of bytes inserted, that is 0 if the data is read-only.
+    /// Panics if the offset is past the end of the data.
+    pub fn insert(&mut self, offset: usize, new: Vec<u8>) -> usize {
+        if self.read_only || new.is_empty() {
+            return 0;
+        }
+        if offset > self.bytes.len() {
+            panic!(
+                "Offset {} out of bounds for data of length {}",
+                offset,
+                self.bytes.len()
+            );
+        }
+        let change = Change::insertion(offset, &new);
+        change.apply(&mut self.bytes);
+        self.history.push(change);
+        self.dirty = true;
+        Self::shift_saved_bytes(&mut self.saved_bytes, offset, new.len() as isize);
+        self.shifts.push((offset, new.len() as isize));
+        new.len()
+    }
+
+    /// Replaces the `len` bytes at `offset` with `new` as a single change in the history,
+    /// the bytes after them are shifted if the length changes.
+    /// Returns false if nothing changed or the data is read-only.
+    /// Panics if the range is out of bounds.
+    pub fn replace(&mut self, offset: usize, len: usize, new: Vec<u8>) -> bool {
+        if self.read_only {
+            return false;
+        }
+        if offset + len > self.bytes.len() {
+            panic!(
+                "Range {}..{} out of bounds for data of length {}",
+                offset,
+                offset + len,
+                self.bytes.len()
+            );
+        }
+        let old = &self.bytes[offset..offset + len];
+        if old == new.as_slice() {
+            return false;
+        }
+        let change = Change::replacement(offset, old, &new);
+        change.apply(&mut self.bytes);
+        let overwritten = change.overwritten_len();
+        Self::shift_saved_bytes(
+            &mut self.saved_bytes,
+            offset + overwritten,
+            change.size_delta(),
+        );
+        if change.size_delta() != 0 {
+            self.shifts
+                .push((offset + overwritten, change.size_delta()));
+        }
+        Self::track_changes(
+            &mut self.saved_bytes,
+            &self.bytes,
+            offset,
+            &change.old()[..overwritten],
+        );
+        self.history.push(change);
+        self.dirty = true;
+        true
     }
 
     /// Pushes a change to the history and updates the data.
-    /// Returns the number of bytes changed.
+    /// Returns the number of bytes changed, that is 0 if the data is read-only.
     /// Panics if the offset is out of bounds.
     pub fn push_change(&mut self, offset: usize, mut new: Vec<u8>) -> usize {
+        if self.read_only {
+            return 0;
+        }
         if offset >= self.bytes.len() {
             panic!(
                 "Offset {} out of bounds for data of length {}",
@@ -60,28 +221,90 @@ impl Data {
         if old == new.as_slice() {
             return 0;
         }
-        self.history.push(Change::new(offset, old, &new));
+        let old = old.to_vec();
+        self.history.push(Change::new(offset, &old, &new));
         self.bytes[offset..offset + new.len()].copy_from_slice(&new);
         self.dirty = true;
+        Self::track_changes(&mut self.saved_bytes, &self.bytes, offset, &old);
         new.len()
     }
 
+    /// Pushes the changes as a group that is undone and redone at once,
+    /// the changes that don't modify the data are left out of the group.
+    /// Returns the number of bytes changed, that is 0 if the data is read-only.
+    /// Panics if an offset is out of bounds.
+    pub fn push_changes(&mut self, changes: impl IntoIterator<Item = (usize, Vec<u8>)>) -> usize {
+        let mut pushed = 0;
+        let mut changed = 0;
+        for (offset, new) in changes {
+            let len = self.push_change(offset, new);
+            if len > 0 {
+                pushed += 1;
+                changed += len;
+            }
+        }
+        self.history.join_last(pushed);
+        changed
+    }
+
     /// Undo the last change.
     /// Returns the change that was undone, if any.
     pub fn undo(&mut self) -> Option<&Change> {
-        self.history.undo(&mut self.bytes)
+        let change = self.history.undo(&mut self.bytes)?;
+        let overwritten = change.overwritten_len();
+        if change.size_delta() != 0 {
+            Self::shift_saved_bytes(
+                &mut self.saved_bytes,
+                change.offset() + overwritten,
+                -change.size_delta(),
+            );
+            self.shifts
+                .push((change.offset() + overwritten, -change.size_delta()));
+        }
+        Self::track_changes(
+            &mut self.saved_bytes,
+            &self.bytes,
+            change.offset(),
+            &change.new_bytes()[..overwritten],
+        );
+        Some(change)
     }
 
     /// Redo the last change.
     /// Returns the change that was redone, if any.
     pub fn redo(&mut self) -> Option<&Change> {
-        self.history.redo(&mut self.bytes)
+        let change = self.history.redo(&mut self.bytes)?;
+        let overwritten = change.overwritten_len();
+        if change.size_delta() != 0 {
+            Self::shift_saved_bytes(
+                &mut self.saved_bytes,
+                change.offset() + overwritten,
+                change.size_delta(),
+            );
+            self.shifts
+                .push((change.offset() + overwritten, change.size_delta()));
+        }
+        Self::track_changes(
+            &mut self.saved_bytes,
+            &self.bytes,
+            change.offset(),
+            &change.old()[..overwritten],
+        );
+        Some(change)
     }
 
     pub fn clear_history(&mut self) {
         self.history.clear();
     }
 
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    pub fn history_truncated(&self) -> bool {
+        self.history.is_truncated()
+    }
+
     pub fn len(&self) -> usize {
         self.bytes.len()
     }
@@ -144,6 +367,20 @@ mod tests {
         assert_eq!(data.bytes(), &[9, 8, 9, 8, 9]);
     }
 
+    #[test]
+    fn test_data_push_changes() {
+        let mut data = Data::new(vec![0, 1, 2, 3, 4], 0);
+        assert_eq!(
+            data.push_changes([(0, vec![9]), (1, vec![1]), (3, vec![8, 7])]),
+            3
+        );
+        assert_eq!(data.bytes(), &[9, 1, 2, 8, 7]);
+        assert_eq!(data.history().changes().len(), 2);
+        assert!(data.history().changes()[1].is_joined());
+        data.set_read_only(true);
+        assert_eq!(data.push_changes([(0, vec![0])]), 0);
+    }
+
     #[test]
     fn test_data_clear_history() {
         let mut data = Data::new(vec![0, 1, 2, 3, 4], 0);
@@ -155,4 +392,82 @@ mod tests {
         data.undo();
         assert_eq!(data.bytes(), &[9, 8, 9, 8, 9]);
     }
+
+    #[test]
+    fn test_data_changed_bytes() {
+        let mut data = Data::new(vec![0, 1, 2, 3, 4], 0);
+        assert_eq!(data.changed_bytes(), 0);
+        data.push_change(2, vec![9, 8, 7]);
+        assert_eq!(data.changed_bytes(), 3);
+        data.push_change(2, vec![2, 8]);
+        assert_eq!(data.changed_bytes(), 2);
+        data.set(0, 5).unwrap();
+        assert_eq!(data.changed_bytes(), 3);
+        data.undo();
+        data.undo();
+        assert_eq!(data.changed_bytes(), 3);
+        data.undo();
+        assert_eq!(data.changed_bytes(), 0);
+        data.redo();
+        data.reset_dirty();
+        assert_eq!(data.changed_bytes(), 0);
+        data.undo();
+        assert_eq!(data.changed_bytes(), 3);
+    }
+
+    #[test]
+    fn test_data_insert() {
+        let mut data = Data::new(vec![0, 1, 2, 3, 4], 0);
+        data.push_change(3, vec![9]);
+        assert_eq!(data.insert(1, vec![7, 7]), 2);
+        assert_eq!(data.bytes(), &[0, 7, 7, 1, 2, 9, 4]);
+        assert_eq!(data.changed_bytes(), 3);
+        assert_eq!(data.changes(), vec![(5, 3)]);
+        assert!(data.size_changed());
+        let change = data.undo().unwrap();
+        assert_eq!(change.len(), 2);
+        assert!(!change.is_empty());
+        assert_eq!(data.bytes(), &[0, 1, 2, 9, 4]);
+        assert_eq!(data.take_shifts(), vec![(1, 2), (1, -2)]);
+        assert!(data.take_shifts().is_empty());
+        assert_eq!(data.changes(), vec![(3, 3)]);
+        assert!(!data.size_changed());
+        data.redo();
+        assert_eq!(data.bytes(), &[0, 7, 7, 1, 2, 9, 4]);
+        assert_eq!(data.changes(), vec![(5, 3)]);
+        assert_eq!(data.insert(7, vec![1]), 1);
+        assert_eq!(data.len(), 8);
+        data.reset_dirty();
+        assert_eq!(data.changed_bytes(), 0);
+        data.set_read_only(true);
+        assert_eq!(data.insert(0, vec![1]), 0);
+    }
+
+    #[test]
+    fn test_data_replace() {
+        let mut data = Data::new(vec![0, 1, 2, 3, 4], 0);
+        data.push_change(4, vec![9]);
+        assert!(data.replace(1, 2, vec![7, 7, 7]));
+        assert_eq!(data.bytes(), &[0, 7, 7, 7, 3, 9]);
+        assert_eq!(data.changes(), vec![(1, 1), (2, 2), (5, 4)]);
+        data.undo();
+        assert_eq!(data.bytes(), &[0, 1, 2, 3, 9]);
+        assert_eq!(data.changes(), vec![(4, 4)]);
+        data.redo();
+        assert_eq!(data.bytes(), &[0, 7, 7, 7, 3, 9]);
+        assert_eq!(data.changes(), vec![(1, 1), (2, 2), (5, 4)]);
+
+        assert!(data.replace(1, 3, vec![8]));
+        assert_eq!(data.bytes(), &[0, 8, 3, 9]);
+        assert_eq!(data.changes(), vec![(1, 1), (3, 4)]);
+        assert!(!data.replace(1, 1, vec![8]));
+        data.undo();
+        assert_eq!(data.bytes(), &[0, 7, 7, 7, 3, 9]);
+        data.redo();
+        assert_eq!(data.bytes(), &[0, 8, 3, 9]);
+        assert!(data.replace(0, 4, Vec::new()));
+        assert!(data.is_empty());
+        data.set_read_only(true);
+        assert!(!data.replace(0, 0, vec![1]));
+    }
 }
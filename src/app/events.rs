@@ -4,6 +4,9 @@ use ratatui::{backend::Backend, Terminal};
 use crate::get_app_context;
 
 use super::{
+    focus::Focus,
+    info_mode::InfoMode,
+    log::NotificationLevel,
     plugins::ui_location::point::Point,
     popup::{binary_choice::BinaryChoice, popup_state::PopupState, simple_choice::SimpleChoice},
     settings::key_settings::KeySettings,
@@ -17,14 +20,32 @@ impl App {
     ) -> Result<(), Box<dyn std::error::Error>> {
         match event {
             event::Event::Key(event) if event.kind == event::KeyEventKind::Press => {
+                // while the text view is focused the characters are typed instead of being keys
+                if let (Focus::Text, KeyCode::Char(c)) = (self.focus, event.code) {
+                    if !event
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                    {
+                        self.edit_text_data(c);
+                        return Ok(());
+                    }
+                }
                 if event == self.settings.key.up {
-                    self.move_cursor(0, -1, false);
+                    self.move_cursor_not_selecting(0, -1);
                 } else if event == self.settings.key.down {
-                    self.move_cursor(0, 1, false);
+                    self.move_cursor_not_selecting(0, 1);
                 } else if event == self.settings.key.left {
-                    self.move_cursor(-1, 0, false);
+                    self.move_cursor_not_selecting(-1, 0);
                 } else if event == self.settings.key.right {
-                    self.move_cursor(1, 0, false);
+                    self.move_cursor_not_selecting(1, 0);
+                } else if event == self.settings.key.select_up {
+                    self.move_cursor_selecting(0, -1);
+                } else if event == self.settings.key.select_down {
+                    self.move_cursor_selecting(0, 1);
+                } else if event == self.settings.key.select_left {
+                    self.move_cursor_selecting(-1, 0);
+                } else if event == self.settings.key.select_right {
+                    self.move_cursor_selecting(1, 0);
                 } else if event == self.settings.key.next {
                     match self.info_mode {
                         super::info_mode::InfoMode::Text => {
@@ -51,6 +72,18 @@ impl App {
                     self.move_cursor_to_start();
                 } else if event == self.settings.key.goto_end {
                     self.move_cursor_to_end();
+                } else if event == self.settings.key.section_start {
+                    self.jump_to_section_boundary(false);
+                } else if event == self.settings.key.section_end {
+                    self.jump_to_section_boundary(true);
+                } else if event == self.settings.key.previous_section {
+                    self.jump_to_adjacent_section(false);
+                } else if event == self.settings.key.next_section {
+                    self.jump_to_adjacent_section(true);
+                } else if event == self.settings.key.previous_offset {
+                    self.move_in_recent_offsets(false);
+                } else if event == self.settings.key.next_offset {
+                    self.move_in_recent_offsets(true);
                 } else if event == self.settings.key.quit {
                     self.request_quit();
                 } else if event == self.settings.key.save_as {
@@ -79,10 +112,64 @@ impl App {
                     self.request_popup_jump();
                 } else if event == self.settings.key.change_view {
                     self.request_view_change();
+                } else if event == self.settings.key.view_text {
+                    self.set_info_mode(InfoMode::Text);
+                } else if event == self.settings.key.view_assembly {
+                    self.set_info_mode(InfoMode::Assembly);
+                } else if event == self.settings.key.previous_view {
+                    self.switch_to_previous_view();
+                } else if event == self.settings.key.jump_to_instruction {
+                    self.jump_to_current_instruction();
+                } else if event == self.settings.key.jump_to_operand {
+                    self.jump_to_operand_target();
+                } else if event == self.settings.key.switch_focus {
+                    self.switch_focus();
+                } else if event == self.settings.key.select {
+                    self.toggle_selection();
+                } else if event == self.settings.key.swap_selection {
+                    self.swap_selection_anchor();
+                } else if event == self.settings.key.find_selection {
+                    self.find_selection();
+                } else if event == self.settings.key.find_next {
+                    self.find_next();
+                } else if event == self.settings.key.find_prev {
+                    self.find_prev();
+                } else if event == self.settings.key.copy {
+                    self.copy_selection(false);
+                } else if event == self.settings.key.copy_alternate {
+                    self.copy_selection(true);
+                } else if event == self.settings.key.pin {
+                    self.toggle_pin();
+                } else if event == self.settings.key.jump_to_pin {
+                    self.jump_to_pin();
+                } else if event == self.settings.key.set_mark {
+                    self.toggle_mark();
+                } else if event == self.settings.key.measure {
+                    self.log_mark_distance();
+                } else if event == self.settings.key.highlight_equal_bytes {
+                    self.toggle_highlight_equal_bytes();
+                } else if event == self.settings.key.toggle_hex_case {
+                    self.toggle_hex_case();
+                } else if event == self.settings.key.toggle_split_view {
+                    self.toggle_split_view();
+                } else if event == self.settings.key.toggle_fold {
+                    if self.info_mode == InfoMode::Assembly {
+                        self.toggle_fold();
+                    }
+                } else if event == self.settings.key.close_popup {
+                    self.clear_selection();
                 } else if event == self.settings.key.undo {
                     self.undo();
                 } else if event == self.settings.key.redo {
                     self.redo();
+                } else if event == self.settings.key.increment_byte {
+                    self.add_to_byte(1);
+                } else if event == self.settings.key.decrement_byte {
+                    self.add_to_byte(0xFF);
+                } else if event == self.settings.key.increment_byte_by_16 {
+                    self.add_to_byte(0x10);
+                } else if event == self.settings.key.decrement_byte_by_16 {
+                    self.add_to_byte(0xF0);
                 } else if let KeyCode::Char(c) = event.code {
                     match c {
                         '0'..='9' | 'A'..='F' | 'a'..='f' => {
@@ -220,8 +307,13 @@ impl App {
                     &self.settings.key,
                 )?;
                 if old_path != *path || results.is_empty() {
-                    *results =
-                        Self::find_dir_contents(currently_open_path, path, &self.filesystem)?;
+                    *results = Self::find_open_results(
+                        currently_open_path,
+                        path,
+                        &self.filesystem,
+                        self.settings.app.follow_symlinks,
+                        &self.recent_dirs,
+                    )?;
                 }
             }
             Some(PopupState::Run {
@@ -241,10 +333,43 @@ impl App {
                     &self.settings.key,
                 )?;
                 if old_command != *command || results.is_empty() {
-                    *results = self.find_commands(command);
+                    *results = self.find_commands(Self::split_command_argument(command).0);
+                }
+            }
+            Some(PopupState::FindText {
+                text,
+                encoding,
+                cursor,
+            }) => {
+                let key = match &event {
+                    event::Event::Key(key) if key.kind == event::KeyEventKind::Press => Some(*key),
+                    _ => None,
+                };
+                if key == Some(self.settings.key.next_button) {
+                    *encoding = encoding.next();
+                } else if key == Some(self.settings.key.previous_button) {
+                    *encoding = encoding.previous();
+                } else {
+                    Self::handle_string_edit(
+                        text,
+                        cursor,
+                        &event,
+                        None,
+                        None,
+                        false,
+                        &self.settings.key,
+                    )?;
                 }
             }
-            Some(PopupState::FindText { text, cursor }) => {
+            Some(PopupState::FindHex { text, cursor })
+            | Some(PopupState::Fill { text, cursor })
+            | Some(PopupState::FindOccurrence { text, cursor })
+            | Some(PopupState::SaveProfile { text, cursor })
+            | Some(PopupState::AssemblyGoto { text, cursor })
+            | Some(PopupState::GotoRow { text, cursor })
+            | Some(PopupState::Calculator { text, cursor })
+            | Some(PopupState::WriteValue { text, cursor })
+            | Some(PopupState::AddField { text, cursor }) => {
                 Self::handle_string_edit(
                     text,
                     cursor,
@@ -275,6 +400,27 @@ impl App {
                     *symbols = self.find_symbols(filter);
                 }
             }
+            Some(PopupState::Sections {
+                filter,
+                cursor,
+                results,
+                scroll,
+            }) => {
+                let old_filter = filter.clone();
+                Self::handle_string_edit(
+                    filter,
+                    cursor,
+                    &event,
+                    None,
+                    None,
+                    false,
+                    &self.settings.key,
+                )?;
+                if old_filter != *filter {
+                    *results = self.find_sections(filter);
+                    *scroll = 0;
+                }
+            }
             Some(PopupState::InsertText { text, cursor }) => {
                 Self::handle_string_edit(
                     text,
@@ -319,7 +465,14 @@ impl App {
                     &self.settings.key,
                 )?;
             }
-            Some(PopupState::SaveAs { path, cursor }) => {
+            Some(PopupState::SaveAs { path, cursor })
+            | Some(PopupState::ExtractAs { path, cursor })
+            | Some(PopupState::ExportHexdump { path, cursor })
+            | Some(PopupState::ExportStrings { path, cursor })
+            | Some(PopupState::CompareWith { path, cursor })
+            | Some(PopupState::SavePatch { path, cursor })
+            | Some(PopupState::ApplyPatch { path, cursor })
+            | Some(PopupState::OpenPane { path, cursor }) => {
                 Self::handle_string_edit(
                     path,
                     cursor,
@@ -335,25 +488,26 @@ impl App {
 
         match event {
             event::Event::Key(event) if event.kind == event::KeyEventKind::Press => {
-                if event == self.settings.key.right {
-                    match &mut popup {
-                        Some(PopupState::Save(choice)) | Some(PopupState::SaveAndQuit(choice)) => {
-                            *choice = choice.next();
-                        }
-                        Some(PopupState::QuitDirtySave(choice)) => {
-                            *choice = choice.next();
-                        }
-                        _ => {}
+                // a dialog the user is looking at is not closed by its timeout
+                if let Some(PopupState::PluginDialog { expires, .. }) = &mut popup {
+                    *expires = None;
+                }
+                if matches!(popup, Some(PopupState::Help(_)))
+                    && event != self.settings.key.up
+                    && event != self.settings.key.down
+                {
+                    // the help is only read, any other key closes it
+                    popup = None;
+                } else if event == self.settings.key.right || event == self.settings.key.next_button
+                {
+                    if let Some(popup) = &mut popup {
+                        popup.select_button(true);
                     }
-                } else if event == self.settings.key.left {
-                    match &mut popup {
-                        Some(PopupState::Save(choice)) | Some(PopupState::SaveAndQuit(choice)) => {
-                            *choice = choice.previous();
-                        }
-                        Some(PopupState::QuitDirtySave(choice)) => {
-                            *choice = choice.previous();
-                        }
-                        _ => {}
+                } else if event == self.settings.key.left
+                    || event == self.settings.key.previous_button
+                {
+                    if let Some(popup) = &mut popup {
+                        popup.select_button(false);
                     }
                 } else if event == self.settings.key.confirm {
                     match &mut popup {
@@ -380,15 +534,19 @@ impl App {
                             results: _results,
                             scroll,
                         }) => {
-                            self.run_command(command, *scroll)?;
+                            self.run_command(command, *scroll, terminal)?;
                             popup.clone_from(&self.popup);
                         }
+                        // text that can't be encoded keeps the popup open so that it can be fixed
                         Some(PopupState::FindText {
                             text,
+                            encoding,
                             cursor: _cursor,
-                        }) => {
-                            self.find_text(text);
-                            // Maybe removing the popup is not a good idea, more testing needed
+                        }) if self.find_text(text, *encoding) => {
+                            popup = None;
+                        }
+                        // invalid bytes keep the popup open so that they can be fixed
+                        Some(PopupState::FindHex { text, cursor: _ }) if self.find_hex(text) => {
                             popup = None;
                         }
                         Some(PopupState::FindSymbol {
@@ -400,6 +558,14 @@ impl App {
                             self.jump_to_fuzzy_symbol(filter, symbols, *scroll);
                             popup = None;
                         }
+                        Some(PopupState::Sections {
+                            results, scroll, ..
+                        }) => {
+                            if let Some(section) = results.get(*scroll) {
+                                self.jump_to_section(section);
+                                popup = None;
+                            }
+                        }
                         Some(PopupState::Log(_)) => {
                             popup = None;
                         }
@@ -425,43 +591,227 @@ impl App {
                             self.jump_to_symbol(location);
                             popup = None;
                         }
+                        Some(PopupState::AssemblyGoto { text, cursor: _ }) => {
+                            self.jump_to_assembly_target(text);
+                            popup = None;
+                        }
+                        Some(PopupState::GotoRow { text, cursor: _ }) => {
+                            self.jump_to_row(text);
+                            popup = None;
+                        }
+                        // invalid expressions keep the popup open so that they can be fixed
+                        Some(PopupState::Calculator { text, cursor: _ })
+                            if self.jump_to_calculated_offset(text) =>
+                        {
+                            popup = None;
+                        }
+                        // invalid values keep the popup open so that they can be fixed
+                        Some(PopupState::WriteValue { text, cursor: _ })
+                            if self.write_value(text) =>
+                        {
+                            popup = None;
+                        }
+                        Some(PopupState::Fill { text, cursor: _ }) => {
+                            self.fill_pattern(text);
+                            popup = None;
+                        }
+                        Some(PopupState::FindOccurrence { text, cursor: _ }) => {
+                            self.jump_to_occurrence(text);
+                            popup = None;
+                        }
+                        Some(PopupState::AddField { text, cursor: _ }) => {
+                            self.add_field(text);
+                            popup = None;
+                        }
+                        Some(PopupState::Fields(scroll)) => {
+                            if let Some(offset) = self.fields.get(*scroll).map(|field| field.offset) {
+                                self.jump_to_remembered(offset, false);
+                            }
+                            popup = None;
+                        }
+                        Some(PopupState::History(scroll)) => {
+                            self.move_in_history(*scroll);
+                            popup = None;
+                        }
+                        Some(PopupState::Pointers { pointers, scroll }) => {
+                            match pointers.get(*scroll).and_then(|pointer| pointer.target) {
+                                Some(target) => {
+                                    self.jump_to_remembered(target, false);
+                                    popup = None;
+                                }
+                                None => self.log(
+                                    NotificationLevel::Warning,
+                                    "The pointer does not point inside the file.",
+                                ),
+                            }
+                        }
+                        Some(PopupState::Strings { strings, scroll }) => {
+                            if let Some(string) = strings.get(*scroll) {
+                                self.jump_to_remembered(string.offset, false);
+                            }
+                            popup = None;
+                        }
+                        Some(PopupState::RecentDirs { dirs, scroll }) => {
+                            if let Some(dir) = dirs.get(*scroll).cloned() {
+                                Self::open_dir(
+                                    &mut popup,
+                                    &dir,
+                                    &mut self.filesystem,
+                                    self.settings.app.follow_symlinks,
+                                    &self.recent_dirs,
+                                )?;
+                            }
+                        }
+                        Some(PopupState::SaveProfile { text, cursor: _ }) => {
+                            self.save_profile(text);
+                            popup = None;
+                        }
+                        Some(PopupState::Profiles { profiles, scroll }) => {
+                            if let Some(profile) = profiles.get(*scroll).cloned() {
+                                popup = None;
+                                self.apply_profile(&profile);
+                            }
+                        }
                         Some(PopupState::SaveAs { path, cursor: _ }) => {
                             self.save_file_as(path)?;
                             popup = None;
                         }
+                        Some(PopupState::ExtractAs { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.extract_selection_as(&path, terminal)?;
+                        }
+                        Some(PopupState::ExportHexdump { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.export_view(&path)?;
+                        }
+                        Some(PopupState::ExportStrings { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.export_strings(&path)?;
+                        }
+                        Some(PopupState::CompareWith { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.compare_with_file(
+                                &path,
+                                self.get_cursor_position().global_byte_index,
+                            )?;
+                        }
+                        Some(PopupState::SavePatch { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.save_patch(&path)?;
+                        }
+                        Some(PopupState::ApplyPatch { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.popup = None;
+                            self.apply_patch(&path)?;
+                            popup.clone_from(&self.popup);
+                        }
+                        Some(PopupState::PreviewPatch {
+                            path,
+                            patch,
+                            choice,
+                        }) => {
+                            if *choice == BinaryChoice::Yes {
+                                self.commit_patch(path, patch);
+                            }
+                            popup = None;
+                        }
+                        Some(PopupState::OpenPane { path, cursor: _ }) => {
+                            let path = path.clone();
+                            popup = None;
+                            self.open_pane(&path)?;
+                        }
                         Some(PopupState::Save(choice)) => {
                             if *choice == BinaryChoice::Yes {
                                 self.save_file()?;
                             }
                             popup = None;
                         }
-                        Some(PopupState::SaveAndQuit(choice)) => {
+                        Some(PopupState::OpenLargeFile { path, choice, .. }) => {
+                            let (path, choice) = (path.clone(), *choice);
+                            popup = None;
+                            self.open_large_file(&path, choice, &mut popup, terminal)?;
+                        }
+                        Some(PopupState::ReadTimedOut { path, choice }) => {
+                            let (path, keep_waiting) = (path.clone(), *choice == BinaryChoice::Yes);
+                            popup = None;
+                            self.answer_read_timed_out(&path, keep_waiting, &mut popup, terminal)?;
+                        }
+                        Some(PopupState::Revert(choice)) => {
                             if *choice == BinaryChoice::Yes {
-                                self.save_file()?;
-                                self.needs_to_exit = true;
+                                self.revert_file()?;
                             }
                             popup = None;
                         }
+                        Some(PopupState::DuplicateSelection(choice)) => {
+                            if *choice == BinaryChoice::Yes {
+                                self.duplicate_selection();
+                            }
+                            popup = None;
+                        }
+                        Some(PopupState::SaveAndQuit(choice)) => {
+                            let choice = *choice;
+                            popup = None;
+                            if choice == BinaryChoice::Yes {
+                                self.save_file()?;
+                                self.request_exit();
+                                popup.clone_from(&self.popup);
+                            }
+                        }
                         Some(PopupState::QuitDirtySave(choice)) => {
+                            let choice = *choice;
+                            popup = None;
                             match choice {
                                 SimpleChoice::Yes => {
                                     self.save_file()?;
-                                    self.needs_to_exit = true;
+                                    self.request_exit();
+                                    popup.clone_from(&self.popup);
                                 }
                                 SimpleChoice::No => {
-                                    self.needs_to_exit = true;
+                                    self.request_exit();
+                                    popup.clone_from(&self.popup);
                                 }
                                 SimpleChoice::Cancel => {}
                             }
+                        }
+                        Some(PopupState::QuitPluginData { choice, .. }) => {
+                            if *choice == BinaryChoice::Yes {
+                                self.needs_to_exit = true;
+                            }
                             popup = None;
                         }
                         Some(PopupState::Help(_)) => {
                             popup = None;
                         }
+                        Some(PopupState::PluginDialog {
+                            plugin_index,
+                            dialog,
+                            selected,
+                            ..
+                        }) => {
+                            let (plugin_index, callback, selected) =
+                                (*plugin_index, dialog.callback.clone(), *selected);
+                            // the callback can open another popup
+                            self.popup = None;
+                            self.answer_plugin_dialog(plugin_index, &callback, Some(selected));
+                            popup = self.popup.take();
+                        }
                         Some(PopupState::Custom {
                             plugin_index: _,
                             callback: _,
                         }) => {}
+                        // the input was invalid and the popup stays open
+                        Some(
+                            PopupState::FindText { .. }
+                            | PopupState::FindHex { .. }
+                            | PopupState::Calculator { .. }
+                            | PopupState::WriteValue { .. },
+                        ) => {}
                         None => {}
                     }
                 } else if event == self.settings.key.down {
@@ -499,6 +849,11 @@ impl App {
                                 Self::handle_popup_scroll(scroll, symbols.len(), None, 1);
                             }
                         }
+                        Some(PopupState::Sections {
+                            results, scroll, ..
+                        }) => {
+                            Self::handle_popup_scroll(scroll, results.len(), None, 1);
+                        }
                         Some(PopupState::Log(scroll)) => {
                             Self::handle_popup_scroll(
                                 scroll,
@@ -515,6 +870,30 @@ impl App {
                                 1,
                             );
                         }
+                        Some(PopupState::Fields(scroll)) => {
+                            Self::handle_popup_scroll(scroll, self.fields.len(), None, 1);
+                        }
+                        Some(PopupState::History(scroll)) => {
+                            let entries = self.data.history().changes().len() + 1;
+                            Self::handle_popup_scroll(scroll, entries, None, 1);
+                        }
+                        Some(PopupState::Pointers { pointers, scroll }) => {
+                            Self::handle_popup_scroll(scroll, pointers.len(), None, 1);
+                        }
+                        Some(PopupState::RecentDirs { dirs, scroll }) => {
+                            Self::handle_popup_scroll(scroll, dirs.len(), None, 1);
+                        }
+                        Some(PopupState::Profiles { profiles, scroll }) => {
+                            Self::handle_popup_scroll(scroll, profiles.len(), None, 1);
+                        }
+                        Some(PopupState::Strings { strings, scroll }) => {
+                            Self::handle_popup_scroll(scroll, strings.len(), None, 1);
+                        }
+                        Some(PopupState::PluginDialog {
+                            dialog, selected, ..
+                        }) => {
+                            Self::handle_popup_scroll(selected, dialog.options.len(), None, 1);
+                        }
                         _ => {}
                     }
                 } else if event == self.settings.key.up {
@@ -544,6 +923,11 @@ impl App {
                         }) => {
                             Self::handle_popup_scroll(scroll, symbols.len(), None, -1);
                         }
+                        Some(PopupState::Sections {
+                            results, scroll, ..
+                        }) => {
+                            Self::handle_popup_scroll(scroll, results.len(), None, -1);
+                        }
                         Some(PopupState::Log(scroll)) => {
                             Self::handle_popup_scroll(
                                 scroll,
@@ -560,10 +944,44 @@ impl App {
                                 -1,
                             );
                         }
+                        Some(PopupState::Fields(scroll)) => {
+                            Self::handle_popup_scroll(scroll, self.fields.len(), None, -1);
+                        }
+                        Some(PopupState::History(scroll)) => {
+                            let entries = self.data.history().changes().len() + 1;
+                            Self::handle_popup_scroll(scroll, entries, None, -1);
+                        }
+                        Some(PopupState::Pointers { pointers, scroll }) => {
+                            Self::handle_popup_scroll(scroll, pointers.len(), None, -1);
+                        }
+                        Some(PopupState::RecentDirs { dirs, scroll }) => {
+                            Self::handle_popup_scroll(scroll, dirs.len(), None, -1);
+                        }
+                        Some(PopupState::Profiles { profiles, scroll }) => {
+                            Self::handle_popup_scroll(scroll, profiles.len(), None, -1);
+                        }
+                        Some(PopupState::Strings { strings, scroll }) => {
+                            Self::handle_popup_scroll(scroll, strings.len(), None, -1);
+                        }
+                        Some(PopupState::PluginDialog {
+                            dialog, selected, ..
+                        }) => {
+                            Self::handle_popup_scroll(selected, dialog.options.len(), None, -1);
+                        }
                         _ => {}
                     }
                 } else if event == self.settings.key.close_popup {
-                    if self.filesystem.is_file(self.filesystem.pwd())
+                    if let Some(PopupState::PluginDialog {
+                        plugin_index,
+                        dialog,
+                        ..
+                    }) = &popup
+                    {
+                        let (plugin_index, callback) = (*plugin_index, dialog.callback.clone());
+                        self.popup = None;
+                        self.answer_plugin_dialog(plugin_index, &callback, None);
+                        popup = self.popup.take();
+                    } else if self.filesystem.is_file(self.filesystem.pwd())
                     // if no file is open, close the program instead of the popup
                     {
                         popup = None;
@@ -571,9 +989,20 @@ impl App {
                         self.needs_to_exit = true;
                     }
                 } else if event == self.settings.key.clear_log {
-                    if let Some(PopupState::Log(scroll)) = &mut popup {
-                        *scroll = 0;
-                        self.logger.clear();
+                    match &mut popup {
+                        Some(PopupState::Log(scroll)) => {
+                            *scroll = 0;
+                            self.logger.clear();
+                        }
+                        Some(PopupState::Fields(scroll)) => {
+                            self.remove_field(*scroll);
+                            if self.fields.is_empty() {
+                                popup = None;
+                            } else {
+                                *scroll = (*scroll).min(self.fields.len() - 1);
+                            }
+                        }
+                        _ => {}
                     }
                 } else if let KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete = event.code {
                     if event.modifiers.is_empty() {
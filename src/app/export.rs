@@ -0,0 +1,360 @@
+use std::error::Error;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use super::{files::path, log::NotificationLevel, settings::color_settings::ColorSettings, App};
+
+/// The format of an exported hex dump, chosen from the extension of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexdumpFormat {
+    Text,
+    /// A `<pre>` block with the colors of the hex view inlined.
+    Html,
+}
+
+impl HexdumpFormat {
+    pub fn from_path(path: &str) -> Self {
+        let extension = path::filename(path)
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_lowercase());
+        match extension.as_deref() {
+            Some("html") | Some("htm") => Self::Html,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// The first 16 colors of the terminal palette.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0x80, 0x00, 0x00),
+    (0x00, 0x80, 0x00),
+    (0x80, 0x80, 0x00),
+    (0x00, 0x00, 0x80),
+    (0x80, 0x00, 0x80),
+    (0x00, 0x80, 0x80),
+    (0xC0, 0xC0, 0xC0),
+    (0x80, 0x80, 0x80),
+    (0xFF, 0x00, 0x00),
+    (0x00, 0xFF, 0x00),
+    (0xFF, 0xFF, 0x00),
+    (0x00, 0x00, 0xFF),
+    (0xFF, 0x00, 0xFF),
+    (0x00, 0xFF, 0xFF),
+    (0xFF, 0xFF, 0xFF),
+];
+
+impl App {
+    /// Formats the rows of a hex dump like `hexdump -C`, `decorate` receives each byte
+    /// and the text that shows it, and returns what is written in its place.
+    fn format_hexdump(
+        bytes: &[u8],
+        start: usize,
+        uppercase: bool,
+        line_ending: &str,
+        mut decorate: impl FnMut(u8, &str) -> String,
+    ) -> String {
+        let mut ret = String::new();
+        for (i, row) in bytes.chunks(16).enumerate() {
+            let mut hex = String::new();
+            let mut hex_width = 0;
+            for (j, &byte) in row.iter().enumerate() {
+                if j == 8 {
+                    hex.push(' ');
+                    hex_width += 1;
+                }
+                let cell = if uppercase {
+                    format!("{:02X}", byte)
+                } else {
+                    format!("{:02x}", byte)
+                };
+                hex.push_str(&decorate(byte, &cell));
+                hex.push(' ');
+                hex_width += 3;
+            }
+            let text = row
+                .iter()
+                .map(|&byte| {
+                    let char = match byte {
+                        0x20..=0x7E => byte as char,
+                        _ => '.',
+                    };
+                    decorate(byte, &char.to_string())
+                })
+                .collect::<String>();
+            ret.push_str(&format!(
+                "{:08X}  {}{} |{}|{}",
+                start + i * 16,
+                hex,
+                " ".repeat(49 - hex_width),
+                text,
+                line_ending
+            ));
+        }
+        ret
+    }
+
+    /// Formats the bytes like `hexdump -C`, `start` is the offset of the first byte.
+    /// Every row, the last one too, ends with `line_ending`.
+    pub(super) fn bytes_to_hexdump(
+        bytes: &[u8],
+        start: usize,
+        uppercase: bool,
+        line_ending: &str,
+    ) -> String {
+        Self::format_hexdump(bytes, start, uppercase, line_ending, |_, cell| {
+            cell.to_string()
+        })
+    }
+
+    /// Formats the bytes like [`App::bytes_to_hexdump`] inside a `<pre>` block,
+    /// each byte has the color it has in the hex view.
+    pub(super) fn bytes_to_html_hexdump(
+        bytes: &[u8],
+        start: usize,
+        uppercase: bool,
+        color_settings: &ColorSettings,
+        line_ending: &str,
+    ) -> String {
+        let rows = Self::format_hexdump(bytes, start, uppercase, line_ending, |byte, cell| {
+            let cell = Self::escape_html(cell);
+            let css = Self::style_to_css(Self::get_style_for_byte(color_settings, byte));
+            if css.is_empty() {
+                cell
+            } else {
+                format!("<span style=\"{}\">{}</span>", css, cell)
+            }
+        });
+        format!("<pre>{}{}</pre>{}", line_ending, rows, line_ending)
+    }
+
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn color_to_css(color: Color) -> Option<String> {
+        let (r, g, b) = match color {
+            Color::Reset => return None,
+            Color::Black => PALETTE[0],
+            Color::Red => PALETTE[1],
+            Color::Green => PALETTE[2],
+            Color::Yellow => PALETTE[3],
+            Color::Blue => PALETTE[4],
+            Color::Magenta => PALETTE[5],
+            Color::Cyan => PALETTE[6],
+            Color::Gray => PALETTE[7],
+            Color::DarkGray => PALETTE[8],
+            Color::LightRed => PALETTE[9],
+            Color::LightGreen => PALETTE[10],
+            Color::LightYellow => PALETTE[11],
+            Color::LightBlue => PALETTE[12],
+            Color::LightMagenta => PALETTE[13],
+            Color::LightCyan => PALETTE[14],
+            Color::White => PALETTE[15],
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(index @ 0..=15) => PALETTE[index as usize],
+            // the 6x6x6 color cube
+            Color::Indexed(index @ 16..=231) => {
+                let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+                let index = index - 16;
+                (level(index / 36), level(index / 6 % 6), level(index % 6))
+            }
+            // the grayscale ramp
+            Color::Indexed(index) => {
+                let value = 8 + (index - 232) * 10;
+                (value, value, value)
+            }
+        };
+        Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    fn style_to_css(style: Style) -> String {
+        let mut css = Vec::new();
+        if let Some(color) = style.fg.and_then(Self::color_to_css) {
+            css.push(format!("color:{}", color));
+        }
+        if let Some(color) = style.bg.and_then(Self::color_to_css) {
+            css.push(format!("background-color:{}", color));
+        }
+        if style.add_modifier.contains(Modifier::BOLD) {
+            css.push("font-weight:bold".to_string());
+        }
+        if style.add_modifier.contains(Modifier::ITALIC) {
+            css.push("font-style:italic".to_string());
+        }
+        if style.add_modifier.contains(Modifier::UNDERLINED) {
+            css.push("text-decoration:underline".to_string());
+        }
+        css.join(";")
+    }
+
+    /// Returns the range of bytes to export: the selection,
+    /// or the rows shown in the hex view if nothing is selected.
+    fn get_export_range(&self) -> (usize, usize) {
+        if let Some(selection) = self.get_selection() {
+            return selection;
+        }
+        let bytes_per_row = self.block_size * self.blocks_per_row;
+        let visible_rows = self.screen_size.1.saturating_sub(self.vertical_margin) as usize;
+        let start = (self.scroll * bytes_per_row).min(self.data.len());
+        let end = (start + visible_rows.max(1) * bytes_per_row).min(self.data.len());
+        (start, end)
+    }
+
+    /// Writes the bytes from `range.0` to `range.1` (excluded) to `path` as a hex dump.
+    pub(super) fn export_hexdump(
+        &mut self,
+        path: &str,
+        format: HexdumpFormat,
+        range: (usize, usize),
+    ) -> Result<(), Box<dyn Error>> {
+        let (start, end) = range;
+        let bytes = &self.data.bytes()[start..end];
+        let line_ending = self.settings.app.line_ending.as_str();
+        let text = match format {
+            HexdumpFormat::Text => {
+                Self::bytes_to_hexdump(bytes, start, self.settings.app.uppercase_hex, line_ending)
+            }
+            HexdumpFormat::Html => Self::bytes_to_html_hexdump(
+                bytes,
+                start,
+                self.settings.app.uppercase_hex,
+                &self.settings.color,
+                line_ending,
+            ),
+        };
+
+        if let Some(parent) = path::parent(path) {
+            self.filesystem.mkdirs(parent)?;
+        };
+        self.filesystem.create(path)?;
+        let path = self.filesystem.canonicalize(path)?;
+        self.filesystem.write(&path, text.as_bytes())?;
+        self.log(
+            NotificationLevel::Info,
+            &format!("Exported {} bytes to {}", end - start, path),
+        );
+        Ok(())
+    }
+
+    /// Exports the selection, or the rows shown if nothing is selected,
+    /// as HTML if the file ends with `.html` or `.htm`, as text otherwise.
+    pub(super) fn export_view(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let range = self.get_export_range();
+        self.export_hexdump(path, HexdumpFormat::from_path(path), range)
+    }
+
+    /// Formats the bytes as a C array definition with 12 bytes per line.
+    pub(super) fn bytes_to_c_array(bytes: &[u8], line_ending: &str) -> String {
+        let mut ret = format!("unsigned char data[{}] = {{{}", bytes.len(), line_ending);
+        for row in bytes.chunks(12) {
+            let row = row
+                .iter()
+                .map(|byte| format!("0x{:02x},", byte))
+                .collect::<Vec<_>>()
+                .join(" ");
+            ret.push_str(&format!("    {}{}", row, line_ending));
+        }
+        ret.push_str(&format!("}};{}", line_ending));
+        ret
+    }
+
+    pub(super) fn copy_selection_as_hexdump(&mut self) {
+        if let Some((start, end)) = self.get_selection() {
+            let text = Self::bytes_to_hexdump(
+                &self.data.bytes()[start..end],
+                start,
+                self.settings.app.uppercase_hex,
+                self.settings.app.line_ending.as_str(),
+            );
+            self.copy_to_clipboard(&text);
+        } else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+        }
+    }
+
+    pub(super) fn copy_selection_as_c_array(&mut self) {
+        if let Some(bytes) = self.get_selection_bytes() {
+            let text = Self::bytes_to_c_array(bytes, self.settings.app.line_ending.as_str());
+            self.copy_to_clipboard(&text);
+        } else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_export() {
+        let bytes = (0x3C..0x50).collect::<Vec<u8>>();
+        let hexdump = App::bytes_to_hexdump(&bytes, 0x10, true, "\n");
+        assert_eq!(
+            hexdump,
+            "00000010  3C 3D 3E 3F 40 41 42 43  44 45 46 47 48 49 4A 4B  |<=>?@ABCDEFGHIJK|\n\
+             00000020  4C 4D 4E 4F                                       |LMNO|\n"
+        );
+        let crlf = App::bytes_to_hexdump(&bytes, 0x10, true, "\r\n");
+        assert_eq!(crlf.matches("\r\n").count(), 2);
+        assert_eq!(crlf.replace("\r\n", "\n"), hexdump);
+
+        assert_eq!(
+            App::bytes_to_c_array(&[0xde, 0xad], "\r\n"),
+            "unsigned char data[2] = {\r\n    0xde, 0xad,\r\n};\r\n"
+        );
+    }
+
+    #[test]
+    fn test_export_hexdump() {
+        let mut color_settings = ColorSettings::get_default_dark_theme();
+        color_settings.hex_symbol = Style::default().fg(Color::Rgb(0x12, 0x34, 0x56));
+        color_settings.hex_alphanumeric = Style::default().fg(Color::Indexed(196));
+        let html = App::bytes_to_html_hexdump(b"<A", 0, true, &color_settings, "\n");
+        assert!(html.starts_with("<pre>\n00000000  "));
+        assert!(html.contains("<span style=\"color:#123456\">3C</span>"));
+        assert!(html.contains("<span style=\"color:#123456\">&lt;</span>"));
+        assert!(html.contains("<span style=\"color:#ff0000\">A</span>"));
+        assert!(html.ends_with("|\n</pre>\n"));
+
+        assert_eq!(
+            HexdumpFormat::from_path("/tmp/dump.HTML"),
+            HexdumpFormat::Html
+        );
+        assert_eq!(
+            HexdumpFormat::from_path("/tmp/dump.txt"),
+            HexdumpFormat::Text
+        );
+        assert_eq!(
+            HexdumpFormat::from_path("/tmp.html/dump"),
+            HexdumpFormat::Text
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dump.txt").to_string_lossy().to_string();
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 6);
+        app.export_view(&path).unwrap();
+        let (start, end) = app.get_export_range();
+        assert_eq!(start, 0);
+        assert!(end < 0x40);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            App::bytes_to_hexdump(
+                &app.data.bytes()[..end],
+                0,
+                true,
+                app.settings.app.line_ending.as_str()
+            )
+        );
+
+        app.jump_to(0x30, false);
+        app.toggle_selection();
+        app.jump_to(0x3F, false);
+        assert_eq!(app.get_export_range(), (0x30, 0x40));
+    }
+}
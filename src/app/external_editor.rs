@@ -0,0 +1,174 @@
+use std::{io::Write, process::Command};
+
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::Backend, Terminal};
+
+use crate::get_app_context;
+
+use super::{log::NotificationLevel, App};
+
+impl App {
+    /// The program and the arguments of the external editor, from the `editor` setting
+    /// or from the `VISUAL` and `EDITOR` environment variables.
+    fn get_editor_command(&self) -> Vec<String> {
+        let non_empty = |editor: String| Some(editor).filter(|editor| !editor.trim().is_empty());
+        let editor = non_empty(self.settings.app.editor.clone())
+            .or_else(|| std::env::var("VISUAL").ok().and_then(non_empty))
+            .or_else(|| std::env::var("EDITOR").ok().and_then(non_empty))
+            .unwrap_or_else(|| String::from(if cfg!(windows) { "notepad" } else { "vi" }));
+        editor.split_whitespace().map(str::to_string).collect()
+    }
+
+    /// Writes the bytes to a temporary file, waits for the editor to be closed
+    /// and reads the file back.
+    /// Editors like vi end the file with a newline, so one is removed if the bytes did not end with it.
+    fn edit_bytes_in_editor(&self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        let mut file = tempfile::Builder::new()
+            .prefix("hexpatch-")
+            .suffix(".txt")
+            .tempfile()
+            .map_err(|e| e.to_string())?;
+        file.write_all(bytes).map_err(|e| e.to_string())?;
+        file.flush().map_err(|e| e.to_string())?;
+        let command = self.get_editor_command();
+        let status = Command::new(&command[0])
+            .args(&command[1..])
+            .arg(file.path())
+            .status()
+            .map_err(|e| format!("Could not run \"{}\": {}", command[0], e))?;
+        if !status.success() {
+            return Err(format!("The editor exited with {}", status));
+        }
+        let mut edited = std::fs::read(file.path()).map_err(|e| e.to_string())?;
+        if !bytes.ends_with(b"\n") && edited.ends_with(b"\n") {
+            edited.pop();
+            if !bytes.ends_with(b"\r") && edited.ends_with(b"\r") {
+                edited.pop();
+            }
+        }
+        Ok(edited)
+    }
+
+    /// Replaces the selected bytes and selects the new ones, if the length changed
+    /// the bytes after the selection are shifted and the file is parsed again.
+    pub(in crate::app) fn replace_selection(&mut self, mut bytes: Vec<u8>) {
+        let Some((start, end)) = self.get_selection() else {
+            return;
+        };
+        if bytes == self.data.bytes()[start..end] {
+            self.log(NotificationLevel::Info, "The selection was not changed.");
+            return;
+        }
+        if bytes.len() == end - start {
+            self.jump_to(start, false);
+            self.patch_bytes(&bytes, false);
+        } else {
+            let mut app_context = get_app_context!(self);
+            app_context.offset = start;
+            self.plugin_manager.on_edit(&mut bytes, &mut app_context);
+            if !self.data.replace(start, end - start, bytes.clone()) {
+                return;
+            }
+            self.refresh_after_insertion();
+        }
+        if bytes.is_empty() {
+            self.clear_selection();
+        } else {
+            self.selection_anchor = Some(start);
+            self.jump_to(start + bytes.len() - 1, false);
+        }
+        self.log(
+            NotificationLevel::Info,
+            &format!(
+                "Replaced {} bytes at {:X} with {} bytes",
+                end - start,
+                start,
+                bytes.len()
+            ),
+        );
+    }
+
+    /// Opens the selected bytes in the external editor, the terminal is given to the editor
+    /// until it is closed and then the edited bytes replace the selection.
+    pub(in crate::app) fn edit_selection_in_editor<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) {
+        let Some(bytes) = self.get_selection_bytes().map(<[u8]>::to_vec) else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+            return;
+        };
+        if self.data.read_only() {
+            self.log(NotificationLevel::Warning, "The file is read-only.");
+            return;
+        }
+        let edited = Self::suspend_terminal()
+            .map_err(|e| e.to_string())
+            .and_then(|()| self.edit_bytes_in_editor(&bytes));
+        if let Err(e) = Self::resume_terminal(terminal) {
+            self.log(
+                NotificationLevel::Error,
+                &format!("Could not restore the terminal: {}", e),
+            );
+        }
+        match edited {
+            Ok(edited) => self.replace_selection(edited),
+            Err(e) => self.log(NotificationLevel::Error, &e),
+        }
+    }
+
+    fn suspend_terminal() -> std::io::Result<()> {
+        disable_raw_mode()?;
+        execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture)
+    }
+
+    fn resume_terminal<B: Backend>(terminal: &mut Terminal<B>) -> std::io::Result<()> {
+        enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        // the editor drew over the screen, so everything is drawn again
+        terminal.clear()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_edit_selection_in_editor() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("editor.sh");
+        std::fs::write(&script, "printf 'longer' > \"$1\"\n").unwrap();
+        let mut app = App::mockup(b"0123456789".to_vec());
+        app.resize_to_size(80, 24);
+        app.settings.app.editor = format!("sh {}", script.to_string_lossy());
+        assert_eq!(app.edit_bytes_in_editor(b"abc"), Ok(b"longer".to_vec()));
+        std::fs::write(&script, "printf 'longer\\r\\n' > \"$1\"\n").unwrap();
+        assert_eq!(app.edit_bytes_in_editor(b"abc"), Ok(b"longer".to_vec()));
+        assert_eq!(
+            app.edit_bytes_in_editor(b"abc\n"),
+            Ok(b"longer\r\n".to_vec())
+        );
+        app.settings.app.editor = "false".to_string();
+        assert!(app.edit_bytes_in_editor(b"abc").is_err());
+
+        app.selection_anchor = Some(2);
+        app.jump_to(4, false);
+        app.replace_selection(b"longer".to_vec());
+        assert_eq!(app.data.bytes(), b"01longer56789");
+        assert_eq!(app.get_selection(), Some((2, 8)));
+        app.replace_selection(b"ab".to_vec());
+        assert_eq!(app.data.bytes(), b"01ab56789");
+        app.replace_selection(b"cd".to_vec());
+        assert_eq!(app.data.bytes(), b"01cd56789");
+        app.undo();
+        app.undo();
+        app.undo();
+        assert_eq!(app.data.bytes(), b"0123456789");
+    }
+}
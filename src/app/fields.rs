@@ -0,0 +1,521 @@
+use std::{fmt::Display, str::FromStr};
+
+use object::Endianness;
+use serde::{Deserialize, Serialize};
+
+use super::{log::NotificationLevel, App};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    String,
+    Bytes,
+}
+
+impl FieldType {
+    /// The size of the type in bytes, `None` if the size depends on the selection.
+    pub fn size(&self) -> Option<usize> {
+        match self {
+            FieldType::U8 | FieldType::I8 => Some(1),
+            FieldType::U16 | FieldType::I16 => Some(2),
+            FieldType::U32 | FieldType::I32 | FieldType::F32 => Some(4),
+            FieldType::U64 | FieldType::I64 | FieldType::F64 => Some(8),
+            FieldType::String | FieldType::Bytes => None,
+        }
+    }
+
+    /// The name of the type in C, arrays of chars and bytes are declared by the caller.
+    pub fn c_type(&self) -> &'static str {
+        match self {
+            FieldType::U8 | FieldType::Bytes => "uint8_t",
+            FieldType::U16 => "uint16_t",
+            FieldType::U32 => "uint32_t",
+            FieldType::U64 => "uint64_t",
+            FieldType::I8 => "int8_t",
+            FieldType::I16 => "int16_t",
+            FieldType::I32 => "int32_t",
+            FieldType::I64 => "int64_t",
+            FieldType::F32 => "float",
+            FieldType::F64 => "double",
+            FieldType::String => "char",
+        }
+    }
+
+    /// Decodes `bytes`, that must be as long as the size of the type if it has one.
+    pub fn decode(&self, bytes: &[u8], endianness: Endianness) -> String {
+        macro_rules! number {
+            ($t:ty) => {{
+                let bytes = bytes.try_into().expect("Field size mismatch.");
+                match endianness {
+                    Endianness::Little => <$t>::from_le_bytes(bytes).to_string(),
+                    Endianness::Big => <$t>::from_be_bytes(bytes).to_string(),
+                }
+            }};
+        }
+        match self {
+            FieldType::U8 => number!(u8),
+            FieldType::U16 => number!(u16),
+            FieldType::U32 => number!(u32),
+            FieldType::U64 => number!(u64),
+            FieldType::I8 => number!(i8),
+            FieldType::I16 => number!(i16),
+            FieldType::I32 => number!(i32),
+            FieldType::I64 => number!(i64),
+            FieldType::F32 => number!(f32),
+            FieldType::F64 => number!(f64),
+            FieldType::String => format!("{:?}", String::from_utf8_lossy(bytes)),
+            FieldType::Bytes => bytes
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// Encodes `value`, the opposite of `decode`. Integers can be decimal or hex with the `0x` prefix,
+    /// signed types also accept the unsigned values of the same size. Bytes are written in hex.
+    pub fn encode(&self, value: &str, endianness: Endianness) -> Result<Vec<u8>, String> {
+        let value = value.trim();
+        let invalid = || format!("Invalid {} \"{}\"", self, value);
+        let integer = || {
+            let (negative, digits) = match value.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, value),
+            };
+            let magnitude = match digits
+                .strip_prefix("0x")
+                .or_else(|| digits.strip_prefix("0X"))
+            {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => digits.parse::<u64>(),
+            }
+            .map_err(|_| invalid())?;
+            Ok::<_, String>(if negative {
+                -(magnitude as i128)
+            } else {
+                magnitude as i128
+            })
+        };
+        macro_rules! bytes {
+            ($value:expr) => {
+                match endianness {
+                    Endianness::Little => $value.to_le_bytes().to_vec(),
+                    Endianness::Big => $value.to_be_bytes().to_vec(),
+                }
+            };
+        }
+        macro_rules! integer {
+            ($t:ty) => {{
+                let value = integer()?;
+                let value = <$t>::try_from(value)
+                    .map_err(|_| format!("{} is out of range for {}", value, self))?;
+                bytes!(value)
+            }};
+            ($t:ty, $u:ty) => {{
+                let value = integer()?;
+                let value = <$t>::try_from(value)
+                    .or_else(|_| <$u>::try_from(value).map(|value| value as $t))
+                    .map_err(|_| format!("{} is out of range for {}", value, self))?;
+                bytes!(value)
+            }};
+        }
+        macro_rules! float {
+            ($t:ty) => {{
+                let value = value.parse::<$t>().map_err(|_| invalid())?;
+                bytes!(value)
+            }};
+        }
+        Ok(match self {
+            FieldType::U8 => integer!(u8),
+            FieldType::U16 => integer!(u16),
+            FieldType::U32 => integer!(u32),
+            FieldType::U64 => integer!(u64),
+            FieldType::I8 => integer!(i8, u8),
+            FieldType::I16 => integer!(i16, u16),
+            FieldType::I32 => integer!(i32, u32),
+            FieldType::I64 => integer!(i64, u64),
+            FieldType::F32 => float!(f32),
+            FieldType::F64 => float!(f64),
+            FieldType::String => value.as_bytes().to_vec(),
+            FieldType::Bytes => {
+                let hex = value
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect::<String>();
+                if !hex.is_ascii() || hex.len() % 2 != 0 {
+                    return Err(invalid());
+                }
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                    .collect::<Result<Vec<u8>, _>>()
+                    .map_err(|_| invalid())?
+            }
+        })
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "u8" => Ok(FieldType::U8),
+            "u16" => Ok(FieldType::U16),
+            "u32" => Ok(FieldType::U32),
+            "u64" => Ok(FieldType::U64),
+            "i8" => Ok(FieldType::I8),
+            "i16" => Ok(FieldType::I16),
+            "i32" => Ok(FieldType::I32),
+            "i64" => Ok(FieldType::I64),
+            "f32" => Ok(FieldType::F32),
+            "f64" => Ok(FieldType::F64),
+            "string" => Ok(FieldType::String),
+            "bytes" => Ok(FieldType::Bytes),
+            other => Err(format!("Unknown field type \"{}\"", other)),
+        }
+    }
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FieldType::U8 => "u8",
+            FieldType::U16 => "u16",
+            FieldType::U32 => "u32",
+            FieldType::U64 => "u64",
+            FieldType::I8 => "i8",
+            FieldType::I16 => "i16",
+            FieldType::I32 => "i32",
+            FieldType::I64 => "i64",
+            FieldType::F32 => "f32",
+            FieldType::F64 => "f64",
+            FieldType::String => "string",
+            FieldType::Bytes => "bytes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A named range of the file that is decoded as `field_type`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub field_type: FieldType,
+}
+
+impl Field {
+    pub fn end(&self) -> usize {
+        self.offset + self.size
+    }
+
+    /// Returns true if the field is within the first `data_len` bytes and its size
+    /// matches its type, fields loaded from a file must be checked before being decoded.
+    pub fn is_valid(&self, data_len: usize) -> bool {
+        self.field_type.size().is_none_or(|size| size == self.size)
+            && self
+                .offset
+                .checked_add(self.size)
+                .is_some_and(|end| end <= data_len)
+    }
+}
+
+impl App {
+    /// Parses a field definition in the form `NAME: TYPE`.
+    pub(super) fn parse_field(text: &str) -> Result<(String, FieldType), String> {
+        let (name, field_type) = text
+            .rsplit_once(':')
+            .ok_or("Expected a name and a type separated by \":\"")?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("The name of the field is empty".into());
+        }
+        Ok((name.to_string(), field_type.parse()?))
+    }
+
+    /// Adds a field that starts at the selection, or at the cursor if nothing is selected,
+    /// the fields that overlap with it are replaced.
+    pub(super) fn add_field(&mut self, text: &str) {
+        let (name, field_type) = match Self::parse_field(text) {
+            Ok(field) => field,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return;
+            }
+        };
+        let selection = self.get_selection();
+        let offset = match selection {
+            Some((start, _end)) => start,
+            None => self.get_cursor_position().global_byte_index,
+        };
+        let size = match (field_type.size(), selection) {
+            (Some(size), _) => size,
+            (None, Some((start, end))) => end - start,
+            (None, None) => {
+                self.log(
+                    NotificationLevel::Warning,
+                    &format!("Select the bytes of the {} field.", field_type),
+                );
+                return;
+            }
+        };
+        if offset + size > self.data.len() {
+            self.log(
+                NotificationLevel::Error,
+                &format!("The field {} does not fit in the file", name),
+            );
+            return;
+        }
+        let field = Field {
+            name,
+            offset,
+            size,
+            field_type,
+        };
+        let fields_count = self.fields.len();
+        self.fields
+            .retain(|other| other.end() <= field.offset || other.offset >= field.end());
+        if self.fields.len() != fields_count {
+            self.log(
+                NotificationLevel::Warning,
+                &format!(
+                    "Replaced {} overlapping field(s)",
+                    fields_count - self.fields.len()
+                ),
+            );
+        }
+        self.log(
+            NotificationLevel::Info,
+            &format!("Added field {} at {:X}", field.name, field.offset),
+        );
+        let index = self
+            .fields
+            .partition_point(|other| other.offset < field.offset);
+        self.fields.insert(index, field);
+    }
+
+    pub(super) fn remove_field(&mut self, index: usize) {
+        if index < self.fields.len() {
+            let field = self.fields.remove(index);
+            self.log(
+                NotificationLevel::Info,
+                &format!("Removed field {}", field.name),
+            );
+        }
+    }
+
+    /// The fields are sorted by offset and never overlap.
+    pub(super) fn get_field_at(&self, offset: usize) -> Option<&Field> {
+        let index = self.fields.partition_point(|field| field.end() <= offset);
+        self.fields
+            .get(index)
+            .filter(|field| field.offset <= offset)
+    }
+
+    /// Builds a packed C struct with the fields in order of offset,
+    /// the gaps between them are declared as reserved byte arrays.
+    pub(super) fn fields_to_c_struct(&self) -> String {
+        let mut ret = String::from("#pragma pack(push, 1)\nstruct fields {\n");
+        let mut previous_end = self.fields.first().map_or(0, |field| field.offset);
+        for field in self.fields.iter() {
+            if field.offset > previous_end {
+                ret.push_str(&format!(
+                    "    uint8_t reserved_{:x}[{}];\n",
+                    previous_end,
+                    field.offset - previous_end
+                ));
+            }
+            let name = field
+                .name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>();
+            let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("_{}", name)
+            } else {
+                name
+            };
+            let array = match field.field_type.size() {
+                Some(_) => String::new(),
+                None => format!("[{}]", field.size),
+            };
+            ret.push_str(&format!(
+                "    {} {}{}; // {:X}\n",
+                field.field_type.c_type(),
+                name,
+                array,
+                field.offset
+            ));
+            previous_end = field.end();
+        }
+        ret.push_str("};\n#pragma pack(pop)\n");
+        ret
+    }
+
+    pub(super) fn copy_fields_as_c_struct(&mut self) {
+        if self.fields.is_empty() {
+            self.log(NotificationLevel::Warning, "There are no fields.");
+            return;
+        }
+        let text = self
+            .settings
+            .app
+            .line_ending
+            .apply(&self.fields_to_c_struct());
+        self.copy_to_clipboard(&text);
+    }
+
+    /// Returns the decoded value of the field, or `None` if the file is too short.
+    pub(super) fn decode_field(&self, field: &Field) -> Option<String> {
+        let bytes = self.data.bytes().get(field.offset..field.end())?;
+        Some(self.group_digits(&field.field_type.decode(bytes, self.header.endianness())))
+    }
+
+    /// Separates the digits of a decimal integer in groups of three with the `digit_grouping`
+    /// setting, anything that is not an integer is returned as it is.
+    pub(super) fn group_digits(&self, number: &str) -> String {
+        let separator = &self.settings.app.digit_grouping;
+        let (sign, digits) = match number.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", number),
+        };
+        if separator.is_empty()
+            || digits.is_empty()
+            || !digits.bytes().all(|digit| digit.is_ascii_digit())
+        {
+            return number.to_string();
+        }
+        let mut grouped = sign.to_string();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push_str(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_field_type() {
+        assert_eq!(
+            FieldType::U32.decode(&[1, 0, 0, 0], Endianness::Little),
+            "1"
+        );
+        assert_eq!(
+            FieldType::U32.decode(&[0, 0, 0, 1], Endianness::Big),
+            "1"
+        );
+        assert_eq!(FieldType::I8.decode(&[0xFF], Endianness::Little), "-1");
+        assert_eq!(
+            FieldType::F32.decode(&1.5f32.to_le_bytes(), Endianness::Little),
+            "1.5"
+        );
+        assert_eq!(
+            FieldType::String.decode(b"abc", Endianness::Little),
+            "\"abc\""
+        );
+        assert_eq!(
+            FieldType::Bytes.decode(&[0xDE, 0xAD], Endianness::Little),
+            "DE AD"
+        );
+        assert_eq!("U16".parse::<FieldType>(), Ok(FieldType::U16));
+        assert!("u24".parse::<FieldType>().is_err());
+    }
+
+    #[test]
+    fn test_encode_field_type() {
+        assert_eq!(
+            FieldType::U32.encode("0x1234", Endianness::Big),
+            Ok(vec![0, 0, 0x12, 0x34])
+        );
+        assert_eq!(
+            FieldType::I16.encode("-2", Endianness::Little),
+            Ok(vec![0xFE, 0xFF])
+        );
+        assert_eq!(
+            FieldType::I8.encode("0xFF", Endianness::Little),
+            Ok(vec![0xFF])
+        );
+        assert_eq!(
+            FieldType::F32.encode("1.5", Endianness::Little),
+            Ok(1.5f32.to_le_bytes().to_vec())
+        );
+        assert_eq!(
+            FieldType::Bytes.encode("DE AD", Endianness::Big),
+            Ok(vec![0xDE, 0xAD])
+        );
+        assert!(FieldType::U8.encode("256", Endianness::Little).is_err());
+        assert!(FieldType::U8.encode("-1", Endianness::Little).is_err());
+        assert!(FieldType::I8.encode("-129", Endianness::Little).is_err());
+        assert!(FieldType::U16.encode("0xZZ", Endianness::Little).is_err());
+        assert!(FieldType::Bytes.encode("DEA", Endianness::Little).is_err());
+    }
+
+    #[test]
+    fn test_add_field() {
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        app.jump_to(4, false);
+        app.add_field("size: u32");
+        app.add_field("no type");
+        app.add_field("name: string");
+        assert_eq!(app.fields.len(), 1);
+        assert_eq!(app.get_field_at(7).unwrap().name, "size");
+        assert!(app.get_field_at(8).is_none());
+        assert_eq!(
+            app.decode_field(&app.fields[0]).unwrap(),
+            u32::from_le_bytes([4, 5, 6, 7]).to_string()
+        );
+        app.settings.app.digit_grouping = ",".to_string();
+        assert_eq!(app.decode_field(&app.fields[0]).unwrap(), "117,835,012");
+        assert_eq!(app.group_digits("-1048576"), "-1,048,576");
+        assert_eq!(app.group_digits("100"), "100");
+        assert_eq!(app.group_digits("1234.5"), "1234.5");
+        app.settings.app.digit_grouping.clear();
+
+        app.jump_to(0x10, false);
+        app.toggle_selection();
+        app.jump_to(0x13, false);
+        app.add_field("name: string");
+        app.clear_selection();
+        app.jump_to(0, false);
+        app.add_field("magic: u64");
+        assert_eq!(
+            app.fields
+                .iter()
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["magic", "name"]
+        );
+        assert_eq!(app.fields[1].size, 4);
+
+        assert_eq!(
+            app.fields_to_c_struct(),
+            "#pragma pack(push, 1)\nstruct fields {\n    uint64_t magic; // 0\n    \
+            uint8_t reserved_8[8];\n    char name[4]; // 10\n};\n#pragma pack(pop)\n"
+        );
+
+        app.remove_field(0);
+        assert_eq!(app.fields.len(), 1);
+        app.jump_to(0x3E, false);
+        app.add_field("too_long: u32");
+        assert_eq!(app.fields.len(), 1);
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use crate::app::{log::NotificationLevel, App};
+
+/// A compression format that can be decompressed when a file is opened,
+/// each codec is available only if its feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Files that are larger than this once decompressed are left compressed,
+    /// so that a small file can't fill the memory.
+    pub const MAX_DECOMPRESSED_SIZE: u64 = 1 << 30;
+
+    /// Detects the format from the magic bytes at the start of the file,
+    /// the extension is not trusted.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1F, 0x8B]) {
+            Some(Self::Gzip)
+        } else if bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// Decompresses `bytes`, failing if the result would be larger than `limit` bytes.
+    pub fn decompress(&self, bytes: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+        let mut decompressed = Vec::new();
+        // one byte more than the limit is read to tell if the limit was exceeded
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                use std::io::Read;
+                flate2::read::MultiGzDecoder::new(bytes)
+                    .take(limit.saturating_add(1))
+                    .read_to_end(&mut decompressed)
+                    .map_err(|e| e.to_string())?;
+            }
+            #[cfg(feature = "zstd")]
+            Self::Zstd => {
+                use std::io::Read;
+                let mut source = bytes;
+                ruzstd::StreamingDecoder::new(&mut source)
+                    .map_err(|e| e.to_string())?
+                    .take(limit.saturating_add(1))
+                    .read_to_end(&mut decompressed)
+                    .map_err(|e| e.to_string())?;
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(format!(
+                    "HexPatch was built without the \"{}\" feature",
+                    self.feature()
+                ))
+            }
+        }
+        if decompressed.len() as u64 > limit {
+            return Err(format!("it is larger than {limit} bytes once decompressed"));
+        }
+        Ok(decompressed)
+    }
+
+    fn feature(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+impl Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gzip => write!(f, "gzip"),
+            Self::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+impl App {
+    /// Decompresses the bytes of a file if they are compressed and the `decompress` setting is on,
+    /// returns the bytes unchanged if the file can't be decompressed.
+    pub(in crate::app) fn decompress_file(
+        &mut self,
+        bytes: Vec<u8>,
+    ) -> (Vec<u8>, Option<Compression>) {
+        if !self.settings.app.decompress {
+            return (bytes, None);
+        }
+        let Some(compression) = Compression::detect(&bytes) else {
+            return (bytes, None);
+        };
+        match compression.decompress(&bytes, Compression::MAX_DECOMPRESSED_SIZE) {
+            Ok(decompressed) => (decompressed, Some(compression)),
+            Err(e) => {
+                self.log(
+                    NotificationLevel::Warning,
+                    &format!("Could not decompress the {} file: {}", compression, e),
+                );
+                (bytes, None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_decompress_gzip() {
+        use ratatui::{backend::TestBackend, Terminal};
+        use std::io::Write;
+        let data = b"Hello, world!".repeat(16);
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&data).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(Compression::detect(&compressed), Some(Compression::Gzip));
+        assert_eq!(Compression::detect(&data), None);
+
+        assert!(Compression::Gzip
+            .decompress(&compressed, data.len() as u64 - 1)
+            .is_err());
+
+        // files are decompressed only if the setting is on, it is off by default
+        let app = App::mockup(compressed.clone());
+        assert_eq!(app.data.bytes(), compressed);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("compressed.gz");
+        std::fs::write(&path, &compressed).unwrap();
+        let mut app = App::mockup(vec![0; 0x10]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.settings.app.decompress = true;
+        app.open_file(&path.to_string_lossy(), &mut terminal)
+            .unwrap();
+        assert_eq!(app.data.bytes(), data);
+        assert!(app.data.read_only());
+
+        let broken = vec![0x1F, 0x8B, 0x00];
+        std::fs::write(&path, &broken).unwrap();
+        app.open_file(&path.to_string_lossy(), &mut terminal)
+            .unwrap();
+        assert_eq!(app.data.bytes(), broken);
+        assert!(!app.data.read_only());
+    }
+}
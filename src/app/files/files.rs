@@ -1,18 +1,43 @@
 #![allow(clippy::module_inception)]
-use std::error::Error;
+use std::{
+    error::Error,
+    time::{Duration, Instant},
+};
 
 use ratatui::{backend::Backend, Terminal};
 
 use crate::{
     app::{
-        data::Data, info_mode::InfoMode, log::NotificationLevel, popup::popup_state::PopupState,
+        data::Data,
+        focus::Focus,
+        info_mode::InfoMode,
+        log::NotificationLevel,
+        popup::{
+            binary_choice::BinaryChoice, large_file_choice::LargeFileChoice,
+            popup_state::PopupState,
+        },
+        settings::initial_cursor::InitialCursor,
         App,
     },
     get_app_context,
     headers::Header,
 };
 
-use super::{filesystem::FileSystem, path, path_result::PathResult};
+use super::{
+    filesystem::{FileSystem, PendingRead},
+    path,
+    path_result::PathResult,
+    recent_dirs::RecentDirs,
+    remembered_files::RememberedFiles,
+};
+
+/// A file that is read on another thread while the ui keeps running,
+/// it is opened as `choice` once it is read.
+pub(in crate::app) struct OpeningFile {
+    read: PendingRead,
+    choice: LargeFileChoice,
+    since: Instant,
+}
 
 impl App {
     pub(in crate::app) fn go_to_path<B: Backend>(
@@ -23,7 +48,14 @@ impl App {
         popup: &mut Option<PopupState>,
         terminal: &mut Terminal<B>,
     ) -> Result<(), Box<dyn Error>> {
-        let contents = Self::find_dir_contents(currently_open_path, path, &self.filesystem)?;
+        let follow_symlinks = self.settings.app.follow_symlinks;
+        let contents = Self::find_open_results(
+            currently_open_path,
+            path,
+            &self.filesystem,
+            follow_symlinks,
+            &self.recent_dirs,
+        )?;
         if contents.is_empty() {
             return Err(format!("No files found that matches \"{}\"", path).into());
         }
@@ -32,13 +64,246 @@ impl App {
             .nth(scroll)
             .expect("Scroll out of bounds for go_to_path.");
 
-        if self.filesystem.is_dir(selected.path()) {
-            Self::open_dir(popup, selected.path(), &mut self.filesystem)?;
+        if selected.is_broken() {
+            self.log(
+                NotificationLevel::Warning,
+                &format!(
+                    "\"{}\" is a broken link to \"{}\"",
+                    selected.path(),
+                    selected.link_target().unwrap_or_default()
+                ),
+            );
+            Self::open_dir(
+                popup,
+                currently_open_path,
+                &mut self.filesystem,
+                follow_symlinks,
+                &self.recent_dirs,
+            )?;
+        } else if self.filesystem.is_dir(selected.path()) {
+            Self::open_dir(
+                popup,
+                selected.path(),
+                &mut self.filesystem,
+                follow_symlinks,
+                &self.recent_dirs,
+            )?;
+        } else {
+            self.request_open_file(selected.path(), popup, terminal)?;
+        }
+
+        Ok(())
+    }
+
+    /// Opens the file, unless it is larger than the size set in the settings,
+    /// in that case the file is opened as set in the `large_file_action` setting.
+    pub(in crate::app) fn request_open_file<B: Backend>(
+        &mut self,
+        path: &str,
+        popup: &mut Option<PopupState>,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        let size = self.filesystem.file_size(path)?;
+        let large_file_size = self
+            .settings
+            .app
+            .large_file_size
+            .saturating_mul(1024 * 1024);
+        if large_file_size != 0 && size > large_file_size {
+            match self.settings.app.large_file_action.choice() {
+                None => {
+                    *popup = Some(PopupState::OpenLargeFile {
+                        path: path.to_string(),
+                        size,
+                        choice: LargeFileChoice::Open,
+                    });
+                }
+                Some(choice) => {
+                    if choice == LargeFileChoice::Cancel {
+                        self.log(
+                            NotificationLevel::Warning,
+                            &format!(
+                                "\"{}\" is larger than {} MiB, it was not opened",
+                                path, self.settings.app.large_file_size
+                            ),
+                        );
+                    } else {
+                        *popup = None;
+                    }
+                    self.open_large_file(path, choice, popup, terminal)?;
+                }
+            }
         } else {
-            self.open_file(selected.path(), terminal)?;
             *popup = None;
+            self.open_file_as(path, LargeFileChoice::Open, terminal)?;
         }
+        Ok(())
+    }
 
+    /// Reads a file, giving up after the time set in the `read_timeout` setting.
+    /// If the same file timed out before, the read that is still running is waited for.
+    pub(in crate::app) fn read_file(&mut self, path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.filesystem.read_with_timeout(
+            path,
+            Duration::from_secs(self.settings.app.read_timeout),
+            &mut self.pending_read,
+        )
+    }
+
+    /// Opens the file as chosen in `choice`. If the `read_timeout` setting is on,
+    /// a local file is read on another thread and opened by [`App::poll_opening_file`].
+    fn open_file_as<B: Backend>(
+        &mut self,
+        path: &str,
+        choice: LargeFileChoice,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.settings.app.read_timeout == 0
+            || !matches!(self.filesystem, FileSystem::Local { .. })
+        {
+            return self.open_file_with(path, None, choice, terminal);
+        }
+        // the read that timed out is reused, a fifo for example can only be read once
+        let read = match self.pending_read.take() {
+            Some(read) if read.path() == path => read,
+            _ => PendingRead::start(path),
+        };
+        self.opening_file = Some(OpeningFile {
+            read,
+            choice,
+            since: Instant::now(),
+        });
+        self.log(
+            NotificationLevel::Info,
+            &format!("Reading file: \"{}\"", path),
+        );
+        Ok(())
+    }
+
+    /// Opens the file being read on another thread if the read is over,
+    /// asks whether to keep waiting after the time set in the `read_timeout` setting.
+    pub(in crate::app) fn poll_opening_file<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        let Some(opening_file) = &self.opening_file else {
+            return Ok(());
+        };
+        let path = opening_file.read.path().to_string();
+        match opening_file.read.poll() {
+            Some(bytes) => {
+                let choice = opening_file.choice;
+                self.opening_file = None;
+                if matches!(self.popup, Some(PopupState::ReadTimedOut { .. })) {
+                    self.popup = None;
+                }
+                self.open_file_with(&path, Some(bytes?), choice, terminal)?;
+            }
+            None => {
+                let timeout = Duration::from_secs(self.settings.app.read_timeout);
+                if self.popup.is_none() && opening_file.since.elapsed() >= timeout {
+                    self.log(
+                        NotificationLevel::Warning,
+                        &format!("Reading \"{}\" timed out", path),
+                    );
+                    self.popup = Some(PopupState::ReadTimedOut {
+                        path,
+                        choice: BinaryChoice::Yes,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Answers the popup shown when reading a file times out, the read goes on
+    /// for another `read_timeout` seconds or it is abandoned.
+    pub(in crate::app) fn answer_read_timed_out<B: Backend>(
+        &mut self,
+        path: &str,
+        keep_waiting: bool,
+        popup: &mut Option<PopupState>,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        if keep_waiting {
+            if let Some(opening_file) = &mut self.opening_file {
+                opening_file.since = Instant::now();
+            }
+            Ok(())
+        } else {
+            self.opening_file = None;
+            self.log(
+                NotificationLevel::Warning,
+                &format!("Stopped reading \"{}\"", path),
+            );
+            // opening the file is canceled as if it was too large
+            self.open_large_file(path, LargeFileChoice::Cancel, popup, terminal)
+        }
+    }
+
+    /// Opens the file as chosen in `choice`, reading it unless `bytes` were already read.
+    fn open_file_with<B: Backend>(
+        &mut self,
+        path: &str,
+        bytes: Option<Vec<u8>>,
+        choice: LargeFileChoice,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        let open = |app: &mut Self, terminal: &mut Terminal<B>| match bytes {
+            Some(bytes) => app.open_bytes(path, bytes, terminal),
+            None => app.open_file(path, terminal),
+        };
+        match choice {
+            LargeFileChoice::Open => open(self, terminal)?,
+            LargeFileChoice::ReadOnly => {
+                open(self, terminal)?;
+                self.data.set_read_only(true);
+                self.log(NotificationLevel::Info, "The file is read-only");
+            }
+            LargeFileChoice::NoDisassembly => {
+                // the setting is only turned off while the file is opened,
+                // then the file keeps disassembly off on its own
+                let disassembly = std::mem::replace(&mut self.settings.app.disassembly, false);
+                let opened = open(self, terminal);
+                self.settings.app.disassembly = disassembly;
+                opened?;
+                self.skip_disassembly = true;
+                self.log(
+                    NotificationLevel::Info,
+                    "Disassembly disabled, use the disasm command to enable it",
+                );
+            }
+            LargeFileChoice::Cancel => unreachable!("A canceled file is not opened"),
+        }
+        Ok(())
+    }
+
+    pub(in crate::app) fn open_large_file<B: Backend>(
+        &mut self,
+        path: &str,
+        choice: LargeFileChoice,
+        popup: &mut Option<PopupState>,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        match choice {
+            LargeFileChoice::Open | LargeFileChoice::ReadOnly | LargeFileChoice::NoDisassembly => {
+                self.open_file_as(path, choice, terminal)?;
+            }
+            LargeFileChoice::Cancel => {
+                // nothing is open yet if the file was passed on the command line
+                if self.data.is_empty() {
+                    if let Some(parent) = path::parent(path) {
+                        Self::open_dir(
+                            popup,
+                            parent,
+                            &mut self.filesystem,
+                            self.settings.app.follow_symlinks,
+                            &self.recent_dirs,
+                        )?;
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -57,15 +322,16 @@ impl App {
         currently_open_path: &str,
         path: &str,
         filesystem: &FileSystem,
+        follow_symlinks: bool,
     ) -> Result<Vec<PathResult>, Box<dyn Error>> {
         let mut ret = Vec::new();
         let (selected_dir, file_name) = if path::is_absolute(path) {
             if filesystem.is_dir(path) {
-                (filesystem.canonicalize(path)?, "".to_string())
+                (filesystem.resolve(path, follow_symlinks)?, "".to_string())
             } else if let Some(parent) = path::parent(path) {
                 if filesystem.is_dir(parent) {
                     (
-                        filesystem.canonicalize(parent)?,
+                        filesystem.resolve(parent, follow_symlinks)?,
                         path::filename(path).map_or("".into(), |name| name.to_string()),
                     )
                 } else {
@@ -92,6 +358,7 @@ impl App {
             if let Ok(result) = PathResult::new(
                 &path::join(&selected_dir, &entry, filesystem.separator()),
                 filesystem,
+                follow_symlinks,
             ) {
                 ret.push(result);
             }
@@ -100,17 +367,67 @@ impl App {
         Ok(ret)
     }
 
+    /// The results of the open popup, the recent directories are listed after the contents
+    /// of `currently_open_path` while nothing has been typed.
+    pub(in crate::app) fn find_open_results(
+        currently_open_path: &str,
+        path: &str,
+        filesystem: &FileSystem,
+        follow_symlinks: bool,
+        recent_dirs: &RecentDirs,
+    ) -> Result<Vec<PathResult>, Box<dyn Error>> {
+        let mut results =
+            Self::find_dir_contents(currently_open_path, path, filesystem, follow_symlinks)?;
+        if path.is_empty() {
+            results.extend(recent_dirs.results(currently_open_path, filesystem));
+        }
+        Ok(results)
+    }
+
+    /// Opens the path given on the command line, a directory is shown in the file browser,
+    /// anything that is neither a file nor a directory is browsed from its parent directory.
+    pub(in crate::app) fn open_start_path<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = self.filesystem.pwd().to_string();
+        if self.filesystem.is_file(&path) {
+            let mut popup = None;
+            self.request_open_file(&path, &mut popup, terminal)?;
+            self.popup = popup;
+            return Ok(());
+        }
+        let dir = if self.filesystem.is_dir(&path) {
+            path
+        } else {
+            self.log(
+                NotificationLevel::Warning,
+                &format!("\"{}\" is not a file or a directory", path),
+            );
+            path::parent(&path).unwrap_or(&path).to_string()
+        };
+        Self::open_dir(
+            &mut self.popup,
+            &dir,
+            &mut self.filesystem,
+            self.settings.app.follow_symlinks,
+            &self.recent_dirs,
+        )
+    }
+
     pub(in crate::app) fn open_dir(
         popup: &mut Option<PopupState>,
         path: &str,
         filesystem: &mut FileSystem,
+        follow_symlinks: bool,
+        recent_dirs: &RecentDirs,
     ) -> Result<(), Box<dyn Error>> {
-        let path = filesystem.canonicalize(path)?;
+        let path = filesystem.resolve(path, follow_symlinks)?;
         *popup = Some(PopupState::Open {
             currently_open_path: path.clone(),
             path: "".into(),
             cursor: 0,
-            results: Self::find_dir_contents(&path, "", filesystem)?,
+            results: Self::find_open_results(&path, "", filesystem, follow_symlinks, recent_dirs)?,
             scroll: 0,
         });
         Ok(())
@@ -125,6 +442,7 @@ impl App {
                 ),
                 // TODO: maybe add info for a more detailed log
                 Header::CustomHeader(_) => self.log(NotificationLevel::Info, "File type: Custom"),
+                Header::Raw(_) => self.log(NotificationLevel::Info, "File type: Raw"),
                 Header::None => unreachable!(),
             }
             self.log(
@@ -139,8 +457,24 @@ impl App {
                 NotificationLevel::Info,
                 &format!("Entry point: {:#X}", self.header.entry_point()),
             );
+            let mut previous_end = 0;
             for section in self.header.get_sections() {
                 self.log(NotificationLevel::Info, &format!("Section: {}", section));
+                if let Some(malformation) =
+                    section.malformation(previous_end, self.data.len() as u64)
+                {
+                    self.log(
+                        NotificationLevel::Warning,
+                        &format!("Section {} is malformed: {}", section.name, malformation),
+                    );
+                }
+                previous_end = previous_end.max(section.file_offset.saturating_add(section.size));
+            }
+            if let Some((offset, size)) = self.header.get_overlay(self.data.len() as u64) {
+                self.log(
+                    NotificationLevel::Info,
+                    &format!("Overlay: {} bytes at {:#X}", size, offset),
+                );
             }
         } else {
             self.log(NotificationLevel::Info, "No header found. Assuming 64-bit.");
@@ -164,26 +498,72 @@ impl App {
             NotificationLevel::Info,
             &format!("Opening file: \"{}\"", path),
         );
+        Self::print_loading_status(
+            &self.settings.color,
+            &format!("Opening \"{}\"...", path),
+            terminal,
+        )?;
+        // the file is read first so that the open file is kept if the read fails
+        let bytes = self.read_file(path)?;
+        self.open_bytes(path, bytes, terminal)
+    }
+
+    /// Replaces the open file with `bytes`, read from the file at `path`.
+    fn open_bytes<B: Backend>(
+        &mut self,
+        path: &str,
+        bytes: Vec<u8>,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.save_session();
+        self.remember_file();
 
         self.filesystem.cd(path);
+        self.push_recent_dir();
         self.info_mode = InfoMode::Text;
+        self.previous_info_mode = None;
+        self.focus = Focus::Hex;
         self.scroll = 0;
         self.cursor = (0, 0);
+        self.selection_anchor = None;
+        self.shift_selection = false;
+        self.pinned = None;
+        self.mark = None;
+        self.compare_range = None;
+        self.selection_differences.clear();
+        self.follow = None;
+        self.disassembly_base = 0;
+        self.skip_disassembly = false;
+        self.fields.clear();
+        self.color_tags.clear();
+        self.assembly_folds.clear();
+        self.clear_recent_offsets();
 
         self.screen_size = Self::get_size(terminal)?;
         self.block_size = 8;
         self.vertical_margin = 2;
-        self.blocks_per_row = Self::calc_blocks_per_row(self.block_size, self.screen_size.0);
 
-        Self::print_loading_status(
-            &self.settings.color,
-            &format!("Opening \"{}\"...", path),
-            terminal,
-        )?;
-        self.data = Data::new(
-            self.filesystem.read(self.filesystem.pwd())?,
-            self.settings.app.history_limit,
-        );
+        let compressed_size = bytes.len();
+        let (bytes, compression) = self.decompress_file(bytes);
+        self.data = Data::new(bytes, self.settings.app.history_limit);
+        self.keep_original_bytes();
+        // the width of the compact address column depends on the size of the file
+        self.blocks_per_row = self.get_blocks_per_row(self.screen_size.0);
+        if let Some(compression) = compression {
+            // the file can't be compressed again when saving
+            self.data.set_read_only(true);
+            self.log(
+                NotificationLevel::Info,
+                &format!(
+                    "Decompressed {} file from {} to {} bytes, the file is read-only",
+                    compression,
+                    compressed_size,
+                    self.data.len()
+                ),
+            );
+        }
+
+        self.restore_remembered_file();
 
         Self::print_loading_status(&self.settings.color, "Decoding binary data...", terminal)?;
 
@@ -195,8 +575,8 @@ impl App {
             terminal,
         )?;
 
-        (self.assembly_offsets, self.assembly_instructions) =
-            Self::sections_from_bytes(self.data.bytes(), &self.header);
+        self.disassemble();
+        self.jump_to(self.get_initial_cursor_offset(), false);
 
         Self::print_loading_status(&self.settings.color, "Opening ui...", terminal)?;
         self.log_header_info();
@@ -206,6 +586,26 @@ impl App {
         Ok(())
     }
 
+    /// The offset chosen by the `initial_cursor` setting, 0 if it can't be found.
+    fn get_initial_cursor_offset(&self) -> usize {
+        let entry_point = || match self.header {
+            Header::GenericHeader(_) | Header::CustomHeader(_) => self
+                .header
+                .virtual_to_physical_address(self.header.entry_point())
+                .map(|offset| offset as usize),
+            Header::Raw(_) | Header::None => None,
+        };
+        let first_non_zero = || self.data.bytes().iter().position(|&byte| byte != 0);
+        match self.settings.app.initial_cursor {
+            InitialCursor::Start => None,
+            InitialCursor::EntryPoint => entry_point(),
+            InitialCursor::FirstNonZero => first_non_zero(),
+            InitialCursor::Auto => entry_point().or_else(first_non_zero),
+        }
+        .filter(|&offset| offset < self.data.len())
+        .unwrap_or(0)
+    }
+
     pub(in crate::app) fn save_file_as(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
         if let Some(parent) = path::parent(path) {
             self.filesystem.mkdirs(parent)?;
@@ -213,15 +613,129 @@ impl App {
 
         self.filesystem.create(path)?;
         self.filesystem.cd(&self.filesystem.canonicalize(path)?);
-        self.save_file()?;
+        self.write_file()?;
+        Ok(())
+    }
+
+    /// Reads the open file again from disk, discarding every change and the undo history.
+    pub(in crate::app) fn reload_file(&mut self) -> Result<(), Box<dyn Error>> {
+        let path = self.filesystem.pwd().to_string();
+        let bytes = self.read_file(&path)?;
+        let (bytes, _) = self.decompress_file(bytes);
+        let read_only = self.data.read_only();
+        self.data = Data::new(bytes, self.settings.app.history_limit);
+        self.data.set_read_only(read_only);
+        self.keep_original_bytes();
+        self.color_tags.clear();
+        self.header = self.parse_header();
+        self.disassemble();
+        self.resize_to_size(self.screen_size.0, self.screen_size.1);
         Ok(())
     }
 
+    pub(in crate::app) fn revert_file(&mut self) -> Result<(), Box<dyn Error>> {
+        let offset = self.get_cursor_position().global_byte_index;
+        self.reload_file()?;
+        self.jump_to(offset.min(self.data.len().saturating_sub(1)), false);
+        self.log(NotificationLevel::Info, "Reverted all changes");
+        Ok(())
+    }
+
+    /// Writes the selected bytes to a new file and opens it in place of the current one,
+    /// this is refused if the current file has unsaved changes.
+    pub(in crate::app) fn extract_selection_as<B: Backend>(
+        &mut self,
+        path: &str,
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.data.dirty() {
+            self.log(
+                NotificationLevel::Warning,
+                "Save or undo your changes before extracting the selection.",
+            );
+            return Ok(());
+        }
+        let bytes = match self.get_selection_bytes() {
+            Some(bytes) => bytes.to_vec(),
+            None => {
+                self.log(NotificationLevel::Warning, "Nothing is selected.");
+                return Ok(());
+            }
+        };
+
+        if let Some(parent) = path::parent(path) {
+            self.filesystem.mkdirs(parent)?;
+        };
+        self.filesystem.create(path)?;
+        let path = self.filesystem.canonicalize(path)?;
+        self.filesystem.write(&path, &bytes)?;
+        self.log(
+            NotificationLevel::Info,
+            &format!("Extracted {} bytes to {}", bytes.len(), path),
+        );
+        self.open_file(&path, terminal)
+    }
+
+    /// Saves the changes to the open file, if there are none the file is written again
+    /// only if the `save_unchanged` setting is on, so that its modification time is kept.
     pub(in crate::app) fn save_file(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.data.read_only() {
+            return Err("The file was opened read-only".into());
+        }
+        if !self.data.dirty() && !self.settings.app.save_unchanged {
+            self.log(NotificationLevel::Info, "No changes to save");
+            return Ok(());
+        }
+        self.write_file()
+    }
+
+    /// Saves the open file, the plugins can change the bytes before they are written
+    /// and the save is aborted if one of them fails.
+    fn write_file(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.data.read_only() {
+            return Err("The file was opened read-only".into());
+        }
+        let mut bytes = self.data.bytes().to_vec();
+        let mut app_context = get_app_context!(self);
+        self.plugin_manager
+            .on_before_save(&mut bytes, &mut app_context)
+            .map_err(|e| format!("Save aborted by a plugin: {}", e))?;
+        // only the bytes changed by the plugins are recorded, they can change the length too
+        let old = self.data.bytes();
+        if bytes != old {
+            let start = old.iter().zip(&bytes).take_while(|(a, b)| a == b).count();
+            let end = old[start..]
+                .iter()
+                .rev()
+                .zip(bytes[start..].iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            let size_changed = old.len() != bytes.len();
+            let old_len = old.len() - start - end;
+            let new = bytes[start..bytes.len() - end].to_vec();
+            if self.data.replace(start, old_len, new) && size_changed {
+                self.refresh_after_insertion();
+            }
+        }
         let mut app_context = get_app_context!(self);
         self.plugin_manager.on_save(&mut app_context);
-        self.filesystem
-            .write(self.filesystem.pwd(), self.data.bytes())?;
+        // the file is written byte for byte unless the user asked for a trailing newline
+        if self.settings.app.trailing_newline
+            && self.data.bytes().last().is_some_and(|&byte| byte != b'\n')
+        {
+            let mut bytes = self.data.bytes().to_vec();
+            bytes.push(b'\n');
+            self.filesystem.write(self.filesystem.pwd(), &bytes)?;
+            if self.file_key.is_some() {
+                self.file_key = Some(RememberedFiles::key(&bytes));
+            }
+        } else {
+            self.filesystem
+                .write(self.filesystem.pwd(), self.data.bytes())?;
+            if self.file_key.is_some() {
+                self.file_key = Some(RememberedFiles::key(self.data.bytes()));
+            }
+        }
         self.data.reset_dirty();
         match &self.filesystem {
             FileSystem::Local { path } => {
@@ -238,3 +752,280 @@ impl App {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use ratatui::backend::TestBackend;
+
+    use crate::app::settings::large_file_action::LargeFileAction;
+
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir_path = dir.path().canonicalize().unwrap();
+        let file_path = dir_path.join("file");
+        std::fs::write(&file_path, [1, 2, 3]).unwrap();
+        std::os::unix::fs::symlink(&file_path, dir_path.join("link")).unwrap();
+        std::os::unix::fs::symlink(dir_path.join("missing"), dir_path.join("broken")).unwrap();
+        let dir_path = dir_path.to_string_lossy().to_string();
+        let filesystem = FileSystem::new_local(&dir_path).unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+
+        let results = App::find_dir_contents(&dir_path, "", &filesystem, false).unwrap();
+        let link = results
+            .iter()
+            .find(|result| result.path().ends_with("link"))
+            .unwrap();
+        assert_eq!(link.link_target(), Some(file_path.as_str()));
+        assert!(!link.is_broken());
+        let broken = results
+            .iter()
+            .find(|result| result.path().ends_with("broken"))
+            .unwrap();
+        assert!(broken.is_broken());
+        let unresolved = format!("{}/missing/../link", dir_path);
+        assert_eq!(
+            filesystem.resolve(&unresolved, false).unwrap(),
+            format!("{}/link", dir_path)
+        );
+
+        let results = App::find_dir_contents(&dir_path, "", &filesystem, true).unwrap();
+        let link = results
+            .iter()
+            .find(|result| result.link_target().is_some() && !result.is_broken())
+            .unwrap();
+        assert_eq!(link.path(), file_path);
+        assert!(results.iter().any(|result| result.is_broken()));
+    }
+
+    #[test]
+    fn test_open_start_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir_path = dir.path().canonicalize().unwrap();
+        std::fs::write(dir_path.join("file"), [1, 2, 3]).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.filesystem = FileSystem::new_local(&dir_path.to_string_lossy()).unwrap();
+        app.open_start_path(&mut terminal).unwrap();
+        assert!(matches!(
+            app.popup,
+            Some(PopupState::Open { ref currently_open_path, .. })
+                if currently_open_path.as_str() == dir_path.to_string_lossy()
+        ));
+        assert_eq!(app.data.len(), 0x10);
+
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.filesystem = FileSystem::new_local(&dir_path.join("file").to_string_lossy()).unwrap();
+        app.open_start_path(&mut terminal).unwrap();
+        assert!(app.popup.is_none());
+        assert_eq!(app.data.bytes(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_extract_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("extracted.bin");
+        let path = path.to_string_lossy();
+        let mut app = App::mockup((0..0x40).collect());
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.resize_to_size(80, 24);
+        app.jump_to(4, false);
+        app.toggle_selection();
+        app.jump_to(7, false);
+        app.extract_selection_as(&path, &mut terminal).unwrap();
+        assert_eq!(app.data.bytes(), &[4, 5, 6, 7]);
+        assert_eq!(std::fs::read(path.as_ref()).unwrap(), vec![4, 5, 6, 7]);
+        assert_eq!(app.get_selection(), None);
+    }
+
+    #[test]
+    fn test_open_large_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.bin");
+        std::fs::write(&path, vec![0; 1024 * 1024 + 1]).unwrap();
+        let path = path.canonicalize().unwrap().to_string_lossy().to_string();
+        let mut app = App::mockup(vec![0; 0x10]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.settings.app.large_file_size = 1;
+        let mut popup = None;
+        app.request_open_file(&path, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(matches!(popup, Some(PopupState::OpenLargeFile { .. })));
+        assert_eq!(app.data.len(), 0x10);
+
+        app.open_large_file(&path, LargeFileChoice::ReadOnly, &mut popup, &mut terminal)
+            .unwrap();
+        assert_eq!(app.data.len(), 1024 * 1024 + 1);
+        assert_eq!(app.data.push_change(0, vec![1]), 0);
+        assert!(app.save_file().is_err());
+
+        app.open_large_file(
+            &path,
+            LargeFileChoice::NoDisassembly,
+            &mut popup,
+            &mut terminal,
+        )
+        .unwrap();
+        assert!(!app.is_disassembly_enabled());
+        assert!(app.settings.app.disassembly);
+
+        app.settings.app.large_file_size = 2;
+        app.request_open_file(&path, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(popup.is_none());
+        assert!(!app.data.read_only());
+        assert!(app.is_disassembly_enabled());
+
+        app.settings.app.large_file_size = 1;
+        app.settings.app.large_file_action = LargeFileAction::ReadOnly;
+        app.request_open_file(&path, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(popup.is_none());
+        assert!(app.data.read_only());
+
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.settings.app.large_file_size = 1;
+        app.settings.app.large_file_action = LargeFileAction::Refuse;
+        app.request_open_file(&path, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(popup.is_none());
+        assert_eq!(app.data.len(), 0x10);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_read_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        // reading a fifo blocks until something writes to it
+        let fifo = dir.path().join("fifo");
+        let status = std::process::Command::new("mkfifo")
+            .arg(&fifo)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let fifo = fifo.to_string_lossy().to_string();
+        let mut app = App::mockup(vec![1, 2, 3]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        let path = app.filesystem.pwd().to_string();
+        app.settings.app.read_timeout = 1;
+        let mut popup = None;
+        app.request_open_file(&fifo, &mut popup, &mut terminal)
+            .unwrap();
+        // the read goes on in the background while the open file is kept
+        assert!(popup.is_none());
+        assert!(app.opening_file.is_some());
+        app.poll_opening_file(&mut terminal).unwrap();
+        assert!(app.popup.is_none());
+        std::thread::sleep(Duration::from_millis(1100));
+        app.poll_opening_file(&mut terminal).unwrap();
+        assert!(matches!(app.popup, Some(PopupState::ReadTimedOut { .. })));
+        assert_eq!(app.data.bytes(), &[1, 2, 3]);
+        assert_eq!(app.filesystem.pwd(), path);
+
+        let mut popup = None;
+        app.answer_read_timed_out(&fifo, true, &mut popup, &mut terminal)
+            .unwrap();
+        app.popup = None;
+        let writer_path = fifo.clone();
+        let writer = std::thread::spawn(move || std::fs::write(writer_path, [4, 5, 6]).unwrap());
+        writer.join().unwrap();
+        let start = Instant::now();
+        while app.opening_file.is_some() && start.elapsed() < Duration::from_secs(10) {
+            app.poll_opening_file(&mut terminal).unwrap();
+        }
+        assert_eq!(app.data.bytes(), &[4, 5, 6]);
+        assert!(app.popup.is_none());
+
+        // a canceled read is abandoned and the open file is kept
+        app.request_open_file(&fifo, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(app.opening_file.is_some());
+        app.answer_read_timed_out(&fifo, false, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(app.opening_file.is_none());
+        let writer_path = fifo.clone();
+        std::thread::spawn(move || std::fs::write(writer_path, [7]).unwrap())
+            .join()
+            .unwrap();
+        app.poll_opening_file(&mut terminal).unwrap();
+        assert_eq!(app.data.bytes(), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn test_save_byte_exact() {
+        let bytes = b"no newline\r\n\0\xFF".to_vec();
+        let mut app = App::mockup(bytes.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("exact.bin");
+        let path = path.to_string_lossy();
+        app.save_file_as(&path).unwrap();
+        assert_eq!(std::fs::read(path.as_ref()).unwrap(), bytes);
+
+        app.settings.app.trailing_newline = true;
+        app.save_file().unwrap();
+        assert_eq!(std::fs::read(path.as_ref()).unwrap(), bytes);
+        app.settings.app.save_unchanged = true;
+        app.save_file().unwrap();
+        let mut with_newline = bytes.clone();
+        with_newline.push(b'\n');
+        assert_eq!(std::fs::read(path.as_ref()).unwrap(), with_newline);
+        assert_eq!(app.data.bytes(), bytes);
+
+        app.settings.app.trailing_newline = false;
+        app.save_file().unwrap();
+        assert_eq!(std::fs::read(path.as_ref()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_initial_cursor() {
+        let elf = include_bytes!("../../../test/elf.bin").to_vec();
+        let mut app = App::mockup(elf);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+        let entry_point = app
+            .header
+            .virtual_to_physical_address(app.header.entry_point())
+            .unwrap() as usize;
+        app.settings.app.initial_cursor = InitialCursor::EntryPoint;
+        assert_eq!(app.get_initial_cursor_offset(), entry_point);
+        app.settings.app.initial_cursor = InitialCursor::Auto;
+        assert_eq!(app.get_initial_cursor_offset(), entry_point);
+        app.settings.app.initial_cursor = InitialCursor::FirstNonZero;
+        assert_eq!(app.get_initial_cursor_offset(), 0);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0, 0, 0, 5, 0]).unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        let mut app = App::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.settings.app.initial_cursor = InitialCursor::Auto;
+        app.open_file(&path, &mut terminal).unwrap();
+        assert_eq!(app.get_cursor_position().global_byte_index, 3);
+        app.settings.app.initial_cursor = InitialCursor::EntryPoint;
+        app.open_file(&path, &mut terminal).unwrap();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+    }
+
+    #[test]
+    fn test_revert_file() {
+        let mut app = App::mockup((0..0x40).collect());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("revert.bin");
+        let path = path.to_string_lossy();
+        app.save_file_as(&path).unwrap();
+        app.resize_to_size(80, 24);
+        app.jump_to(0x10, false);
+        app.data.push_change(0x10, vec![0xFF; 4]);
+        app.data.push_change(0x20, vec![0xFF; 4]);
+        app.revert_file().unwrap();
+        assert_eq!(app.data.bytes(), (0..0x40).collect::<Vec<u8>>());
+        assert!(!app.data.dirty());
+        assert!(app.data.undo().is_none());
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+    }
+}
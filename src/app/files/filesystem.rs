@@ -1,9 +1,47 @@
-use std::{error::Error, path::Path};
+use std::{error::Error, io, path::Path, sync::mpsc, time::Duration};
 
 use crate::app::ssh::connection::Connection;
 
 use super::path;
 
+/// A read of a local file running on another thread, the read can't be interrupted
+/// so the thread keeps running even if the result is no longer needed.
+pub struct PendingRead {
+    path: String,
+    receiver: mpsc::Receiver<io::Result<Vec<u8>>>,
+}
+
+impl PendingRead {
+    pub fn start(path: &str) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let thread_path = path.to_string();
+        std::thread::spawn(move || {
+            // the receiver is gone if the read was abandoned
+            let _ = sender.send(std::fs::read(thread_path));
+        });
+        Self {
+            path: path.to_string(),
+            receiver,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the result of the read if it is over, without waiting for it.
+    pub fn poll(&self) -> Option<Result<Vec<u8>, Box<dyn Error>>> {
+        match self.receiver.try_recv() {
+            Ok(bytes) => Some(bytes.map_err(Into::into)),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                let e = io::Error::other("The thread reading the file stopped");
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
+
 pub enum FileSystem {
     Local {
         path: String,
@@ -84,6 +122,52 @@ impl FileSystem {
         }
     }
 
+    /// Reads the file on another thread and gives up after `timeout`, a zero timeout waits forever.
+    /// The read can't be interrupted, if it times out it is kept in `pending` and reading
+    /// the same file again waits for it instead of starting another thread.
+    /// Remote files are read without a timeout.
+    pub fn read_with_timeout(
+        &self,
+        path: &str,
+        timeout: Duration,
+        pending: &mut Option<PendingRead>,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        match self {
+            Self::Local { .. } if !timeout.is_zero() => {
+                let receiver = match pending.take() {
+                    Some(read) if read.path == path => read.receiver,
+                    _ => PendingRead::start(path).receiver,
+                };
+                match receiver.recv_timeout(timeout) {
+                    Ok(bytes) => Ok(bytes?),
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        *pending = Some(PendingRead {
+                            path: path.to_string(),
+                            receiver,
+                        });
+                        Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("Reading \"{}\" timed out", path),
+                        )
+                        .into())
+                    }
+                    Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        Err(io::Error::other("The thread reading the file stopped").into())
+                    }
+                }
+            }
+            _ => self.read(path),
+        }
+    }
+
+    /// The size of the file in bytes.
+    pub fn file_size(&self, path: &str) -> Result<u64, Box<dyn Error>> {
+        match self {
+            Self::Local { .. } => Ok(std::fs::metadata(path)?.len()),
+            Self::Remote { connection, .. } => connection.file_size(path),
+        }
+    }
+
     pub fn mkdirs(&self, path: &str) -> Result<(), Box<dyn Error>> {
         match self {
             Self::Local { .. } => std::fs::create_dir_all(path)?,
@@ -125,6 +209,31 @@ impl FileSystem {
         }
     }
 
+    /// The target of the symbolic link, `None` if the path is not a symbolic link.
+    pub fn read_link(&self, path: &str) -> Option<String> {
+        match self {
+            Self::Local { .. } => std::fs::read_link(path)
+                .ok()
+                .map(|target| target.to_string_lossy().to_string()),
+            Self::Remote { connection, .. } => connection.read_link(path),
+        }
+    }
+
+    /// Canonicalizes the path, if symbolic links should not be followed only the `.` and `..`
+    /// components are resolved so that a link is not replaced by its target.
+    /// Relative paths are resolved from the current directory.
+    pub fn resolve(&self, path: &str, follow_symlinks: bool) -> Result<String, Box<dyn Error>> {
+        if follow_symlinks {
+            return self.canonicalize(path);
+        }
+        let path = if path::is_absolute(path) {
+            path.to_string()
+        } else {
+            path::join(&self.canonicalize(".")?, path, self.separator())
+        };
+        Ok(path::normalize(&path, self.separator()))
+    }
+
     pub fn canonicalize(&self, path: &str) -> Result<String, Box<dyn Error>> {
         match self {
             Self::Local { .. } => Ok(Path::new(path)
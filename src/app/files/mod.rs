@@ -1,4 +1,8 @@
+pub mod compression;
 mod files;
+pub(in crate::app) use files::OpeningFile;
 pub mod filesystem;
 pub mod path;
 pub mod path_result;
+pub mod recent_dirs;
+pub mod remembered_files;
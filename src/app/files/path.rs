@@ -35,6 +35,33 @@ pub fn join(start: &str, end: &str, separator: char) -> String {
     }
 }
 
+/// Resolves the `.` and `..` components of an absolute path without accessing the filesystem,
+/// `..` at the root is ignored.
+pub fn normalize(path: &str, separator: char) -> String {
+    let (prefix, rest) = path
+        .strip_prefix("\\\\?\\")
+        .map_or(("", path), |rest| ("\\\\?\\", rest));
+    let mut components = rest.split(['/', '\\']).filter(|c| !c.is_empty());
+    let mut ret = if rest.starts_with('/') {
+        format!("{}/", prefix)
+    } else {
+        // the drive of a windows path
+        format!("{}{}{}", prefix, components.next().unwrap_or_default(), separator)
+    };
+    for component in components {
+        if component == ".." {
+            ret = parent(&ret).unwrap_or(&ret).to_string();
+        } else if component != "." {
+            ret = join(&ret, component, separator);
+        }
+    }
+    if is_root(&ret) {
+        ret
+    } else {
+        ret.trim_end_matches(['/', '\\']).to_string()
+    }
+}
+
 pub fn filename(path: &str) -> Option<&str> {
     if is_root(path) {
         None
@@ -133,6 +160,22 @@ mod test {
         assert_eq!(join("C:\\Users", ".", '\\'), "C:\\Users");
     }
 
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("/home/user", '/'), "/home/user");
+        assert_eq!(normalize("/home/./user/", '/'), "/home/user");
+        assert_eq!(normalize("/home/user/..", '/'), "/home");
+        assert_eq!(normalize("/home/../..", '/'), "/");
+        assert_eq!(normalize("/home//user/../other", '/'), "/home/other");
+
+        assert_eq!(normalize("C:\\Users\\.\\user", '\\'), "C:\\Users\\user");
+        assert_eq!(normalize("C:\\Users\\..", '\\'), "C:\\");
+        assert_eq!(
+            normalize("\\\\?\\C:\\Users\\user\\..", '\\'),
+            "\\\\?\\C:\\Users"
+        );
+    }
+
     #[test]
     fn test_filename() {
         assert_eq!(filename("/home/user"), Some("user"));
@@ -10,13 +10,45 @@ use super::{filesystem::FileSystem, path};
 pub struct PathResult {
     path: String,
     is_dir: bool,
+    link_target: Option<String>,
+    is_broken: bool,
+    is_recent: bool,
 }
 
 impl PathResult {
-    pub fn new(path: &str, filesystem: &FileSystem) -> Result<Self, Box<dyn Error>> {
-        let path = filesystem.canonicalize(path)?;
+    pub fn new(
+        path: &str,
+        filesystem: &FileSystem,
+        follow_symlinks: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let link_target = filesystem.read_link(path);
+        let is_broken =
+            link_target.is_some() && !filesystem.is_dir(path) && !filesystem.is_file(path);
+        // broken links can't be canonicalized, they are listed with the path of the link
+        let path = if is_broken {
+            path.to_string()
+        } else {
+            filesystem.resolve(path, follow_symlinks)?
+        };
         let is_dir = filesystem.is_dir(&path);
-        Ok(Self { path, is_dir })
+        Ok(Self {
+            path,
+            is_dir,
+            link_target,
+            is_broken,
+            is_recent: false,
+        })
+    }
+
+    /// A recently visited directory, listed with its full path.
+    pub fn recent(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            is_dir: true,
+            link_target: None,
+            is_broken: false,
+            is_recent: true,
+        }
     }
 
     pub fn path(&self) -> &str {
@@ -27,6 +59,20 @@ impl PathResult {
         self.is_dir
     }
 
+    /// The target of the symbolic link, `None` if the path is not a symbolic link.
+    pub fn link_target(&self) -> Option<&str> {
+        self.link_target.as_deref()
+    }
+
+    /// True if the path is a symbolic link to something that does not exist.
+    pub fn is_broken(&self) -> bool {
+        self.is_broken
+    }
+
+    pub fn is_recent(&self) -> bool {
+        self.is_recent
+    }
+
     pub fn to_line(
         &self,
         color_settings: &ColorSettings,
@@ -41,8 +87,27 @@ impl PathResult {
         } else {
             color_settings.path_file
         };
-        let path = path::diff(&self.path, base_path);
+        let path = if self.is_recent {
+            &self.path
+        } else {
+            path::diff(&self.path, base_path)
+        };
         ret.spans.push(Span::styled(path.to_string(), style));
+        if self.is_recent {
+            ret.spans.push(Span::styled(" (recent)", style));
+        }
+        if let Some(target) = &self.link_target {
+            let link_style = if is_selected {
+                color_settings.path_selected
+            } else {
+                color_settings.path_symlink
+            };
+            let broken = if self.is_broken { " (broken)" } else { "" };
+            ret.spans.push(Span::styled(
+                format!(" -> {}{}", target, broken),
+                link_style,
+            ));
+        }
 
         ret.left_aligned()
     }
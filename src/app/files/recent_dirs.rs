@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{log::NotificationLevel, popup::popup_state::PopupState, App};
+
+use super::{filesystem::FileSystem, path, path_result::PathResult};
+
+/// The local directories of the files opened most recently, the most recent first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentDirs {
+    dirs: Vec<String>,
+}
+
+impl RecentDirs {
+    pub const LIMIT: usize = 16;
+
+    /// Loads the list, the directories that no longer exist are removed.
+    pub fn load(path: Option<&Path>) -> Self {
+        let mut recent_dirs = path
+            .map(Path::to_path_buf)
+            .or_else(Self::get_default_path)
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str::<Self>(&text).ok())
+            .unwrap_or_default();
+        recent_dirs.prune();
+        recent_dirs
+    }
+
+    pub fn save(&self, path: Option<&Path>) -> Option<()> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => Self::get_default_path()?,
+        };
+
+        let recent_dirs = serde_json::to_string_pretty(self).ok()?;
+        std::fs::create_dir_all(path.parent()?).ok()?;
+        std::fs::write(&path, recent_dirs).ok()?;
+        Some(())
+    }
+
+    /// Moves `dir` to the top of the list, the oldest directories are dropped past the limit.
+    pub fn push(&mut self, dir: &str) {
+        self.dirs.retain(|other| other != dir);
+        self.dirs.insert(0, dir.to_string());
+        self.dirs.truncate(Self::LIMIT);
+    }
+
+    pub fn prune(&mut self) {
+        self.dirs.retain(|dir| Path::new(dir).is_dir());
+    }
+
+    pub fn dirs(&self) -> &[String] {
+        &self.dirs
+    }
+
+    /// The directories listed after the contents of `currently_open_path` in the open popup,
+    /// only local directories that still exist are listed.
+    pub fn results(&self, currently_open_path: &str, filesystem: &FileSystem) -> Vec<PathResult> {
+        if !matches!(filesystem, FileSystem::Local { .. }) {
+            return Vec::new();
+        }
+        self.dirs
+            .iter()
+            .filter(|dir| *dir != currently_open_path && Path::new(dir).is_dir())
+            .map(|dir| PathResult::recent(dir))
+            .collect()
+    }
+
+    fn get_default_path() -> Option<PathBuf> {
+        let config = dirs::config_dir()?;
+        Some(config.join("HexPatch").join("recent_dirs.json"))
+    }
+}
+
+impl App {
+    /// Remembers the directory of the file that is open, only local directories are kept.
+    pub(in crate::app) fn push_recent_dir(&mut self) {
+        if let FileSystem::Local { path } = &self.filesystem {
+            if let Some(dir) = path::parent(path) {
+                let dir = Path::new(dir)
+                    .canonicalize()
+                    .map(|dir| dir.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| dir.to_string());
+                self.recent_dirs.push(&dir);
+            }
+        }
+    }
+
+    pub(in crate::app) fn save_recent_dirs(&mut self) {
+        if self.recent_dirs.save(None).is_none() {
+            self.log(
+                NotificationLevel::Warning,
+                "Could not save the recent directories",
+            );
+        }
+    }
+
+    pub(in crate::app) fn request_popup_recent_dirs(&mut self) {
+        if !matches!(self.filesystem, FileSystem::Local { .. }) {
+            self.log(
+                NotificationLevel::Warning,
+                "Recent directories are only available for local files.",
+            );
+            return;
+        }
+        self.recent_dirs.prune();
+        let dirs = self.recent_dirs.dirs().to_vec();
+        if dirs.is_empty() {
+            self.log(NotificationLevel::Warning, "No recent directories.");
+            return;
+        }
+        self.popup = Some(PopupState::RecentDirs { dirs, scroll: 0 });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_recent_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = |name: &str| {
+            let dir = root.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            dir.to_string_lossy().to_string()
+        };
+        let mut recent_dirs = RecentDirs::default();
+        let dirs = (0..RecentDirs::LIMIT + 1)
+            .map(|i| dir(&i.to_string()))
+            .collect::<Vec<_>>();
+        for dir in dirs.iter() {
+            recent_dirs.push(dir);
+        }
+        recent_dirs.push(&dirs[5]);
+        assert_eq!(recent_dirs.dirs().len(), RecentDirs::LIMIT);
+        assert_eq!(recent_dirs.dirs()[0], dirs[5]);
+        assert_eq!(recent_dirs.dirs()[1], dirs[RecentDirs::LIMIT]);
+        assert!(!recent_dirs.dirs().contains(&dirs[0]));
+
+        let file = root.path().join("recent_dirs.json");
+        recent_dirs.save(Some(&file)).unwrap();
+        std::fs::remove_dir(&dirs[5]).unwrap();
+        let loaded = RecentDirs::load(Some(&file));
+        assert_eq!(loaded.dirs().len(), RecentDirs::LIMIT - 1);
+        assert_eq!(loaded.dirs()[0], dirs[RecentDirs::LIMIT]);
+
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.recent_dirs = loaded;
+        app.request_popup_recent_dirs();
+        assert!(matches!(
+            app.popup,
+            Some(PopupState::RecentDirs { ref dirs, scroll: 0 }) if dirs.len() == RecentDirs::LIMIT - 1
+        ));
+    }
+
+    #[test]
+    fn test_recent_dirs_in_open_popup() {
+        let root = tempfile::tempdir().unwrap();
+        let current = root.path().join("current");
+        std::fs::create_dir(&current).unwrap();
+        std::fs::write(current.join("file.bin"), [0; 0x10]).unwrap();
+        let recent = root.path().canonicalize().unwrap().join("recent");
+        std::fs::create_dir(&recent).unwrap();
+        let recent = recent.to_string_lossy().to_string();
+
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.filesystem =
+            FileSystem::new_local(&current.join("file.bin").to_string_lossy()).unwrap();
+        app.recent_dirs = RecentDirs::default();
+        app.recent_dirs.push(&recent);
+        app.request_open().unwrap();
+        let Some(PopupState::Open {
+            currently_open_path,
+            results,
+            ..
+        }) = app.popup.clone()
+        else {
+            panic!("The open popup should be open");
+        };
+        // the parent directory and the file come first
+        assert_eq!(results.len(), 3);
+        assert!(!results[1].is_recent());
+        assert!(results[2].is_recent());
+        assert_eq!(results[2].path(), recent);
+
+        // the recent directories are hidden while a path is typed
+        let typed = App::find_open_results(
+            &currently_open_path,
+            "f",
+            &app.filesystem,
+            false,
+            &app.recent_dirs,
+        )
+        .unwrap();
+        assert_eq!(typed.len(), 1);
+
+        let mut popup = None;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 25)).unwrap();
+        app.go_to_path(&currently_open_path, "", 2, &mut popup, &mut terminal)
+            .unwrap();
+        assert!(matches!(
+            popup,
+            Some(PopupState::Open { ref currently_open_path, .. }) if *currently_open_path == recent
+        ));
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{asm::fold::Fold, fields::Field, log::NotificationLevel, App},
+    headers::arch_override::{architecture_name, parse_architecture, ArchOverride},
+};
+
+/// What is remembered about a file between two runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RememberedFile {
+    #[serde(default)]
+    pub architecture: Option<String>,
+    #[serde(default)]
+    pub thumb: bool,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    #[serde(default)]
+    pub folds: Vec<Fold>,
+}
+
+impl RememberedFile {
+    pub fn is_empty(&self) -> bool {
+        self.architecture.is_none() && self.fields.is_empty() && self.folds.is_empty()
+    }
+
+    pub fn arch_override(&self) -> Option<ArchOverride> {
+        let architecture = parse_architecture(self.architecture.as_ref()?).ok()?;
+        ArchOverride::new(architecture, self.thumb).ok()
+    }
+}
+
+/// The files that were opened before, keyed by the hash of their content
+/// so that they are recognized even if they are moved or renamed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RememberedFiles {
+    files: BTreeMap<String, RememberedFile>,
+}
+
+impl RememberedFiles {
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Option<()> {
+        let files = serde_json::to_string_pretty(self).ok()?;
+        std::fs::create_dir_all(path.parent()?).ok()?;
+        std::fs::write(path, files).ok()?;
+        Some(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&RememberedFile> {
+        self.files.get(key)
+    }
+
+    /// Stores the file under `key`, files with nothing to remember are removed.
+    pub fn set(&mut self, key: &str, file: RememberedFile) {
+        if file.is_empty() {
+            self.files.remove(key);
+        } else {
+            self.files.insert(key.to_string(), file);
+        }
+    }
+
+    /// The FNV-1a hash of the content followed by its length.
+    pub fn key(bytes: &[u8]) -> String {
+        let hash = bytes.iter().fold(0xCBF29CE484222325u64, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001B3)
+        });
+        format!("{:016x}-{}", hash, bytes.len())
+    }
+
+    pub fn get_default_path() -> Option<PathBuf> {
+        let config = dirs::config_dir()?;
+        Some(config.join("HexPatch").join("files.json"))
+    }
+}
+
+impl App {
+    /// Saves the architecture override, the fields and the folds of the open file,
+    /// does nothing if the file was not recognized when it was opened.
+    pub(in crate::app) fn remember_file(&mut self) {
+        let (Some(path), Some(key)) = (&self.remembered_files_path, &self.file_key) else {
+            return;
+        };
+        let arch_override = self.arch_override.or(self.file_arch_override);
+        let file = RememberedFile {
+            architecture: arch_override
+                .and_then(|arch_override| architecture_name(arch_override.architecture))
+                .map(str::to_string),
+            thumb: arch_override.is_some_and(|arch_override| arch_override.thumb),
+            fields: self.fields.clone(),
+            folds: self.assembly_folds.clone(),
+        };
+        let mut files = RememberedFiles::load(path);
+        files.set(key, file);
+        if files.save(path).is_none() {
+            self.log(
+                NotificationLevel::Warning,
+                "Could not save the settings of the file",
+            );
+        }
+    }
+
+    /// Recognizes the file that was just read and restores what was remembered about it,
+    /// an architecture given from the command line has the precedence.
+    pub(in crate::app) fn restore_remembered_file(&mut self) {
+        self.file_key = None;
+        self.file_arch_override = None;
+        if !self.settings.app.remember_files {
+            return;
+        }
+        let Some(path) = &self.remembered_files_path else {
+            return;
+        };
+        let key = RememberedFiles::key(self.data.bytes());
+        let files = RememberedFiles::load(path);
+        if let Some(file) = files.get(&key) {
+            self.file_arch_override = file.arch_override();
+            self.fields = file.fields.clone();
+            self.fields.retain(|field| field.is_valid(self.data.len()));
+            self.assembly_folds = file.folds.clone();
+            self.assembly_folds
+                .retain(|fold| fold.start < fold.end && fold.end <= self.data.len());
+            self.log(
+                NotificationLevel::Info,
+                "Restored the settings saved for this file",
+            );
+        }
+        self.file_key = Some(key);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use object::Architecture;
+
+    use crate::app::fields::{Field, FieldType};
+
+    use super::*;
+
+    #[test]
+    fn test_remembered_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("files.json");
+        let data = (0..0x40).collect::<Vec<u8>>();
+
+        let mut app = App::mockup(data.clone());
+        app.settings.app.remember_files = true;
+        app.remembered_files_path = Some(path.clone());
+        app.restore_remembered_file();
+        assert!(app.fields.is_empty());
+        app.arch_override = Some(ArchOverride::new(Architecture::Arm, true).unwrap());
+        app.jump_to(4, false);
+        app.add_field("magic: u32");
+        // fields that don't match their type or overflow are not restored
+        let field = |offset, size| Field {
+            name: "invalid".to_string(),
+            offset,
+            size,
+            field_type: FieldType::U32,
+        };
+        app.fields.push(field(8, 2));
+        app.fields.push(field(usize::MAX, 4));
+        app.remember_file();
+
+        let mut app = App::mockup(data.clone());
+        app.settings.app.remember_files = true;
+        app.remembered_files_path = Some(path.clone());
+        app.restore_remembered_file();
+        assert_eq!(app.fields.len(), 1);
+        assert_eq!(app.fields[0].offset, 4);
+        assert_eq!(
+            app.file_arch_override,
+            Some(ArchOverride::new(Architecture::Arm, true).unwrap())
+        );
+
+        app.fields.clear();
+        app.file_arch_override = None;
+        app.remember_file();
+        assert_eq!(RememberedFiles::load(&path), RememberedFiles::default());
+
+        let mut app = App::mockup(data[1..].to_vec());
+        app.settings.app.remember_files = true;
+        app.remembered_files_path = Some(path.clone());
+        app.restore_remembered_file();
+        assert!(app.fields.is_empty());
+        assert_ne!(
+            RememberedFiles::key(&data),
+            RememberedFiles::key(&data[1..])
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use super::{log::NotificationLevel, App};
+
+impl App {
+    /// Parses a fill request in the form `PATTERN * LENGTH`,
+    /// the pattern is written in hex (spaces are ignored) and the length can be decimal or hex with the `0x` prefix.
+    pub(super) fn parse_fill_pattern(text: &str) -> Result<(Vec<u8>, usize), String> {
+        let (pattern, length) = text
+            .rsplit_once('*')
+            .ok_or("Expected a pattern and a length separated by \"*\"")?;
+        let pattern = pattern
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        if pattern.is_empty() || pattern.len() % 2 != 0 {
+            return Err(format!("Invalid pattern \"{}\"", pattern));
+        }
+        let pattern = (0..pattern.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&pattern[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| format!("Invalid pattern \"{}\"", pattern))?;
+        let length = length.trim();
+        let length = match length.strip_prefix("0x") {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => length.parse(),
+        }
+        .map_err(|_| format!("Invalid length \"{}\"", length))?;
+        Ok((pattern, length))
+    }
+
+    /// Writes the pattern repeated until it reaches the requested length,
+    /// starting from the selection if there is one, otherwise from the cursor.
+    /// The write is a single change in the undo history.
+    pub(super) fn fill_pattern(&mut self, text: &str) {
+        let (pattern, length) = match Self::parse_fill_pattern(text) {
+            Ok(fill) => fill,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return;
+            }
+        };
+        if let Some((start, _end)) = self.get_selection() {
+            self.jump_to(start, false);
+        }
+        let bytes = pattern
+            .iter()
+            .cycle()
+            .take(length)
+            .copied()
+            .collect::<Vec<_>>();
+        self.patch_bytes(&bytes, false);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_fill_pattern() {
+        assert_eq!(
+            App::parse_fill_pattern("DE AD * 5"),
+            Ok((vec![0xDE, 0xAD], 5))
+        );
+        assert_eq!(
+            App::parse_fill_pattern("00*0x10"),
+            Ok((vec![0x00], 0x10))
+        );
+        assert!(App::parse_fill_pattern("DEAD").is_err());
+        assert!(App::parse_fill_pattern("DEA * 5").is_err());
+        assert!(App::parse_fill_pattern("XY * 5").is_err());
+        assert!(App::parse_fill_pattern("DE * five").is_err());
+    }
+
+    #[test]
+    fn test_fill_pattern() {
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.resize_to_size(80, 24);
+        app.jump_to(2, false);
+        app.fill_pattern("DE AD * 5");
+        assert_eq!(
+            &app.data.bytes()[..8],
+            &[0, 0, 0xDE, 0xAD, 0xDE, 0xAD, 0xDE, 0]
+        );
+        app.fill_pattern("FF * 0x100");
+        assert!(app.data.bytes()[2..].iter().all(|b| *b == 0xFF));
+        assert_eq!(app.data.len(), 0x10);
+        app.undo();
+        app.undo();
+        assert_eq!(app.data.bytes(), &[0; 0x10]);
+    }
+}
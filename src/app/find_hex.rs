@@ -0,0 +1,92 @@
+use super::{log::NotificationLevel, popup::popup_state::PopupState, App};
+
+impl App {
+    /// Parses a byte sequence written in hex, such as `de ad be ef` or `0xdeadbeef`,
+    /// whitespace is ignored and every group of digits can have the `0x` prefix.
+    pub(super) fn parse_hex_bytes(text: &str) -> Result<Vec<u8>, String> {
+        let digits = text
+            .split_whitespace()
+            .map(|group| {
+                group
+                    .strip_prefix("0x")
+                    .or_else(|| group.strip_prefix("0X"))
+                    .unwrap_or(group)
+            })
+            .collect::<String>();
+        if digits.is_empty() {
+            return Err("Expected a sequence of hex bytes".into());
+        }
+        if let Some(invalid) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid hex digit \"{}\"", invalid));
+        }
+        if digits.len() % 2 != 0 {
+            return Err(format!(
+                "Odd number of hex digits ({}), every byte needs two digits",
+                digits.len()
+            ));
+        }
+        Ok((0..digits.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap())
+            .collect())
+    }
+
+    /// Jumps to the next occurrence of the bytes written in hex like [`App::find_bytes`].
+    /// Returns false if the input is invalid.
+    pub(super) fn find_hex(&mut self, text: &str) -> bool {
+        let pattern = match Self::parse_hex_bytes(text) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return false;
+            }
+        };
+        self.hex_last_searched_string = text.trim().to_string();
+        self.find_bytes(&pattern, "Bytes");
+        true
+    }
+
+    pub(super) fn request_popup_find_hex(&mut self) {
+        self.popup = Some(PopupState::FindHex {
+            text: self.hex_last_searched_string.clone(),
+            cursor: 0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_hex() {
+        assert_eq!(
+            App::parse_hex_bytes("de ad BE ef"),
+            Ok(vec![0xDE, 0xAD, 0xBE, 0xEF])
+        );
+        assert_eq!(
+            App::parse_hex_bytes(" 0xdead 0XBEEF"),
+            Ok(vec![0xDE, 0xAD, 0xBE, 0xEF])
+        );
+        assert!(App::parse_hex_bytes("").is_err());
+        assert!(App::parse_hex_bytes("dea").is_err());
+        assert!(App::parse_hex_bytes("zz").is_err());
+        assert!(App::parse_hex_bytes("é0").is_err());
+
+        let mut data = vec![0; 0x40];
+        data[0x08..0x0A].copy_from_slice(&[0xCA, 0xFE]);
+        data[0x30..0x32].copy_from_slice(&[0xCA, 0xFE]);
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        assert!(app.find_hex("ca fe"));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x08);
+        assert!(app.find_hex("ca fe"));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x30);
+        assert!(app.find_hex("cafe"));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x08);
+        assert!(app.find_hex("ff"));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x08);
+        assert!(!app.find_hex("caf"));
+        assert_eq!(app.hex_last_searched_string, "ff");
+    }
+}
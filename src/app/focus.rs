@@ -0,0 +1,71 @@
+use crate::get_app_context;
+
+use super::{info_mode::InfoMode, log::NotificationLevel, App};
+
+/// The column that receives the typed characters, hex digits in the hex editor
+/// or ASCII characters in the text view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Focus {
+    #[default]
+    Hex,
+    Text,
+}
+
+impl App {
+    /// Moves the focus between the hex editor and the text view,
+    /// the text view can be focused only while it is shown.
+    pub(super) fn switch_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::Hex if self.info_mode != InfoMode::Text || self.pane.is_some() => {
+                self.log(NotificationLevel::Warning, "The text view is not shown.");
+                Focus::Hex
+            }
+            Focus::Hex => Focus::Text,
+            Focus::Text => Focus::Hex,
+        };
+    }
+
+    /// Writes the ASCII character at the cursor and moves to the next byte.
+    pub(super) fn edit_text_data(&mut self, value: char) {
+        if !value.is_ascii() || value.is_ascii_control() {
+            return;
+        }
+        let offset = self.get_cursor_position().global_byte_index;
+        if offset >= self.data.len() {
+            return;
+        }
+        let mut new_bytes = vec![value as u8];
+        let mut app_context = get_app_context!(self);
+        self.plugin_manager
+            .on_edit(&mut new_bytes, &mut app_context);
+
+        let modified_bytes = self.data.push_change(offset, new_bytes);
+
+        self.edit_assembly(modified_bytes);
+        self.jump_to((offset + 1).min(self.data.len() - 1), false);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_focus() {
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.resize_to_size(80, 24);
+        app.switch_focus();
+        assert_eq!(app.focus, Focus::Text);
+        app.jump_to(2, false);
+        app.edit_text_data('h');
+        app.edit_text_data('i');
+        app.edit_text_data('\n');
+        assert_eq!(&app.data.bytes()[..5], b"\0\0hi\0");
+        assert_eq!(app.get_cursor_position().global_byte_index, 4);
+
+        app.set_info_mode(InfoMode::Assembly);
+        assert_eq!(app.focus, Focus::Hex);
+        app.switch_focus();
+        assert_eq!(app.focus, Focus::Hex);
+    }
+}
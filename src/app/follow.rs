@@ -0,0 +1,105 @@
+use std::{error::Error, time::SystemTime};
+
+use super::{files::filesystem::FileSystem, log::NotificationLevel, App};
+
+/// The size and modification time of the open file, used to detect external changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl App {
+    pub(super) fn get_file_stamp(&self) -> Option<FileStamp> {
+        match &self.filesystem {
+            FileSystem::Local { path } => {
+                let metadata = std::fs::metadata(path).ok()?;
+                Some(FileStamp {
+                    len: metadata.len(),
+                    modified: metadata.modified().ok(),
+                })
+            }
+            FileSystem::Remote { .. } => None,
+        }
+    }
+
+    pub(super) fn toggle_follow(&mut self) {
+        if self.follow.is_some() {
+            self.follow = None;
+            self.log(NotificationLevel::Info, "Stopped following the file");
+        } else if let Some(stamp) = self.get_file_stamp() {
+            self.follow = Some(stamp);
+            self.log(NotificationLevel::Info, "Following the file");
+        } else {
+            self.log(
+                NotificationLevel::Warning,
+                "Only local files can be followed",
+            );
+        }
+    }
+
+    /// Reloads the file if it changed on disk since the last check and moves the cursor to the end.
+    /// Following is paused as soon as the data is edited.
+    pub(super) fn check_follow(&mut self) -> Result<(), Box<dyn Error>> {
+        let last_stamp = match self.follow {
+            Some(stamp) => stamp,
+            None => return Ok(()),
+        };
+        if self.data.dirty() {
+            self.follow = None;
+            self.log(
+                NotificationLevel::Warning,
+                "Stopped following the file because it was edited",
+            );
+            return Ok(());
+        }
+        let stamp = match self.get_file_stamp() {
+            Some(stamp) => stamp,
+            None => return Ok(()),
+        };
+        if stamp == last_stamp {
+            return Ok(());
+        }
+        self.follow = Some(stamp);
+
+        self.reload_file()?;
+        self.jump_to(self.data.len().saturating_sub(1), false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    #[test]
+    fn test_follow() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0; 0x10]).unwrap();
+        let path = file
+            .path()
+            .canonicalize()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let mut app = App::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.filesystem = FileSystem::new_local(&path).unwrap();
+        app.open_file(&path, &mut terminal).unwrap();
+        app.toggle_follow();
+        assert!(app.follow.is_some());
+
+        file.write_all(&[1; 0x10]).unwrap();
+        app.check_follow().unwrap();
+        assert_eq!(app.data.len(), 0x20);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x1F);
+
+        app.data.set(0, 2).unwrap();
+        app.check_follow().unwrap();
+        assert!(app.follow.is_none());
+    }
+}
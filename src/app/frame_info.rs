@@ -9,6 +9,7 @@ pub struct FrameInfo {
     pub hex_view: Rect,
     pub info_view: Rect,
     pub info_view_frame_info: InfoViewFrameInfo,
+    pub split_info_view: Option<(Rect, InfoViewFrameInfo)>,
     pub blocks_per_row: usize,
     pub scroll: usize,
     pub file_size: usize,
@@ -18,4 +19,5 @@ pub struct FrameInfo {
 pub enum InfoViewFrameInfo {
     TextView,
     AssemblyView { scroll: usize },
+    PaneView,
 }
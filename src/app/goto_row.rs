@@ -0,0 +1,74 @@
+use super::{log::NotificationLevel, popup::popup_state::PopupState, App};
+
+impl App {
+    /// Scrolls so that the 1-based row written in `text`, decimal or hex with the `0x` prefix,
+    /// is the first one shown and moves the cursor to its first byte.
+    /// Rows past the end of the file are clamped to the last row.
+    pub(super) fn jump_to_row(&mut self, text: &str) {
+        let text = text.trim();
+        let row = match text.strip_prefix("0x") {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => text.parse(),
+        };
+        let row = match row.ok().filter(|&row| row > 0) {
+            Some(row) => row - 1,
+            None => {
+                self.log(
+                    NotificationLevel::Error,
+                    &format!("Invalid row \"{}\"", text),
+                );
+                return;
+            }
+        };
+        if self.data.is_empty() {
+            return;
+        }
+        let bytes_per_row = self.block_size * self.blocks_per_row;
+        let last_row = (self.data.len() - 1) / bytes_per_row;
+        if row > last_row {
+            self.log(
+                NotificationLevel::Warning,
+                &format!("The file has {} rows, moved to the last one", last_row + 1),
+            );
+        }
+        self.jump_to_remembered(row.min(last_row) * bytes_per_row, false);
+        self.scroll_cursor_to_top();
+    }
+
+    pub(super) fn request_popup_goto_row(&mut self) {
+        self.popup = Some(PopupState::GotoRow {
+            text: String::new(),
+            cursor: 0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_goto_row() {
+        let mut app = App::mockup(vec![0; 0x1000]);
+        app.resize_to_size(80, 24);
+        let bytes_per_row = app.block_size * app.blocks_per_row;
+        app.jump_to_row("3");
+        assert_eq!(
+            app.get_cursor_position().global_byte_index,
+            2 * bytes_per_row
+        );
+        assert_eq!(app.scroll, 2);
+        assert_eq!(app.cursor.1, 0);
+
+        app.jump_to_row("0");
+        assert_eq!(app.scroll, 2);
+
+        let last_row = (0x1000 - 1) / bytes_per_row;
+        app.jump_to_row("0xFFFF");
+        assert_eq!(
+            app.get_cursor_position().global_byte_index,
+            last_row * bytes_per_row
+        );
+        assert_eq!(app.get_cursor_position().line_index, last_row);
+    }
+}
@@ -101,6 +101,22 @@ impl App {
             HelpLine::new(&Self::key_event_to_string(key_settings.down), "Move down"),
             HelpLine::new(&Self::key_event_to_string(key_settings.left), "Move left"),
             HelpLine::new(&Self::key_event_to_string(key_settings.right), "Move right"),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.select_up),
+                "Move up extending the selection",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.select_down),
+                "Move down extending the selection",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.select_left),
+                "Move left extending the selection",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.select_right),
+                "Move right extending the selection",
+            ),
             HelpLine::new(
                 &Self::key_event_to_string(key_settings.next),
                 "Next instruction or block",
@@ -125,6 +141,30 @@ impl App {
                 &Self::key_event_to_string(key_settings.goto_end),
                 "Scroll to end",
             ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.section_start),
+                "Start of section",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.section_end),
+                "End of section",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.previous_section),
+                "Previous section",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.next_section),
+                "Next section",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.previous_offset),
+                "Previous visited offset",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.next_offset),
+                "Next visited offset",
+            ),
             HelpLine::new(&Self::key_event_to_string(key_settings.run), "Run command"),
             HelpLine::new(&Self::key_event_to_string(key_settings.save), "Save"),
             HelpLine::new(&Self::key_event_to_string(key_settings.save_as), "Save as"),
@@ -138,6 +178,90 @@ impl App {
                 &Self::key_event_to_string(key_settings.change_view),
                 "Change view",
             ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.view_text),
+                "Show text view",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.view_assembly),
+                "Show assembly view",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.previous_view),
+                "Switch to the previous view",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.jump_to_instruction),
+                "Show the instruction at the cursor",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.jump_to_operand),
+                "Jump to the address in the memory operand of the instruction",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.switch_focus),
+                "Type in the hex editor or in the text view",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.select),
+                "Start or clear selection",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.swap_selection),
+                "Move cursor to the other end of the selection",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.find_selection),
+                "Select the next occurrence of the selected bytes",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.find_next),
+                "Jump to the next match of the last search",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.find_prev),
+                "Jump to the previous match of the last search",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.copy),
+                "Copy the selection to the clipboard",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.copy_alternate),
+                "Copy the selection in the alternate format",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.pin),
+                "Pin the offset at the cursor",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.jump_to_pin),
+                "Jump to the pinned offset",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.set_mark),
+                "Set or clear the mark",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.measure),
+                "Show the distance from the mark",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.highlight_equal_bytes),
+                "Highlight bytes equal to the one at the cursor",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.toggle_hex_case),
+                "Toggle hex case",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.toggle_split_view),
+                "Toggle split view",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.toggle_fold),
+                "Fold or unfold the function at the cursor (in assembly view)",
+            ),
             HelpLine::new(
                 &Self::key_event_to_string(key_settings.jump),
                 "Jump to location",
@@ -164,13 +288,21 @@ impl App {
                 &Self::key_event_to_string(key_settings.close_popup),
                 "Close popup",
             ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.next_button),
+                "Next popup button",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.previous_button),
+                "Previous popup button",
+            ),
             HelpLine::new(
                 &Self::key_event_to_string(key_settings.new_line),
                 "Insert new line (with multiline text)",
             ),
             HelpLine::new(
                 &Self::key_event_to_string(key_settings.clear_log),
-                "Clear log (with log open)",
+                "Clear log (with log open) or remove field (with fields open)",
             ),
             HelpLine::new(
                 &Self::key_event_to_string(key_settings.undo),
@@ -180,6 +312,22 @@ impl App {
                 &Self::key_event_to_string(key_settings.redo),
                 "Redo last change",
             ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.increment_byte),
+                "Increment byte",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.decrement_byte),
+                "Decrement byte",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.increment_byte_by_16),
+                "Add 0x10 to byte",
+            ),
+            HelpLine::new(
+                &Self::key_event_to_string(key_settings.decrement_byte_by_16),
+                "Subtract 0x10 from byte",
+            ),
             HelpLine::new(&Self::key_event_to_string(key_settings.help), "Help"),
         ]
     }
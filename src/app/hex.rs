@@ -1,6 +1,9 @@
 use std::error::Error;
 
-use ratatui::text::{Line, Span, Text};
+use ratatui::{
+    style::Style,
+    text::{Line, Span, Text},
+};
 
 use crate::get_app_context;
 
@@ -16,6 +19,7 @@ pub(super) struct InstructionInfo {
 }
 
 impl App {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn bytes_to_styled_hex(
         color_settings: &ColorSettings,
         bytes: &[u8],
@@ -24,6 +28,9 @@ impl App {
         selected_byte_index: usize,
         high_byte: bool,
         instruction_info: Option<InstructionInfo>,
+        selection: Option<(usize, usize)>,
+        uppercase: bool,
+        column_highlight: usize,
     ) -> Text<'static> {
         let mut ret = Text::default();
         ret.lines
@@ -34,12 +41,20 @@ impl App {
         for (byte_index, b) in bytes.iter().enumerate() {
             let byte_index = byte_index as isize;
             let mut next_line = false;
-            let hex_chars = Self::u8_to_hex(*b);
+            let mut hex_chars = Self::u8_to_hex(*b);
+            if !uppercase {
+                hex_chars = hex_chars.map(|c| c.to_ascii_lowercase());
+            }
             let hex_high = hex_chars[0].to_string();
             let hex_low = hex_chars[1].to_string();
             let (mut space_style, mut style) = (
                 color_settings.hex_default,
-                Self::get_style_for_byte(color_settings, *b),
+                Self::get_base_style_for_byte(
+                    color_settings,
+                    *b,
+                    byte_index as usize % (block_size * blocks_per_row),
+                    column_highlight,
+                ),
             );
 
             if let Some(instruction_info) = &instruction_info {
@@ -60,6 +75,16 @@ impl App {
                 }
             }
 
+            if let Some((selection_start, selection_end)) = selection {
+                let byte_index = byte_index as usize;
+                if byte_index >= selection_start && byte_index < selection_end {
+                    if byte_index != selection_end - 1 {
+                        space_style = color_settings.hex_selection;
+                    }
+                    style = color_settings.hex_selection;
+                }
+            }
+
             let span = Span::styled(
                 hex_high,
                 if byte_index == selected_byte_index as isize && high_byte {
@@ -106,8 +131,24 @@ impl App {
         ret
     }
 
+    /// The style of a byte in the column `column` of a row before any highlight,
+    /// every `column_highlight`th column is emphasized unless `column_highlight` is 0.
+    pub(super) fn get_base_style_for_byte(
+        color_settings: &ColorSettings,
+        byte: u8,
+        column: usize,
+        column_highlight: usize,
+    ) -> Style {
+        let style = Self::get_style_for_byte(color_settings, byte);
+        if column_highlight != 0 && column % column_highlight == column_highlight - 1 {
+            style.patch(color_settings.hex_column)
+        } else {
+            style
+        }
+    }
+
     pub(super) fn resize_to_size(&mut self, width: u16, height: u16) {
-        let blocks_per_row = Self::calc_blocks_per_row(self.block_size, width);
+        let blocks_per_row = self.get_blocks_per_row(width);
         if (width, height) != self.screen_size {
             self.screen_size = (width, height);
             self.resize(blocks_per_row);
@@ -121,6 +162,35 @@ impl App {
         self.jump_to(old_cursor.global_byte_index, false);
     }
 
+    /// Returns the number of blocks per row for the given width,
+    /// if the bytes per row are fixed in the settings the width is ignored.
+    pub(super) fn get_blocks_per_row(&self, width: u16) -> usize {
+        // the columns saved by a compact address column are given to the bytes,
+        // the ones taken by the change gutter are taken from them
+        let width = width
+            .saturating_add(Self::ADDRESS_WIDTH)
+            .saturating_sub(self.get_address_width());
+        let auto_blocks_per_row = if self.pane.is_some() {
+            Self::calc_pane_blocks_per_row(self.block_size, width)
+        } else if self.settings.app.info_view_width != 0 {
+            Self::calc_locked_blocks_per_row(
+                self.block_size,
+                width,
+                self.settings.app.info_view_width,
+            )
+        } else {
+            Self::calc_blocks_per_row(self.block_size, width)
+        };
+        match self.settings.app.bytes_per_row {
+            // the largest power of two that is not greater than auto_blocks_per_row
+            0 if self.settings.app.power_of_two_rows => {
+                1 << (usize::BITS - 1 - auto_blocks_per_row.leading_zeros())
+            }
+            0 => auto_blocks_per_row,
+            bytes_per_row => bytes_per_row.div_ceil(self.block_size).max(1),
+        }
+    }
+
     pub(super) fn calc_blocks_per_row(block_size: usize, width: u16) -> usize {
         let block_characters_hex = block_size * 3 + 1;
         let block_characters_text = block_size * 2 + 1;
@@ -130,6 +200,17 @@ impl App {
         (blocks_per_row as usize).max(1)
     }
 
+    /// Returns the number of blocks per row that fit next to an info panel of `info_view_width` columns.
+    pub(super) fn calc_locked_blocks_per_row(
+        block_size: usize,
+        width: u16,
+        info_view_width: usize,
+    ) -> usize {
+        let block_characters_hex = block_size * 3 + 1;
+        let available_width = (width as usize).saturating_sub(17 + 2 + info_view_width);
+        (available_width / block_characters_hex).max(1)
+    }
+
     pub(super) fn u8_to_hex(input: u8) -> [char; 2] {
         let symbols = [
             '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F',
@@ -165,10 +246,31 @@ impl App {
                 .push_change(cursor_position.global_byte_index, new_bytes);
 
             self.edit_assembly(modified_bytes);
+            self.scroll_to_edit(cursor_position.global_byte_index, cursor_position.high_byte);
         }
         Ok(())
     }
 
+    /// Adds `delta` to the byte at the cursor, wrapping around 0xFF,
+    /// subtracting is done by adding the two's complement.
+    pub(super) fn add_to_byte(&mut self, delta: u8) {
+        let cursor_position = self.get_cursor_position();
+        let Some(old_byte) = self.data.get(cursor_position.global_byte_index) else {
+            return;
+        };
+        let mut new_bytes = vec![old_byte.wrapping_add(delta)];
+        let mut app_context = get_app_context!(self);
+        self.plugin_manager
+            .on_edit(&mut new_bytes, &mut app_context);
+
+        let modified_bytes = self
+            .data
+            .push_change(cursor_position.global_byte_index, new_bytes);
+
+        self.edit_assembly(modified_bytes);
+        self.scroll_to_edit(cursor_position.global_byte_index, cursor_position.high_byte);
+    }
+
     /// start_row is included, end_row is excluded
     pub(super) fn get_hex_view(&self, start_row: usize, end_row: usize) -> Text<'static> {
         let start_byte = start_row * self.blocks_per_row * self.block_size;
@@ -199,7 +301,7 @@ impl App {
                 None
             }
         };
-        Self::bytes_to_styled_hex(
+        let mut hex_view = Self::bytes_to_styled_hex(
             &self.settings.color,
             bytes,
             self.block_size,
@@ -207,7 +309,26 @@ impl App {
             selected_byte_index,
             high_byte,
             instruction_info,
-        )
+            self.get_selection().map(|(selection_start, selection_end)| {
+                (
+                    selection_start.saturating_sub(start_byte),
+                    selection_end.saturating_sub(start_byte),
+                )
+            }),
+            self.settings.app.uppercase_hex,
+            self.settings.app.column_highlight,
+        );
+        self.style_annotations(
+            &mut hex_view,
+            start_byte,
+            3,
+            self.settings.app.column_highlight,
+        );
+        hex_view
+    }
+
+    pub(super) fn toggle_hex_case(&mut self) {
+        self.settings.app.uppercase_hex = !self.settings.app.uppercase_hex;
     }
 }
 
@@ -236,6 +357,150 @@ mod test {
         app.resize_to_size(80, 24);
     }
 
+    #[test]
+    fn test_fixed_bytes_per_row() {
+        let mut app = App::mockup(vec![0; 0x1000]);
+        app.settings.app.bytes_per_row = 16;
+        app.resize_to_size(80, 24);
+        assert_eq!(app.blocks_per_row, 2);
+        app.resize_to_size(250, 24);
+        assert_eq!(app.blocks_per_row, 2);
+        app.resize_to_size(40, 24);
+        assert_eq!(app.blocks_per_row, 2);
+
+        // the extra space is split between the two sides
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(200, 24)).unwrap();
+        app.resize_to_size(200, 24);
+        app.draw(&mut terminal).unwrap();
+        let frame = app.last_frame_info;
+        let left = frame.address_view.x;
+        let right = frame.scroll_bar.x - frame.info_view.right() - 1;
+        assert!(left > 0);
+        assert!(left.abs_diff(right) <= 1);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 24)).unwrap();
+        app.resize_to_size(60, 24);
+        app.draw(&mut terminal).unwrap();
+        assert_eq!(app.last_frame_info.address_view.x, 0);
+        app.settings.app.bytes_per_row = 0;
+        app.resize_to_size(250, 24);
+        assert_eq!(
+            app.blocks_per_row,
+            App::calc_blocks_per_row(app.block_size, 250)
+        );
+    }
+
+    #[test]
+    fn test_info_view_width() {
+        let mut app = App::mockup(vec![0; 0x1000]);
+        app.settings.app.info_view_width = 40;
+        app.resize_to_size(120, 24);
+        let blocks_per_row = app.blocks_per_row;
+        assert_eq!(
+            blocks_per_row,
+            App::calc_locked_blocks_per_row(app.block_size, 120, 40)
+        );
+        let hex_width = blocks_per_row * (app.block_size * 3 + 1);
+        assert!(17 + hex_width + 40 + 2 <= 120);
+        assert!(17 + hex_width + app.block_size * 3 + 1 + 40 + 2 > 120);
+    }
+
+    #[test]
+    fn test_compact_address() {
+        let mut app = App::mockup(vec![0; 0x1000]);
+        app.resize_to_size(100, 24);
+        let blocks_per_row = app.blocks_per_row;
+        assert_eq!(app.get_address_width(), App::ADDRESS_WIDTH);
+        app.settings.app.compact_address = true;
+        assert_eq!(app.get_address_width(), 9);
+        // the size of the screen did not change, only the layout
+        app.resize(app.get_blocks_per_row(100));
+        assert!(app.blocks_per_row > blocks_per_row);
+        let address_view = app.get_address_view(1, 2);
+        assert_eq!(address_view.lines[0].width(), 8);
+        assert_eq!(
+            address_view.lines[0].spans[0].content.trim(),
+            format!("{:X}", app.blocks_per_row * app.block_size)
+        );
+    }
+
+    #[test]
+    fn test_power_of_two_rows() {
+        let mut app = App::mockup(vec![0; 0x1000]);
+        app.settings.app.power_of_two_rows = true;
+        for width in [40, 80, 120, 250, 400] {
+            app.resize_to_size(width, 24);
+            let auto_blocks_per_row = App::calc_blocks_per_row(app.block_size, width);
+            assert!(app.blocks_per_row.is_power_of_two());
+            assert!(app.blocks_per_row <= auto_blocks_per_row);
+            assert!(app.blocks_per_row * 2 > auto_blocks_per_row);
+        }
+    }
+
+    #[test]
+    fn test_column_highlight() {
+        let color_settings = ColorSettings::get_default_dark_theme();
+        let bytes = [0x41; 8];
+        let text =
+            App::bytes_to_styled_hex(&color_settings, &bytes, 8, 1, 8, true, None, None, true, 4);
+        let highlighted = text.lines[0]
+            .spans
+            .chunks(3)
+            .map(|spans| spans[0].style != App::get_style_for_byte(&color_settings, 0x41))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            highlighted,
+            vec![false, false, false, true, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_add_to_byte() {
+        let mut app = App::mockup(vec![0x00, 0xFF, 0xF8]);
+        app.resize_to_size(80, 24);
+        app.add_to_byte(0xFF);
+        assert_eq!(app.data.bytes(), &[0xFF, 0xFF, 0xF8]);
+        app.jump_to(1, false);
+        app.add_to_byte(1);
+        assert_eq!(app.data.bytes(), &[0xFF, 0x00, 0xF8]);
+        app.jump_to(2, false);
+        app.add_to_byte(0x10);
+        assert_eq!(app.data.bytes(), &[0xFF, 0x00, 0x08]);
+        app.undo();
+        assert_eq!(app.data.bytes(), &[0xFF, 0x00, 0xF8]);
+    }
+
+    #[test]
+    fn test_hex_case() {
+        let color_settings = ColorSettings::get_default_dark_theme();
+        let bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+        for uppercase in [true, false] {
+            let text = App::bytes_to_styled_hex(
+                &color_settings,
+                &bytes,
+                8,
+                1,
+                0,
+                true,
+                None,
+                None,
+                uppercase,
+                0,
+            );
+            let content = text.lines[0]
+                .spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>();
+            if uppercase {
+                assert_eq!(content.trim(), "DE AD BE EF");
+            } else {
+                assert_eq!(content.trim(), "de ad be ef");
+            }
+        }
+    }
+
     #[test]
     fn test_u8_to_hex() {
         assert_eq!(App::u8_to_hex(0x00), ['0', '0']);
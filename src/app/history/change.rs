@@ -3,6 +3,8 @@ pub struct Change {
     offset: usize,
     old: Vec<u8>,
     new: Vec<u8>,
+    /// If true the change is undone and redone together with the previous one.
+    joined: bool,
 }
 
 impl Change {
@@ -14,19 +16,70 @@ impl Change {
             offset,
             old: old.to_vec(),
             new: new.to_vec(),
+            joined: false,
         }
     }
 
+    /// A change that inserts `new` at `offset`, the bytes after it are shifted.
+    pub fn insertion(offset: usize, new: &[u8]) -> Self {
+        Self {
+            offset,
+            old: Vec::new(),
+            new: new.to_vec(),
+            joined: false,
+        }
+    }
+
+    /// A change that replaces `old` with `new` at `offset`, the lengths may differ
+    /// and the bytes after it are shifted.
+    pub fn replacement(offset: usize, old: &[u8], new: &[u8]) -> Self {
+        Self {
+            offset,
+            old: old.to_vec(),
+            new: new.to_vec(),
+            joined: false,
+        }
+    }
+
+    /// How much the data grows when the change is applied,
+    /// 0 unless it is an insertion or a replacement of a different length.
+    pub fn size_delta(&self) -> isize {
+        self.new.len() as isize - self.old.len() as isize
+    }
+
+    pub fn is_joined(&self) -> bool {
+        self.joined
+    }
+
+    pub(super) fn join(&mut self) {
+        self.joined = true;
+    }
+
     pub fn offset(&self) -> usize {
         self.offset
     }
 
+    pub fn old(&self) -> &[u8] {
+        &self.old
+    }
+
+    pub fn new_bytes(&self) -> &[u8] {
+        &self.new
+    }
+
+    /// The number of bytes at the start of the change that are overwritten in place,
+    /// the rest is inserted or removed.
+    pub fn overwritten_len(&self) -> usize {
+        self.old.len().min(self.new.len())
+    }
+
+    /// The number of bytes covered by the change, the longest between the old and the new bytes.
     pub fn len(&self) -> usize {
-        self.old.len()
+        self.old.len().max(self.new.len())
     }
 
     pub fn is_empty(&self) -> bool {
-        self.old.is_empty()
+        self.old.is_empty() && self.new.is_empty()
     }
 
     pub fn apply(&self, data: &mut Vec<u8>) {
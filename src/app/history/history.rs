@@ -8,6 +8,7 @@ pub struct History {
     limit: usize,
     changes: VecDeque<Change>,
     current: usize,
+    truncated: bool,
 }
 
 impl History {
@@ -17,6 +18,7 @@ impl History {
             limit,
             changes: VecDeque::with_capacity(limit),
             current: 0,
+            truncated: false,
         }
     }
 
@@ -25,11 +27,27 @@ impl History {
         if self.changes.len() >= self.limit && self.limit > 0 {
             self.changes.remove(0);
             self.current = self.current.saturating_sub(1);
+            self.truncated = true;
         }
         self.changes.push_back(change);
         self.current += 1;
     }
 
+    /// Joins the last `count` changes, so that they are undone and redone at once.
+    pub fn join_last(&mut self, count: usize) {
+        let start = self.current.saturating_sub(count) + 1;
+        for change in self.changes.range_mut(start.min(self.current)..self.current) {
+            change.join();
+        }
+    }
+
+    /// Returns true if the next change to redo is joined to the one before it.
+    pub fn is_next_joined(&self) -> bool {
+        self.changes
+            .get(self.current)
+            .is_some_and(|change| change.is_joined())
+    }
+
     /// Undo the last change.
     /// Returns the change that was undone, if any.
     pub fn undo(&mut self, data: &mut Vec<u8>) -> Option<&Change> {
@@ -59,6 +77,7 @@ impl History {
         if self.changes.len() > limit && limit > 0 {
             self.changes.drain(0..self.changes.len() - limit);
             self.current = limit;
+            self.truncated = true;
         }
         if let Some(additional) = limit.checked_sub(self.changes.capacity()) {
             self.changes.reserve(additional);
@@ -70,6 +89,23 @@ impl History {
     pub fn clear(&mut self) {
         self.changes.clear();
         self.current = 0;
+        self.truncated = false;
+    }
+
+    /// The changes in the history, the ones from `current()` on were undone.
+    pub fn changes(&self) -> &VecDeque<Change> {
+        &self.changes
+    }
+
+    /// The number of changes that are applied.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Returns true if some of the oldest changes were dropped because of the limit,
+    /// so undoing will not go back to the original data.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
     }
 }
 
@@ -87,9 +123,11 @@ mod test {
         assert_eq!(history.current, 1);
         history.push(Change::new(0, &[1], &[0]));
         assert_eq!(history.current, 2);
+        assert!(!history.is_truncated());
         history.push(Change::new(0, &[0], &[1]));
         assert_eq!(history.current, 2);
         assert_eq!(history.changes.len(), 2);
+        assert!(history.is_truncated());
 
         history.undo(&mut vec![0]);
         assert_eq!(history.current, 1);
@@ -112,5 +150,26 @@ mod test {
         assert_eq!(history.changes.len(), 1);
         assert_eq!(history.current, 1);
         assert_eq!(history.changes[0], Change::new(0, &[2], &[3]));
+        assert!(history.is_truncated());
+
+        history.clear();
+        assert!(!history.is_truncated());
+    }
+
+    #[test]
+    fn test_history_join_last() {
+        let mut history = History::with_limit(0);
+        history.push(Change::new(0, &[0], &[1]));
+        history.push(Change::new(1, &[0], &[1]));
+        history.push(Change::new(2, &[0], &[1]));
+        history.join_last(2);
+        assert!(!history.changes[0].is_joined());
+        assert!(!history.changes[1].is_joined());
+        assert!(history.changes[2].is_joined());
+
+        history.undo(&mut vec![1, 1, 1]);
+        assert!(history.is_next_joined());
+        history.undo(&mut vec![1, 1, 0]);
+        assert!(!history.is_next_joined());
     }
 }
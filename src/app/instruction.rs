@@ -72,6 +72,61 @@ impl Instruction {
     pub fn is_empty(&self) -> bool {
         self.bytes.is_empty()
     }
+
+    /// Computes the address referenced by the memory operand, like `[rip + 0x10]`, `[0x10]`
+    /// or `[pc, #0x10]`, `pc` is the value of the program counter as seen by the instruction.
+    /// Operands that depend on other registers can't be resolved statically.
+    pub fn memory_operand_target(&self, pc: u64) -> Result<u64, &'static str> {
+        let start = self
+            .operands
+            .find('[')
+            .ok_or("The instruction has no memory operand.")?;
+        let end = self.operands[start..]
+            .find(']')
+            .ok_or("The instruction has no memory operand.")?
+            + start;
+        if self.operands[..start].trim_end().ends_with(':') {
+            return Err("The memory operand is relative to a segment register.");
+        }
+        let expression = self.operands[start + 1..end]
+            .replace('#', "")
+            .replace(',', " + ");
+        let mut target = 0u64;
+        let mut negative = false;
+        let mut terms = 0;
+        for token in expression.split_whitespace() {
+            match token {
+                "+" => {}
+                "-" => negative = !negative,
+                token => {
+                    let (token_negative, token) = match token.strip_prefix('-') {
+                        Some(token) => (true, token),
+                        None => (false, token),
+                    };
+                    let token = token.to_lowercase();
+                    let value = match token.as_str() {
+                        "rip" | "eip" | "pc" => pc,
+                        token => match token.strip_prefix("0x") {
+                            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                            None => token.parse::<u64>().ok(),
+                        }
+                        .ok_or("The memory operand depends on registers that are not known.")?,
+                    };
+                    target = if negative != token_negative {
+                        target.wrapping_sub(value)
+                    } else {
+                        target.wrapping_add(value)
+                    };
+                    negative = false;
+                    terms += 1;
+                }
+            }
+        }
+        if terms == 0 {
+            return Err("The instruction has no memory operand.");
+        }
+        Ok(target)
+    }
 }
 
 impl Display for Instruction {
@@ -10,6 +10,7 @@ pub struct Logger {
     pub(super) log: VecDeque<LogLine>,
     pub(super) verbosity: Verbosity,
     pub(super) notification: NotificationLevel,
+    pub(super) unseen: usize,
 }
 
 impl Logger {
@@ -19,6 +20,7 @@ impl Logger {
             log: VecDeque::with_capacity(limit),
             verbosity,
             notification: NotificationLevel::None,
+            unseen: 0,
         }
     }
 
@@ -29,12 +31,14 @@ impl Logger {
                 self.log.pop_front();
             }
             self.log.push_back(LogLine::new(level, message.to_string()));
+            self.unseen += 1;
         }
     }
 
     pub fn clear(&mut self) {
         self.log.clear();
         self.notification.reset();
+        self.unseen = 0;
     }
 
     pub fn get_notification_level(&self) -> NotificationLevel {
@@ -53,8 +57,14 @@ impl Logger {
         self.log.is_empty()
     }
 
+    /// Returns the number of messages logged since the log was last opened.
+    pub fn unseen(&self) -> usize {
+        self.unseen.min(self.log.len())
+    }
+
     pub fn reset_notification_level(&mut self) {
         self.notification.reset();
+        self.unseen = 0;
     }
 
     pub fn change_limit(&mut self, limit: usize) {
@@ -98,6 +108,7 @@ impl Default for Logger {
             log: Default::default(),
             verbosity: Verbosity::Debug,
             notification: Default::default(),
+            unseen: 0,
         }
     }
 }
@@ -150,6 +161,24 @@ mod test {
         assert_eq!(logger1.get_notification_level(), NotificationLevel::Error);
     }
 
+    #[test]
+    fn test_logger_unseen() {
+        let mut logger = Logger::new(5, Verbosity::Info);
+        assert_eq!(logger.unseen(), 0);
+        logger.log(NotificationLevel::Info, "Test info message");
+        logger.log(NotificationLevel::Debug, "Test debug message");
+        logger.log(NotificationLevel::Warning, "Test warning message");
+        assert_eq!(logger.unseen(), 2);
+        logger.reset_notification_level();
+        assert_eq!(logger.unseen(), 0);
+        for i in 0..10 {
+            logger.log(NotificationLevel::Error, &format!("Test error message {}", i));
+        }
+        assert_eq!(logger.unseen(), 5);
+        logger.clear();
+        assert_eq!(logger.unseen(), 0);
+    }
+
     #[test]
     fn test_logger_with_limit() {
         let mut logger = Logger::new(5, Verbosity::Debug);
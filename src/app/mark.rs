@@ -0,0 +1,66 @@
+use super::{log::NotificationLevel, App};
+
+impl App {
+    /// Drops the mark at the cursor, or clears it if the cursor is already on it.
+    pub(super) fn toggle_mark(&mut self) {
+        let offset = self.get_cursor_position().global_byte_index;
+        if self.mark == Some(offset) {
+            self.mark = None;
+            self.log(NotificationLevel::Info, "Mark cleared");
+        } else {
+            self.mark = Some(offset);
+            self.log(
+                NotificationLevel::Info,
+                &format!("Mark set at {:X}", offset),
+            );
+        }
+    }
+
+    /// Returns the distance from the mark to the cursor, negative if the cursor is before it.
+    pub(super) fn get_mark_distance(&self) -> Option<isize> {
+        let offset = self.get_cursor_position().global_byte_index;
+        self.mark.map(|mark| offset as isize - mark as isize)
+    }
+
+    pub(super) fn log_mark_distance(&mut self) {
+        match self.get_mark_distance() {
+            Some(distance) => {
+                let sign = if distance < 0 { "-" } else { "" };
+                let message = format!(
+                    "Distance from the mark: {} ({}{:#X})",
+                    self.group_digits(&distance.to_string()),
+                    sign,
+                    distance.unsigned_abs()
+                );
+                self.log(NotificationLevel::Info, &message);
+            }
+            None => self.log(NotificationLevel::Warning, "No mark is set."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mark_distance() {
+        let mut app = App::mockup(vec![0; 0x100]);
+        assert_eq!(app.get_mark_distance(), None);
+        app.jump_to(0x10, false);
+        app.toggle_mark();
+        app.jump_to(0x2C, false);
+        assert_eq!(app.get_mark_distance(), Some(0x1C));
+        app.log_mark_distance();
+        assert_eq!(
+            app.logger[app.logger.len() - 1].message,
+            "Distance from the mark: 28 (0x1C)"
+        );
+        app.jump_to(0x08, false);
+        assert_eq!(app.get_mark_distance(), Some(-8));
+
+        app.jump_to(0x10, false);
+        app.toggle_mark();
+        assert_eq!(app.mark, None);
+    }
+}
@@ -3,23 +3,50 @@ pub use app::*;
 
 pub mod address;
 pub mod asm;
+pub mod bom;
+pub mod calculator;
+pub mod clipboard;
+pub mod color_tags;
 pub mod commands;
+pub mod compare;
 pub mod cursor_position;
 pub mod data;
 pub mod events;
+pub mod export;
+pub mod external_editor;
+pub mod fields;
 pub mod files;
+pub mod fill;
+pub mod find_hex;
+pub mod focus;
+pub mod follow;
 pub mod frame_info;
+pub mod goto_row;
 pub mod help;
 pub mod hex;
 pub mod history;
 pub mod info_mode;
 pub mod instruction;
 pub mod log;
+pub mod mark;
 pub mod mockup;
+pub mod occurrence;
+pub mod pane;
+pub mod patch_file;
+pub mod pin;
 pub mod plugins;
+pub mod pointers;
 pub mod popup;
+pub mod profiles;
+pub mod recent_offsets;
+pub mod search;
+pub mod selection;
+pub mod session;
 pub mod settings;
 pub mod ssh;
 pub mod status_bar;
+pub mod strings;
 pub mod text;
+pub mod text_encoding;
 pub mod widgets;
+pub mod write_value;
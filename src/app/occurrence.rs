@@ -0,0 +1,101 @@
+use super::{log::NotificationLevel, popup::popup_state::PopupState, App};
+
+impl App {
+    /// Parses a request in the form `BYTE # N`, the byte is in hex with an optional `0x` prefix
+    /// and the 1-based ordinal can be decimal or hex with the `0x` prefix.
+    pub(super) fn parse_occurrence(text: &str) -> Result<(u8, usize), String> {
+        let (byte, ordinal) = text
+            .rsplit_once('#')
+            .ok_or("Expected a byte and an ordinal separated by \"#\"")?;
+        let byte = byte.trim();
+        let byte = u8::from_str_radix(byte.strip_prefix("0x").unwrap_or(byte), 16)
+            .map_err(|_| format!("Invalid byte \"{}\"", byte))?;
+        let ordinal = ordinal.trim();
+        let ordinal = match ordinal.strip_prefix("0x") {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => ordinal.parse(),
+        }
+        .ok()
+        .filter(|&ordinal| ordinal > 0)
+        .ok_or_else(|| format!("Invalid ordinal \"{}\"", ordinal))?;
+        Ok((byte, ordinal))
+    }
+
+    /// Jumps to the Nth occurrence of a byte counting from the start of the file.
+    pub(super) fn jump_to_occurrence(&mut self, text: &str) {
+        let (byte, ordinal) = match Self::parse_occurrence(text) {
+            Ok(occurrence) => occurrence,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return;
+            }
+        };
+        let found = self
+            .data
+            .bytes()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &other)| other == byte)
+            .map(|(offset, _)| offset)
+            .nth(ordinal - 1);
+        match found {
+            Some(offset) => self.jump_to_remembered(offset, false),
+            None => {
+                let count = self
+                    .data
+                    .bytes()
+                    .iter()
+                    .filter(|&&other| other == byte)
+                    .count();
+                self.log(
+                    NotificationLevel::Warning,
+                    &format!("There are only {} occurrences of {:02X}", count, byte),
+                );
+            }
+        }
+    }
+
+    pub(in crate::app) fn request_popup_occurrence(&mut self) {
+        // the byte defaults to the one at the cursor
+        let text = match self.data.get(self.get_cursor_position().global_byte_index) {
+            Some(byte) => format!("{:02X} # ", byte),
+            None => String::new(),
+        };
+        let cursor = text.len();
+        self.popup = Some(PopupState::FindOccurrence { text, cursor });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_jump_to_occurrence() {
+        assert_eq!(App::parse_occurrence("FF # 5"), Ok((0xFF, 5)));
+        assert_eq!(App::parse_occurrence("0x0a#0x10"), Ok((0x0A, 0x10)));
+        assert!(App::parse_occurrence("FF # 0").is_err());
+        assert!(App::parse_occurrence("FFF # 1").is_err());
+        assert!(App::parse_occurrence("FF").is_err());
+
+        let mut data = vec![0; 0x40];
+        for offset in [3, 0x10, 0x20, 0x21] {
+            data[offset] = 0xFF;
+        }
+        let mut app = App::mockup(data);
+        app.jump_to_occurrence("FF # 3");
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x20);
+        app.jump_to_occurrence("FF # 5");
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x20);
+        assert_eq!(
+            app.logger[app.logger.len() - 1].message,
+            "There are only 4 occurrences of FF"
+        );
+
+        app.request_popup_occurrence();
+        assert!(matches!(
+            app.popup,
+            Some(PopupState::FindOccurrence { ref text, cursor: 5 }) if text == "FF # "
+        ));
+    }
+}
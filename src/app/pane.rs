@@ -0,0 +1,218 @@
+use std::error::Error;
+
+use ratatui::text::Text;
+
+use super::{focus::Focus, log::NotificationLevel, App};
+
+/// A second file shown in place of the info view,
+/// it scrolls together with the hex editor and shares its cursor and selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondPane {
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+impl App {
+    pub(super) fn calc_pane_blocks_per_row(block_size: usize, width: u16) -> usize {
+        let block_characters_hex = block_size * 3 + 1;
+        let available_width = width.saturating_sub(18 + 2 + 2);
+        let blocks_per_row = (available_width + 2) / (block_characters_hex * 2) as u16;
+        (blocks_per_row as usize).max(1)
+    }
+
+    pub(super) fn open_pane(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let path = self.filesystem.canonicalize(path)?;
+        let bytes = self.filesystem.read(&path)?;
+        self.log(
+            NotificationLevel::Info,
+            &format!("Opened \"{}\" in the second pane", path),
+        );
+        self.pane = Some(SecondPane { path, bytes });
+        // the pane replaces the text view
+        self.focus = Focus::Hex;
+        self.resize(self.get_blocks_per_row(self.screen_size.0));
+        Ok(())
+    }
+
+    pub(super) fn close_pane(&mut self) {
+        if self.pane.take().is_some() {
+            self.resize(self.get_blocks_per_row(self.screen_size.0));
+        } else {
+            self.log(NotificationLevel::Info, "The second pane is not open.");
+        }
+    }
+
+    /// Opens the file of the second pane for editing and moves the current file to the second pane,
+    /// the cursor and the selection are kept. This is refused if the current file has unsaved changes.
+    pub(super) fn swap_panes(&mut self) -> Result<(), Box<dyn Error>> {
+        let pane = match self.pane.take() {
+            Some(pane) => pane,
+            None => {
+                self.log(NotificationLevel::Warning, "The second pane is not open.");
+                return Ok(());
+            }
+        };
+        if self.data.dirty() {
+            self.pane = Some(pane);
+            self.log(
+                NotificationLevel::Warning,
+                "Save or undo your changes before swapping the panes.",
+            );
+            return Ok(());
+        }
+        let offset = self.get_cursor_position().global_byte_index;
+        let previous = SecondPane {
+            path: self.filesystem.pwd().to_string(),
+            bytes: self.data.bytes().to_vec(),
+        };
+        self.filesystem.cd(&pane.path);
+        self.follow = None;
+        self.disassembly_base = 0;
+        self.fields.clear();
+        self.pane = Some(previous);
+        self.reload_file()?;
+        if let Some(anchor) = self.selection_anchor {
+            self.selection_anchor = Some(anchor.min(self.data.len().saturating_sub(1)));
+        }
+        self.jump_to(offset.min(self.data.len().saturating_sub(1)), false);
+        self.log(
+            NotificationLevel::Info,
+            &format!("Editing \"{}\"", self.filesystem.pwd()),
+        );
+        Ok(())
+    }
+
+    /// Copies the bytes of the second pane under the selection, or under the cursor if nothing is selected,
+    /// to the same offsets of the current file as a single change.
+    pub(super) fn copy_from_pane(&mut self) {
+        let pane = match &self.pane {
+            Some(pane) => pane,
+            None => {
+                self.log(NotificationLevel::Warning, "The second pane is not open.");
+                return;
+            }
+        };
+        let (start, end) = self.get_selection().unwrap_or_else(|| {
+            let offset = self.get_cursor_position().global_byte_index;
+            (offset, offset + 1)
+        });
+        let bytes = match pane.bytes.get(start..end.min(pane.bytes.len())) {
+            Some(bytes) if !bytes.is_empty() => bytes.to_vec(),
+            _ => {
+                self.log(
+                    NotificationLevel::Warning,
+                    "The second pane has no bytes at the selected offset.",
+                );
+                return;
+            }
+        };
+        self.jump_to(start, false);
+        self.patch_bytes(&bytes, false);
+        self.log(
+            NotificationLevel::Info,
+            &format!("Copied {} bytes from the second pane", bytes.len()),
+        );
+    }
+
+    pub(super) fn get_pane_view(&self, start_row: usize, end_row: usize) -> Text<'static> {
+        let pane = match &self.pane {
+            Some(pane) => pane,
+            None => return Text::default(),
+        };
+        let bytes_per_row = self.blocks_per_row * self.block_size;
+        let start_byte = (start_row * bytes_per_row).min(pane.bytes.len());
+        let end_byte = (end_row * bytes_per_row).min(pane.bytes.len());
+        let cursor_position = self.get_cursor_position();
+        let mut text = Self::bytes_to_styled_hex(
+            &self.settings.color,
+            &pane.bytes[start_byte..end_byte],
+            self.block_size,
+            self.blocks_per_row,
+            cursor_position
+                .global_byte_index
+                .saturating_sub(start_byte),
+            cursor_position.high_byte,
+            None,
+            self.get_selection().map(|(selection_start, selection_end)| {
+                (
+                    selection_start.saturating_sub(start_byte),
+                    selection_end.saturating_sub(start_byte),
+                )
+            }),
+            self.settings.app.uppercase_hex,
+            self.settings.app.column_highlight,
+        );
+        // every byte is rendered as the high digit, the low digit and the spacing
+        for (row, line) in text.lines.iter_mut().enumerate() {
+            for (column, spans) in line.spans.chunks_mut(3).enumerate() {
+                let index = start_byte + row * bytes_per_row + column;
+                if index == cursor_position.global_byte_index
+                    || self.data.bytes().get(index) == pane.bytes.get(index)
+                {
+                    continue;
+                }
+                for span in spans.iter_mut().take(2) {
+                    span.style = self.settings.color.hex_difference;
+                }
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_pane() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0xFF; 0x10]).unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        let mut app = App::mockup((0..0x40).collect());
+        // wide enough for more than one block per row next to the pane
+        app.resize_to_size(200, 24);
+        let blocks_per_row = app.blocks_per_row;
+        app.open_pane(&path).unwrap();
+        assert!(app.blocks_per_row < blocks_per_row);
+
+        app.jump_to(4, false);
+        app.toggle_selection();
+        app.jump_to(7, false);
+        app.copy_from_pane();
+        assert_eq!(&app.data.bytes()[..9], &[0, 1, 2, 3, 0xFF, 0xFF, 0xFF, 0xFF, 8]);
+        app.selection_anchor = None;
+        app.jump_to(0x20, false);
+        app.copy_from_pane();
+        assert_eq!(app.data.bytes()[0x20], 0x20);
+
+        app.close_pane();
+        assert!(app.pane.is_none());
+        assert_eq!(app.blocks_per_row, blocks_per_row);
+    }
+
+    #[test]
+    fn test_swap_panes() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0xFF; 0x10]).unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        app.open_pane(&path).unwrap();
+        app.jump_to(0x30, false);
+        app.swap_panes().unwrap();
+        assert_eq!(app.data.bytes(), &[0xFF; 0x10]);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0xF);
+        assert_eq!(
+            app.pane.as_ref().unwrap().bytes,
+            (0..0x40).collect::<Vec<u8>>()
+        );
+
+        app.data.set(0, 0).unwrap();
+        app.swap_panes().unwrap();
+        assert_eq!(app.data.bytes()[0], 0);
+        assert_eq!(app.data.len(), 0x10);
+    }
+}
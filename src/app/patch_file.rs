@@ -0,0 +1,295 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use super::{
+    log::NotificationLevel,
+    popup::{binary_choice::BinaryChoice, popup_state::PopupState},
+    App,
+};
+
+/// A run of consecutive bytes that changed, `old` and `new` have the same length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchEntry {
+    pub offset: usize,
+    pub old: Vec<u8>,
+    pub new: Vec<u8>,
+}
+
+/// The bytes changed since the last save, written as a text file that can be applied
+/// to another copy of the original file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchFile {
+    pub entries: Vec<PatchEntry>,
+}
+
+impl PatchFile {
+    /// Groups the changed bytes in runs of consecutive offsets,
+    /// `changes` must be sorted by offset and contain (offset, old byte, new byte).
+    pub fn from_changes(changes: &[(usize, u8, u8)]) -> Self {
+        let mut entries: Vec<PatchEntry> = Vec::new();
+        for &(offset, old, new) in changes {
+            match entries.last_mut() {
+                Some(entry) if entry.offset.checked_add(entry.old.len()) == Some(offset) => {
+                    entry.old.push(old);
+                    entry.new.push(new);
+                }
+                _ => entries.push(PatchEntry {
+                    offset,
+                    old: vec![old],
+                    new: vec![new],
+                }),
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn changed_bytes(&self) -> usize {
+        self.entries.iter().map(|entry| entry.new.len()).sum()
+    }
+
+    /// The ranges of offsets changed by the patch, at most `limit` are listed
+    /// and the remaining ones are counted in the last line.
+    pub fn ranges(&self, limit: usize) -> Vec<String> {
+        let mut ranges = self
+            .entries
+            .iter()
+            .take(limit)
+            .map(|entry| {
+                format!(
+                    "{:X}..{:X}",
+                    entry.offset,
+                    entry.offset.saturating_add(entry.new.len())
+                )
+            })
+            .collect::<Vec<_>>();
+        if self.entries.len() > limit {
+            ranges.push(format!("… and {} more", self.entries.len() - limit));
+        }
+        ranges
+    }
+
+    fn parse_bytes(text: &str) -> Result<Vec<u8>, String> {
+        text.split_whitespace()
+            .map(|byte| {
+                u8::from_str_radix(byte, 16).map_err(|_| format!("Invalid byte \"{}\"", byte))
+            })
+            .collect()
+    }
+}
+
+/// Every entry is written on its own line as `OFFSET: OLD BYTES -> NEW BYTES`,
+/// the offset and the bytes are in hexadecimal and the bytes are separated by spaces.
+/// Empty lines and lines starting with `#` are ignored.
+impl Display for PatchFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let to_hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        writeln!(f, "# hex-patch patch")?;
+        writeln!(f, "# OFFSET: OLD BYTES -> NEW BYTES")?;
+        for entry in self.entries.iter() {
+            writeln!(
+                f,
+                "{:X}: {} -> {}",
+                entry.offset,
+                to_hex(&entry.old),
+                to_hex(&entry.new)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PatchFile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |e: String| format!("Line {}: {}", number + 1, e);
+            let (offset, bytes) = line
+                .split_once(':')
+                .ok_or_else(|| error("expected \"OFFSET: OLD -> NEW\"".into()))?;
+            let offset = usize::from_str_radix(offset.trim(), 16)
+                .map_err(|_| error(format!("invalid offset \"{}\"", offset.trim())))?;
+            let (old, new) = bytes
+                .split_once("->")
+                .ok_or_else(|| error("expected \"OLD -> NEW\"".into()))?;
+            let old = Self::parse_bytes(old).map_err(error)?;
+            let new = Self::parse_bytes(new).map_err(error)?;
+            if old.len() != new.len() {
+                return Err(error(
+                    "the old and the new bytes must have the same length".into(),
+                ));
+            }
+            entries.push(PatchEntry { offset, old, new });
+        }
+        Ok(Self { entries })
+    }
+}
+
+impl App {
+    /// Writes the bytes changed since the last save to `path`, the file itself is not saved.
+    /// Fails if bytes were inserted, because the offsets of the changes are shifted.
+    pub(super) fn save_patch(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        if self.data.size_changed() {
+            return Err("Bytes were inserted, a patch can only contain overwritten bytes".into());
+        }
+        let changes = self
+            .data
+            .changes()
+            .into_iter()
+            .map(|(offset, old)| (offset, old, self.data.bytes()[offset]))
+            .collect::<Vec<_>>();
+        if changes.is_empty() {
+            self.log(NotificationLevel::Warning, "There are no changes to save.");
+            return Ok(());
+        }
+        let patch = PatchFile::from_changes(&changes);
+        self.filesystem.create(path)?;
+        self.filesystem.write(path, patch.to_string().as_bytes())?;
+        self.log(
+            NotificationLevel::Info,
+            &format!("Saved {} changed bytes to {}", changes.len(), path),
+        );
+        Ok(())
+    }
+
+    pub const PATCH_PREVIEW_RANGES: usize = 8;
+
+    /// Applies the patch at `path`, nothing is changed unless the bytes that are patched
+    /// match the original bytes recorded in the patch.
+    /// If the `preview_patches` setting is on the changes are shown first and applied on confirm.
+    pub(super) fn apply_patch(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let text = String::from_utf8(self.filesystem.read(path)?)?;
+        let patch = text.parse::<PatchFile>()?;
+        for entry in patch.entries.iter() {
+            let current = entry
+                .offset
+                .checked_add(entry.old.len())
+                .and_then(|end| self.data.bytes().get(entry.offset..end))
+                .ok_or_else(|| {
+                    format!(
+                        "The patch at {:X} is past the end of the file",
+                        entry.offset
+                    )
+                })?;
+            if let Some(index) = current.iter().zip(&entry.old).position(|(a, b)| a != b) {
+                return Err(format!(
+                    "The patch does not match the file at {:X}: expected {:02X}, found {:02X}",
+                    entry.offset + index,
+                    entry.old[index],
+                    current[index]
+                )
+                .into());
+            }
+        }
+        if patch.entries.is_empty() {
+            self.log(NotificationLevel::Warning, "The patch is empty.");
+        } else if self.settings.app.preview_patches {
+            self.popup = Some(PopupState::PreviewPatch {
+                path: path.to_string(),
+                patch,
+                choice: BinaryChoice::No,
+            });
+        } else {
+            self.commit_patch(path, &patch);
+        }
+        Ok(())
+    }
+
+    /// Writes every entry of the patch as its own change, the changes are joined
+    /// so that the whole patch is undone at once.
+    pub(super) fn commit_patch(&mut self, path: &str, patch: &PatchFile) {
+        if self.data.read_only() {
+            self.log(
+                NotificationLevel::Warning,
+                "The file is read-only, the patch was not applied.",
+            );
+            return;
+        }
+        let changed = self.data.push_changes(
+            patch
+                .entries
+                .iter()
+                .map(|entry| (entry.offset, entry.new.clone())),
+        );
+        if changed == 0 {
+            self.log(
+                NotificationLevel::Warning,
+                "The patch did not change any byte.",
+            );
+            return;
+        }
+        self.disassemble();
+        self.log(
+            NotificationLevel::Info,
+            &format!("Applied {} patches from {}", patch.entries.len(), path),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_patch_file() {
+        let patch = PatchFile::from_changes(&[(1, 0, 0xFF), (2, 1, 0xFE), (0x10, 2, 3)]);
+        assert_eq!(patch.entries.len(), 2);
+        let text = patch.to_string();
+        assert!(text.contains("1: 00 01 -> FF FE\n"));
+        assert!(text.contains("10: 02 -> 03\n"));
+        assert_eq!(text.parse::<PatchFile>(), Ok(patch));
+        assert!("1: 00 -> FF FE".parse::<PatchFile>().is_err());
+        assert!("zz: 00 -> FF".parse::<PatchFile>().is_err());
+    }
+
+    #[test]
+    fn test_save_and_apply_patch() {
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        let patch_file = tempfile::NamedTempFile::new().unwrap();
+        let patch_path = patch_file.path().to_string_lossy().to_string();
+        app.data.push_change(4, vec![0xAA, 0xBB]);
+        app.data.push_change(0x20, vec![0xCC]);
+        app.save_patch(&patch_path).unwrap();
+
+        let mut other = App::mockup((0..0x40).collect());
+        other.resize_to_size(80, 24);
+        other.apply_patch(&patch_path).unwrap();
+        assert_eq!(other.data.bytes(), (0..0x40).collect::<Vec<u8>>());
+        let Some(PopupState::PreviewPatch { patch, .. }) = other.popup.take() else {
+            panic!("The patch should be previewed");
+        };
+        assert_eq!(patch.changed_bytes(), 3);
+        assert_eq!(patch.ranges(1), vec!["4..6", "… and 1 more"]);
+        other.commit_patch(&patch_path, &patch);
+        assert_eq!(other.data.bytes(), app.data.bytes());
+        // every entry is its own change, the whole patch is undone at once
+        assert_eq!(other.data.history().changes().len(), 2);
+        other.undo();
+        assert_eq!(other.data.bytes(), (0..0x40).collect::<Vec<u8>>());
+        other.redo();
+        other.settings.app.preview_patches = false;
+        // the patch is already applied, the original bytes don't match anymore
+        assert!(other.apply_patch(&patch_path).is_err());
+        assert_eq!(other.data.bytes(), app.data.bytes());
+
+        let mut read_only = App::mockup((0..0x40).collect());
+        read_only.data.set_read_only(true);
+        read_only.commit_patch(&patch_path, &patch);
+        assert_eq!(read_only.data.bytes(), (0..0x40).collect::<Vec<u8>>());
+        assert_eq!(read_only.data.history().changes().len(), 0);
+
+        std::fs::write(&patch_path, "FFFFFFFFFFFFFFFF: 00 00 -> 01 01").unwrap();
+        assert!(other.apply_patch(&patch_path).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+use super::{log::NotificationLevel, App};
+
+impl App {
+    /// Pins the offset at the cursor, or removes the pin if the cursor is already on it.
+    pub(super) fn toggle_pin(&mut self) {
+        let offset = self.get_cursor_position().global_byte_index;
+        if self.pinned == Some(offset) {
+            self.pinned = None;
+            self.log(NotificationLevel::Info, "Pin removed");
+        } else {
+            self.pinned = Some(offset);
+            self.log(
+                NotificationLevel::Info,
+                &format!("Pinned the offset {:X}", offset),
+            );
+        }
+    }
+
+    pub(super) fn jump_to_pin(&mut self) {
+        match self.pinned {
+            Some(offset) => {
+                self.jump_to_remembered(offset.min(self.data.len().saturating_sub(1)), false)
+            }
+            None => self.log(NotificationLevel::Warning, "No offset is pinned."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pin() {
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.resize_to_size(80, 24);
+        app.jump_to_pin();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+
+        app.jump_to(0x42, false);
+        app.toggle_pin();
+        assert_eq!(app.pinned, Some(0x42));
+        app.jump_to(0xF0, false);
+        let row = 0x42 / (app.blocks_per_row * app.block_size);
+        let pinned = app.settings.color.hex_pinned.bg;
+        let hex_view = app.get_hex_view(row, row + 1);
+        assert_eq!(hex_view.lines[0].spans[2 * 3].style.bg, pinned);
+        assert_ne!(hex_view.lines[0].spans[3 * 3].style.bg, pinned);
+        let address_view = app.get_address_view(row - 1, row + 1);
+        assert_eq!(
+            address_view.lines[1].spans[0].style,
+            app.settings.color.address_pinned
+        );
+        assert_eq!(
+            address_view.lines[0].spans[0].style,
+            app.settings.color.address_default
+        );
+
+        app.jump_to_pin();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x42);
+        app.toggle_pin();
+        assert_eq!(app.pinned, None);
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
+    cell::RefCell,
     ops::Deref,
+    rc::Rc,
     sync::{Arc, Mutex},
 };
 
@@ -7,10 +9,11 @@ use mlua::{Function, Lua, Scope, Table};
 
 use crate::{
     app::{
+        color_tags::ColorTag,
         data::Data,
         log::{logger::Logger, NotificationLevel},
         popup::popup_state::PopupState,
-        settings::Settings,
+        settings::{register_color_settings_macro::set_style, Settings},
         App,
     },
     headers::Header,
@@ -18,7 +21,8 @@ use crate::{
 
 use super::{
     exported_commands::ExportedCommands, exported_header_parsers::ExportedHeaderParsers,
-    instruction_info::InstructionInfo, plugin_instant::PluginInstant,
+    exported_unsaved_checks::ExportedUnsavedChecks, instruction_info::InstructionInfo,
+    plugin_dialog::PluginDialog, plugin_instant::PluginInstant,
 };
 
 #[macro_export]
@@ -27,6 +31,8 @@ macro_rules! get_app_context {
         $crate::app::plugins::app_context::AppContext::new(
             $app.get_cursor_position().global_byte_index,
             $app.get_current_instruction().map(|i| i.into()),
+            $app.get_selection(),
+            &$app.text_last_searched_string,
             $app.screen_size.1,
             $app.screen_size.0,
             $app.blocks_per_row,
@@ -39,13 +45,18 @@ macro_rules! get_app_context {
             &mut $app.settings,
             &mut $app.logger,
             &mut $app.popup,
+            &mut $app.color_tags,
         )
     };
 }
 
+/// Draws the screen with the given status while a plugin is running.
+pub type Redraw<'a> = dyn FnMut(&str) -> Result<(), String> + 'a;
+
 pub struct AppContext<'app> {
     pub exported_commands: Arc<Mutex<ExportedCommands>>,
     pub exported_header_parsers: Arc<Mutex<ExportedHeaderParsers>>,
+    pub exported_unsaved_checks: Arc<Mutex<ExportedUnsavedChecks>>,
     pub plugin_index: Option<usize>,
 
     pub screen_height: u16,
@@ -58,10 +69,13 @@ pub struct AppContext<'app> {
     pub cursor: &'app mut (u16, u16),
     pub offset: usize,
     pub current_instruction: Option<InstructionInfo>,
+    pub selection: Option<(usize, usize)>,
+    pub search: &'app str,
     pub header: &'app Header,
     pub settings: &'app mut Settings,
     pub logger: &'app mut Logger,
     pub popup: Arc<Mutex<&'app mut Option<PopupState>>>,
+    pub color_tags: Arc<Mutex<&'app mut Vec<ColorTag>>>,
 }
 
 impl<'app> AppContext<'app> {
@@ -69,6 +83,8 @@ impl<'app> AppContext<'app> {
     pub fn new(
         offset: usize,
         current_instruction: Option<InstructionInfo>,
+        selection: Option<(usize, usize)>,
+        search: &'app str,
         screen_height: u16,
         screen_width: u16,
         blocks_per_row: usize,
@@ -81,10 +97,12 @@ impl<'app> AppContext<'app> {
         settings: &'app mut Settings,
         logger: &'app mut Logger,
         popup: &'app mut Option<PopupState>,
+        color_tags: &'app mut Vec<ColorTag>,
     ) -> Self {
         Self {
             exported_commands: Arc::new(Mutex::new(ExportedCommands::default())),
             exported_header_parsers: Arc::new(Mutex::new(ExportedHeaderParsers::default())),
+            exported_unsaved_checks: Arc::new(Mutex::new(ExportedUnsavedChecks::default())),
             plugin_index: None,
             screen_height,
             screen_width,
@@ -96,10 +114,13 @@ impl<'app> AppContext<'app> {
             cursor,
             offset,
             current_instruction,
+            selection,
+            search,
             header,
             settings,
             logger,
             popup: Arc::new(Mutex::new(popup)),
+            color_tags: Arc::new(Mutex::new(color_tags)),
         }
     }
 
@@ -111,6 +132,10 @@ impl<'app> AppContext<'app> {
         self.exported_header_parsers = Arc::new(Mutex::new(ExportedHeaderParsers::default()));
     }
 
+    pub fn reset_exported_unsaved_checks(&mut self) {
+        self.exported_unsaved_checks = Arc::new(Mutex::new(ExportedUnsavedChecks::default()));
+    }
+
     pub fn set_exported_commands(&mut self, exported_commands: ExportedCommands) {
         self.exported_commands = Arc::new(Mutex::new(exported_commands));
     }
@@ -119,6 +144,10 @@ impl<'app> AppContext<'app> {
         self.exported_header_parsers = Arc::new(Mutex::new(exported_header_parsers));
     }
 
+    pub fn set_exported_unsaved_checks(&mut self, exported_unsaved_checks: ExportedUnsavedChecks) {
+        self.exported_unsaved_checks = Arc::new(Mutex::new(exported_unsaved_checks));
+    }
+
     pub fn take_exported_commands(&mut self) -> ExportedCommands {
         self.exported_commands.lock().unwrap().take()
     }
@@ -127,18 +156,80 @@ impl<'app> AppContext<'app> {
         self.exported_header_parsers.lock().unwrap().take()
     }
 
+    pub fn take_exported_unsaved_checks(&mut self) -> ExportedUnsavedChecks {
+        self.exported_unsaved_checks.lock().unwrap().take()
+    }
+
     pub fn to_lua<'scope, 'env>(
         &'env mut self,
         lua: &'scope Lua,
         scope: &'scope Scope<'scope, 'env>,
+    ) -> mlua::Table {
+        self.to_lua_with_redraw(lua, scope, None)
+    }
+
+    /// Like [`AppContext::to_lua`], `redraw` draws the screen with the given status
+    /// when the plugin calls `set_status` or `redraw`, it is only available while
+    /// the terminal can be reached, e.g. from a command.
+    pub fn to_lua_with_redraw<'scope, 'env>(
+        &'env mut self,
+        lua: &'scope Lua,
+        scope: &'scope Scope<'scope, 'env>,
+        redraw: Option<&'env mut Redraw>,
     ) -> mlua::Table {
         let context = lua.create_table().unwrap();
+        let logger = Arc::new(Mutex::new(&mut *self.logger));
+        let redraw = Rc::new(RefCell::new(redraw));
+
+        let log_logger = logger.clone();
         context
             .set(
                 "log",
                 scope
-                    .create_function_mut(|_, (level, message): (u8, String)| {
-                        self.logger.log(NotificationLevel::from(level), &message);
+                    .create_function_mut(move |_, (level, message): (u8, String)| {
+                        log_logger
+                            .lock()
+                            .unwrap()
+                            .log(NotificationLevel::from(level), &message);
+                        Ok(())
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let status_logger = logger.clone();
+        let status_redraw = redraw.clone();
+        context
+            .set(
+                "set_status",
+                scope
+                    .create_function_mut(move |_, message: String| {
+                        status_logger
+                            .lock()
+                            .unwrap()
+                            .log(NotificationLevel::Info, &message);
+                        if let Some(redraw) = status_redraw.borrow_mut().as_mut() {
+                            redraw(&message).map_err(mlua::Error::external)?;
+                        }
+                        Ok(())
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+
+        context
+            .set(
+                "redraw",
+                scope
+                    .create_function_mut(move |_, ()| {
+                        let logger = logger.lock().unwrap();
+                        let status = match logger.len() {
+                            0 => String::new(),
+                            len => logger[len - 1].message.clone(),
+                        };
+                        if let Some(redraw) = redraw.borrow_mut().as_mut() {
+                            redraw(&status).map_err(mlua::Error::external)?;
+                        }
                         Ok(())
                     })
                     .unwrap(),
@@ -235,6 +326,52 @@ impl<'app> AppContext<'app> {
             )
             .unwrap();
 
+        let exported_unsaved_checks = self.exported_unsaved_checks.clone();
+        context
+            .set(
+                "add_unsaved_check",
+                scope
+                    .create_function_mut(move |lua, callback: String| {
+                        if let Ok(_check_fn) = lua.globals().get::<Function>(callback.clone()) {
+                            exported_unsaved_checks
+                                .lock()
+                                .unwrap()
+                                .add_unsaved_check(callback);
+                            Ok(())
+                        } else {
+                            Err(mlua::Error::external(format!(
+                                "Function '{}' not found but needed to export the unsaved check",
+                                callback
+                            )))
+                        }
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let exported_unsaved_checks = self.exported_unsaved_checks.clone();
+        context
+            .set(
+                "remove_unsaved_check",
+                scope
+                    .create_function_mut(move |_, callback: String| {
+                        if exported_unsaved_checks
+                            .lock()
+                            .unwrap()
+                            .remove_unsaved_check(&callback)
+                        {
+                            Ok(())
+                        } else {
+                            Err(mlua::Error::external(format!(
+                                "Unsaved check '{}' not found",
+                                callback
+                            )))
+                        }
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+
         context
             .set(
                 "open_popup",
@@ -260,16 +397,51 @@ impl<'app> AppContext<'app> {
             )
             .unwrap();
 
+        context
+            .set(
+                "open_dialog",
+                scope
+                    .create_function_mut(|_, (dialog, callback): (Table, String)| {
+                        let mut popup = self.popup.lock().unwrap();
+                        if popup.is_some() {
+                            Err(mlua::Error::external("Popup already open"))
+                        } else if lua.globals().get::<Function>(callback.clone()).is_err() {
+                            Err(mlua::Error::external(format!(
+                                "Function '{}' not found but needed to answer the dialog",
+                                callback
+                            )))
+                        } else {
+                            let dialog = PluginDialog::from_lua(&dialog, callback)?;
+                            **popup = Some(PopupState::PluginDialog {
+                                plugin_index: self.plugin_index.unwrap(),
+                                expires: dialog.expiry(),
+                                dialog,
+                                selected: 0,
+                            });
+                            Ok(())
+                        }
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+
         context
             .set(
                 "get_popup",
                 scope
                     .create_function(|_, ()| {
                         let popup = self.popup.lock().unwrap();
-                        if let Some(PopupState::Custom {
-                            plugin_index,
-                            callback,
-                        }) = *popup as &Option<PopupState>
+                        if let Some(
+                            PopupState::Custom {
+                                plugin_index,
+                                callback,
+                            }
+                            | PopupState::PluginDialog {
+                                plugin_index,
+                                dialog: PluginDialog { callback, .. },
+                                ..
+                            },
+                        ) = *popup as &Option<PopupState>
                         {
                             if self.plugin_index.unwrap() != *plugin_index {
                                 Ok(mlua::Value::Nil)
@@ -292,10 +464,17 @@ impl<'app> AppContext<'app> {
                 scope
                     .create_function_mut(|_, expected_callback: Option<String>| {
                         let mut popup = self.popup.lock().unwrap();
-                        if let Some(PopupState::Custom {
-                            plugin_index,
-                            callback,
-                        }) = *popup as &mut Option<PopupState>
+                        if let Some(
+                            PopupState::Custom {
+                                plugin_index,
+                                callback,
+                            }
+                            | PopupState::PluginDialog {
+                                plugin_index,
+                                dialog: PluginDialog { callback, .. },
+                                ..
+                            },
+                        ) = *popup as &mut Option<PopupState>
                         {
                             if expected_callback.is_some()
                                 && expected_callback.as_ref() != Some(callback)
@@ -347,6 +526,91 @@ impl<'app> AppContext<'app> {
         )
         .unwrap();
         context.set("data", data).unwrap();
+        context
+            .set(
+                "get_selection_bytes",
+                scope
+                    .create_function(|_, ()| {
+                        let data = self.data.lock().unwrap();
+                        Ok(self
+                            .selection
+                            .map(|(start, end)| data.bytes()[start..end].to_vec()))
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+        context
+            .set(
+                "get_selection",
+                scope
+                    .create_function(|lua, ()| match self.selection {
+                        Some((start, end)) => {
+                            let selection = lua.create_table()?;
+                            selection.set("start", start)?;
+                            selection.set("size", end - start)?;
+                            Ok(Some(selection))
+                        }
+                        None => Ok(None),
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+        context
+            .set(
+                "get_search",
+                scope
+                    .create_function(|lua, ()| {
+                        if self.search.is_empty() {
+                            return Ok(None);
+                        }
+                        let data = self.data.lock().unwrap();
+                        let matches = data
+                            .bytes()
+                            .windows(self.search.len())
+                            .enumerate()
+                            .filter(|(_, window)| *window == self.search.as_bytes())
+                            .map(|(offset, _)| offset)
+                            .collect::<Vec<_>>();
+                        let search = lua.create_table()?;
+                        search.set("query", self.search)?;
+                        search.set("matches", matches)?;
+                        Ok(Some(search))
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+        context
+            .set(
+                "set_color_tag",
+                scope
+                    .create_function_mut(|lua, (start, end, color): (usize, usize, Table)| {
+                        if start >= end {
+                            return Err(mlua::Error::external(
+                                "The start of a color tag must be before its end",
+                            ));
+                        }
+                        let mut style = ratatui::style::Style::default();
+                        set_style(lua, &mut style, color)?;
+                        self.color_tags
+                            .lock()
+                            .unwrap()
+                            .push(ColorTag { start, end, style });
+                        Ok(())
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
+        context
+            .set(
+                "clear_color_tags",
+                scope
+                    .create_function_mut(|_, ()| {
+                        self.color_tags.lock().unwrap().clear();
+                        Ok(())
+                    })
+                    .unwrap(),
+            )
+            .unwrap();
         context.set("offset", self.offset).unwrap();
         context
             .set("current_instruction", self.current_instruction.clone())
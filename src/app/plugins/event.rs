@@ -9,6 +9,9 @@ pub enum Event<'app> {
         new_bytes: &'app mut Vec<u8>,
     },
     Save,
+    BeforeSave {
+        bytes: &'app mut Vec<u8>,
+    },
     Key {
         event: KeyEvent,
     },
@@ -41,6 +44,7 @@ bitflags! {
         const ON_BLUR   = 0b0000_0000_0100_0000;
         const ON_PASTE  = 0b0000_0000_1000_0000;
         const ON_RESIZE = 0b0000_0001_0000_0000;
+        const ON_BEFORE_SAVE = 0b0000_0010_0000_0000;
 
         const NONE      = 0b0000_0000_0000_0000;
     }
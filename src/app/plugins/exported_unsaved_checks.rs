@@ -0,0 +1,23 @@
+#[derive(Debug, Clone, Default)]
+pub struct ExportedUnsavedChecks {
+    pub checks: Vec<String>,
+}
+
+impl ExportedUnsavedChecks {
+    pub fn add_unsaved_check(&mut self, check: String) {
+        self.checks.push(check);
+    }
+
+    pub fn remove_unsaved_check(&mut self, check: &str) -> bool {
+        if let Some(index) = self.checks.iter().position(|c| c == check) {
+            self.checks.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn take(&mut self) -> Self {
+        std::mem::take(self)
+    }
+}
@@ -2,10 +2,12 @@ pub mod app_context;
 pub mod event;
 pub mod exported_commands;
 pub mod exported_header_parsers;
+pub mod exported_unsaved_checks;
 pub mod header_context;
 pub mod header_parser_info;
 pub mod instruction_info;
 pub mod plugin;
+pub mod plugin_dialog;
 pub mod plugin_instant;
 pub mod plugin_manager;
 pub mod popup_context;
@@ -12,10 +12,11 @@ use crate::{
 };
 
 use super::{
-    app_context::AppContext,
+    app_context::{AppContext, Redraw},
     event::{Event, Events},
     exported_commands::ExportedCommands,
     exported_header_parsers::ExportedHeaderParsers,
+    exported_unsaved_checks::ExportedUnsavedChecks,
     header_context::HeaderContext,
     popup_context::PopupContext,
     register_userdata::{
@@ -28,6 +29,7 @@ pub struct Plugin {
     lua: Lua,
     commands: ExportedCommands,
     header_parsers: ExportedHeaderParsers,
+    unsaved_checks: ExportedUnsavedChecks,
 }
 
 impl Plugin {
@@ -45,6 +47,7 @@ impl Plugin {
         register_usize(&lua)?;
 
         app_context.reset_exported_commands();
+        app_context.reset_exported_unsaved_checks();
         if let Ok(init) = lua.globals().get::<Function>("init") {
             lua.scope(|scope| {
                 let context = app_context.to_lua(&lua, scope);
@@ -56,6 +59,7 @@ impl Plugin {
             lua,
             commands: app_context.take_exported_commands(),
             header_parsers: app_context.take_exported_header_parsers(),
+            unsaved_checks: app_context.take_exported_unsaved_checks(),
         })
     }
 
@@ -75,6 +79,9 @@ impl Plugin {
         if self.lua.globals().get::<Function>("on_save").is_ok() {
             handlers |= Events::ON_SAVE;
         }
+        if self.lua.globals().get::<Function>("on_before_save").is_ok() {
+            handlers |= Events::ON_BEFORE_SAVE;
+        }
         if self.lua.globals().get::<Function>("on_key").is_ok() {
             handlers |= Events::ON_KEY;
         }
@@ -130,6 +137,15 @@ impl Plugin {
                     on_save.call::<()>(context)
                 })
             }
+            Event::BeforeSave { bytes } => {
+                // Call the on_before_save function
+                let on_before_save = self.lua.globals().get::<Function>("on_before_save").unwrap();
+                self.lua.scope(|scope| {
+                    let bytes = scope.create_any_userdata_ref_mut(bytes)?;
+                    let context = app_context.to_lua(&self.lua, scope);
+                    on_before_save.call::<()>((bytes, context))
+                })
+            }
             Event::Key { event } => {
                 // Call the on_key function
                 let on_key = self.lua.globals().get::<Function>("on_key").unwrap();
@@ -190,16 +206,25 @@ impl Plugin {
         }
     }
 
-    pub fn run_command(&mut self, command: &str, app_context: &mut AppContext) -> mlua::Result<()> {
+    /// Runs a command of the plugin, `redraw` is called with a status
+    /// when the command asks to redraw the screen.
+    pub fn run_command(
+        &mut self,
+        command: &str,
+        app_context: &mut AppContext,
+        redraw: &mut Redraw,
+    ) -> mlua::Result<()> {
         let command_fn = self.lua.globals().get::<Function>(command)?;
         app_context.set_exported_commands(self.commands.take());
         app_context.set_exported_header_parsers(self.header_parsers.take());
+        app_context.set_exported_unsaved_checks(self.unsaved_checks.take());
         let ret = self.lua.scope(|scope| {
-            let context = app_context.to_lua(&self.lua, scope);
+            let context = app_context.to_lua_with_redraw(&self.lua, scope, Some(redraw));
             command_fn.call::<()>(context)
         });
         self.commands = app_context.take_exported_commands();
         self.header_parsers = app_context.take_exported_header_parsers();
+        self.unsaved_checks = app_context.take_exported_unsaved_checks();
         ret
     }
 
@@ -225,6 +250,51 @@ impl Plugin {
         })
     }
 
+    pub fn answer_dialog(
+        &mut self,
+        callback: &str,
+        choice: Option<usize>,
+        app_context: &mut AppContext,
+    ) -> mlua::Result<()> {
+        let callback_fn = self.lua.globals().get::<Function>(callback)?;
+        app_context.set_exported_commands(self.commands.take());
+        app_context.set_exported_header_parsers(self.header_parsers.take());
+        app_context.set_exported_unsaved_checks(self.unsaved_checks.take());
+        let ret = self.lua.scope(|scope| {
+            let context = app_context.to_lua(&self.lua, scope);
+            callback_fn.call::<()>((choice, context))
+        });
+        self.commands = app_context.take_exported_commands();
+        self.header_parsers = app_context.take_exported_header_parsers();
+        self.unsaved_checks = app_context.take_exported_unsaved_checks();
+        ret
+    }
+
+    /// Calls every registered unsaved check and returns the descriptions of the data
+    /// that would be lost by quitting.
+    pub fn get_unsaved_data(&mut self, app_context: &mut AppContext) -> Vec<String> {
+        let mut ret = Vec::new();
+        for check in self.unsaved_checks.checks.iter() {
+            app_context.set_exported_commands(self.commands.take());
+            let check_fn = self.lua.globals().get::<Function>(check.as_str()).unwrap();
+            let result = self.lua.scope(|scope| {
+                let context = app_context.to_lua(&self.lua, scope);
+                check_fn.call::<Option<String>>(context)
+            });
+            self.commands = app_context.take_exported_commands();
+            match result {
+                Err(e) => {
+                    app_context
+                        .logger
+                        .log(NotificationLevel::Error, &format!("In plugin: {}", e));
+                }
+                Ok(Some(message)) => ret.push(message),
+                Ok(None) => {}
+            }
+        }
+        ret
+    }
+
     pub fn try_parse_header(&mut self, app_context: &mut AppContext) -> Option<CustomHeader> {
         for parser in self.header_parsers.parsers.iter() {
             let mut header_context = HeaderContext::default();
@@ -264,7 +334,10 @@ mod test {
     use ratatui::{backend::TestBackend, layout::Alignment, style::Style, text::Text, Terminal};
 
     use crate::{
-        app::{log::NotificationLevel, settings::settings_value::SettingsValue, App},
+        app::{
+            log::NotificationLevel, popup::popup_state::PopupState,
+            settings::settings_value::SettingsValue, text_encoding::TextEncoding, App,
+        },
         get_app_context,
         headers::{bitness::Bitness, section::Section},
     };
@@ -297,6 +370,7 @@ mod test {
             function on_open(context) end
             function on_edit(new_bytes, context) end
             function on_save(context) end
+            function on_before_save(bytes, context) end
             function on_key(key_event, context) end
             function on_mouse(mouse_event, context) end
             function on_focus(context) end
@@ -441,6 +515,63 @@ mod test {
         assert_eq!(app.data.bytes()[0], 42);
     }
 
+    #[test]
+    fn test_on_before_save() {
+        let source = "
+            function on_before_save(bytes, context)
+                if bytes:get(1) == 0xFF then
+                    error(\"Invalid data\")
+                end
+                bytes:set(0, bytes.len)
+            end
+        ";
+        let mut app = App::mockup(vec![0; 0x100]);
+        let mut app_context = get_app_context!(app);
+        let mut plugin = Plugin::new_from_source(source, &mut app_context).unwrap();
+        let mut bytes = vec![0; 0x10];
+        let event = Event::BeforeSave { bytes: &mut bytes };
+        plugin.handle_with_error(event, &mut app_context).unwrap();
+        assert_eq!(bytes[0], 0x10);
+        bytes[1] = 0xFF;
+        let event = Event::BeforeSave { bytes: &mut bytes };
+        assert!(plugin.handle_with_error(event, &mut app_context).is_err());
+    }
+
+    #[test]
+    fn test_set_status_from_lua() {
+        let source = "
+            function init(context)
+                context.add_command(\"progress\", \"Shows the progress\")
+            end
+
+            function progress(context)
+                context.set_status(\"Step 1\")
+                context.log(2, \"Step 2\")
+                context.redraw()
+            end
+        ";
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.logger.clear();
+        let mut statuses = Vec::new();
+        let mut app_context = get_app_context!(app);
+        let mut plugin = Plugin::new_from_source(source, &mut app_context).unwrap();
+        plugin
+            .run_command("progress", &mut app_context, &mut |status| {
+                statuses.push(status.to_string());
+                Ok(())
+            })
+            .unwrap();
+        let messages = app_context
+            .logger
+            .iter()
+            .map(|line| (line.level, line.message.clone()))
+            .collect::<Vec<_>>();
+        drop(app_context);
+        assert_eq!(statuses, vec!["Step 1", "Step 2"]);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0], (NotificationLevel::Info, "Step 1".to_string()));
+    }
+
     #[test]
     fn test_log_from_lua() {
         let source = "
@@ -533,7 +664,9 @@ mod test {
         assert_eq!(commands[1].command, "test3");
         assert_eq!(commands[1].description, "Test command 3");
 
-        plugin.run_command("test", &mut app_context).unwrap();
+        plugin
+            .run_command("test", &mut app_context, &mut |_| Ok(()))
+            .unwrap();
 
         let commands = plugin.commands.get_commands();
         assert_eq!(commands.len(), 2);
@@ -543,7 +676,9 @@ mod test {
         assert_eq!(commands[1].description, "Test command 2");
 
         assert!(
-            plugin.run_command("test2", &mut app_context).is_err(),
+            plugin
+                .run_command("test2", &mut app_context, &mut |_| Ok(()))
+                .is_err(),
             "Should not be able to add a command that is not defined"
         );
 
@@ -558,7 +693,9 @@ mod test {
         assert_eq!(commands[1].command, "test2");
         assert_eq!(commands[1].description, "Test command 2");
 
-        plugin.run_command("test3", &mut app_context).unwrap();
+        plugin
+            .run_command("test3", &mut app_context, &mut |_| Ok(()))
+            .unwrap();
 
         let commands = plugin.commands.get_commands();
         assert_eq!(commands.len(), 3, "No duplicate commands should be added");
@@ -580,6 +717,8 @@ mod test {
                 context.log(1, context.header.bitness)
                 context.log(1, context.header.architecture)
                 context.log(1, context.header.entry_point)
+                context.log(1, context.header.file_type)
+                context.log(1, #context.header.sections)
             end
         ";
 
@@ -592,7 +731,7 @@ mod test {
         plugin.handle_with_error(event, &mut app_context).unwrap();
 
         let messages = app_context.logger.iter().collect::<Vec<_>>();
-        assert_eq!(messages.len(), 6);
+        assert_eq!(messages.len(), 8);
         assert_eq!(messages[3].message, 64.to_string(), "Default bitness is 64");
         assert_eq!(
             messages[4].message,
@@ -604,6 +743,8 @@ mod test {
             0.to_string(),
             "Default entry point is 0"
         );
+        assert_eq!(messages[6].message, "None", "Default file type is None");
+        assert_eq!(messages[7].message, "0", "Default header has no sections");
     }
 
     #[test]
@@ -728,6 +869,65 @@ mod test {
         assert_eq!(popup_text.lines[2].spans[0].content, "span4");
     }
 
+    #[test]
+    fn test_plugin_dialog() {
+        let source = "
+            function init(context)
+                context.open_dialog({
+                    title=\"Dialog\",
+                    lines={\"line1\", \"line2\"},
+                    options={\"A\", \"B\"}
+                }, \"on_choice\")
+            end
+
+            function on_choice(choice, context)
+                if choice == 2 then
+                    context.jump_to(0x42)
+                elseif choice == nil then
+                    context.jump_to(0x10)
+                end
+            end
+        ";
+
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.resize_to_size(80, 24);
+        let mut app_context = get_app_context!(app);
+        app_context.plugin_index = Some(0);
+        let mut plugin = Plugin::new_from_source(source, &mut app_context).unwrap();
+        plugin
+            .answer_dialog("on_choice", Some(2), &mut app_context)
+            .unwrap();
+        plugin
+            .answer_dialog("on_choice", Some(1), &mut app_context)
+            .unwrap();
+        drop(app_context);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x42);
+        let Some(PopupState::PluginDialog {
+            plugin_index: 0,
+            dialog,
+            selected: 0,
+            expires: None,
+        }) = app.popup.clone()
+        else {
+            panic!("The dialog is not open");
+        };
+        assert_eq!(dialog.title, "Dialog");
+        assert_eq!(dialog.lines, vec!["line1", "line2"]);
+        assert_eq!(dialog.options, vec!["A", "B"]);
+        assert_eq!(dialog.callback, "on_choice");
+        assert_eq!(dialog.height(), 7);
+
+        let mut app_context = get_app_context!(app);
+        plugin
+            .answer_dialog("on_choice", None, &mut app_context)
+            .unwrap();
+        assert!(plugin
+            .answer_dialog("missing", None, &mut app_context)
+            .is_err());
+        drop(app_context);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+    }
+
     #[test]
     fn test_plugin_instant() {
         let source = "
@@ -767,4 +967,99 @@ mod test {
         plugin.handle_with_error(event, &mut app_context).unwrap();
         assert_eq!(app.get_cursor_position().global_byte_index, 0x42);
     }
+
+    #[test]
+    fn test_get_selection_bytes() {
+        let source = "
+            function on_key(key_event, context)
+                bytes = context.get_selection_bytes()
+                if key_event.code == \"Down\" then
+                    if bytes == nil then
+                        error(\"Expected a selection\")
+                    end
+                    if #bytes ~= 3 or bytes[1] ~= 2 or bytes[3] ~= 4 then
+                        error(\"Wrong selection bytes\")
+                    end
+                elseif bytes ~= nil then
+                    error(\"Expected no selection\")
+                end
+            end
+        ";
+
+        let mut app = App::mockup((0..0x10).collect());
+        app.resize_to_size(80, 24);
+        let mut app_context = get_app_context!(app);
+        let mut plugin = Plugin::new_from_source(source, &mut app_context).unwrap();
+        let event = Event::Key {
+            event: KeyEvent::from(KeyCode::Up),
+        };
+        plugin.handle_with_error(event, &mut app_context).unwrap();
+
+        app.jump_to(2, false);
+        app.toggle_selection();
+        app.jump_to(4, false);
+        let mut app_context = get_app_context!(app);
+        let event = Event::Key {
+            event: KeyEvent::from(KeyCode::Down),
+        };
+        plugin.handle_with_error(event, &mut app_context).unwrap();
+    }
+
+    #[test]
+    fn test_get_selection_and_search() {
+        let source = "
+            function on_key(key_event, context)
+                selection = context.get_selection()
+                if selection == nil or selection.start ~= 2 or selection.size ~= 3 then
+                    error(\"Wrong selection\")
+                end
+                search = context.get_search()
+                if search == nil or search.query ~= \"ab\" then
+                    error(\"Wrong search\")
+                end
+                if #search.matches ~= 2 or search.matches[1] ~= 0 or search.matches[2] ~= 5 then
+                    error(\"Wrong matches\")
+                end
+            end
+        ";
+
+        let mut app = App::mockup(b"abcdeabcd".to_vec());
+        app.resize_to_size(80, 24);
+        app.find_text("ab", TextEncoding::Utf8);
+        app.jump_to(2, false);
+        app.toggle_selection();
+        app.jump_to(4, false);
+        let mut app_context = get_app_context!(app);
+        let mut plugin = Plugin::new_from_source(source, &mut app_context).unwrap();
+        let event = Event::Key {
+            event: KeyEvent::from(KeyCode::Down),
+        };
+        plugin.handle_with_error(event, &mut app_context).unwrap();
+    }
+
+    #[test]
+    fn test_unsaved_check() {
+        let source = "
+            notes = 0
+            function init(context)
+                context.add_unsaved_check(\"check_notes\")
+            end
+            function check_notes(context)
+                if notes > 0 then
+                    return \"Notes has \" .. notes .. \" unsaved notes\"
+                end
+            end
+        ";
+
+        let mut app = App::mockup(vec![0; 0x100]);
+        let mut app_context = get_app_context!(app);
+        let mut plugin = Plugin::new_from_source(source, &mut app_context).unwrap();
+        assert_eq!(plugin.unsaved_checks.checks.len(), 1);
+        assert!(plugin.get_unsaved_data(&mut app_context).is_empty());
+        plugin.lua.load("notes = 2").exec().unwrap();
+        assert_eq!(
+            plugin.get_unsaved_data(&mut app_context),
+            vec!["Notes has 2 unsaved notes".to_string()]
+        );
+    }
 }
@@ -0,0 +1,187 @@
+use std::time::{Duration, Instant};
+
+use mlua::Table;
+use ratatui::text::Line;
+
+use crate::{
+    app::{
+        log::NotificationLevel, popup::popup_state::PopupState,
+        settings::color_settings::ColorSettings, App,
+    },
+    get_app_context,
+};
+
+/// A popup whose content is described by a plugin, the core draws it and
+/// calls `callback` with the option chosen by the user.
+/// If `timeout` is set the dialog is closed by itself when it expires, unless the user touched it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginDialog {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub options: Vec<String>,
+    pub callback: String,
+    pub timeout: Option<Duration>,
+}
+
+impl PluginDialog {
+    /// Reads the dialog from a table like `{title="...", lines={...}, options={...}, timeout=...}`,
+    /// every field is optional, a dialog without options has a single "Ok" option,
+    /// the timeout is in seconds.
+    pub fn from_lua(dialog: &Table, callback: String) -> mlua::Result<Self> {
+        let title = dialog
+            .get::<Option<String>>("title")?
+            .unwrap_or_else(|| "Popup".to_string());
+        let lines = dialog
+            .get::<Option<Vec<String>>>("lines")?
+            .unwrap_or_default();
+        let mut options = dialog
+            .get::<Option<Vec<String>>>("options")?
+            .unwrap_or_default();
+        if options.is_empty() {
+            options.push("Ok".to_string());
+        }
+        let timeout = dialog
+            .get::<Option<f64>>("timeout")?
+            .and_then(|timeout| Duration::try_from_secs_f64(timeout).ok())
+            .filter(|timeout| !timeout.is_zero());
+        Ok(Self {
+            title,
+            lines,
+            options,
+            callback,
+            timeout,
+        })
+    }
+
+    pub fn expiry(&self) -> Option<Instant> {
+        self.timeout.map(|timeout| Instant::now() + timeout)
+    }
+
+    pub fn height(&self) -> usize {
+        let separator = if self.lines.is_empty() { 0 } else { 1 };
+        self.lines.len() + separator + self.options.len() + 2
+    }
+
+    pub fn to_lines(&self, selected: usize, color_settings: &ColorSettings) -> Vec<Line<'static>> {
+        let mut lines = self
+            .lines
+            .iter()
+            .map(|line| Line::raw(line.clone()))
+            .collect::<Vec<_>>();
+        if !lines.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        lines.extend(self.options.iter().enumerate().map(|(i, option)| {
+            let style = if i == selected {
+                color_settings.menu_text_selected
+            } else {
+                color_settings.menu_text
+            };
+            Line::styled(option.clone(), style)
+        }));
+        lines
+    }
+}
+
+impl App {
+    /// Calls the callback of a plugin dialog with the 1-based index of the chosen option,
+    /// or with `nil` if the dialog was closed without choosing.
+    pub(in crate::app) fn answer_plugin_dialog(
+        &mut self,
+        plugin_index: usize,
+        callback: &str,
+        choice: Option<usize>,
+    ) {
+        let mut app_context = get_app_context!(self);
+        app_context.plugin_index = Some(plugin_index);
+        if let Err(e) = self.plugin_manager.answer_dialog(
+            plugin_index,
+            callback,
+            choice.map(|choice| choice + 1),
+            &mut app_context,
+        ) {
+            self.log(NotificationLevel::Error, &format!("In plugin: {}", e));
+        }
+    }
+
+    /// Closes a plugin dialog whose timeout expired as if the user closed it.
+    pub(in crate::app) fn close_expired_popup(&mut self) {
+        if let Some(PopupState::PluginDialog {
+            plugin_index,
+            dialog,
+            expires: Some(expires),
+            ..
+        }) = &self.popup
+        {
+            if Instant::now() >= *expires {
+                let (plugin_index, callback) = (*plugin_index, dialog.callback.clone());
+                self.popup = None;
+                self.answer_plugin_dialog(plugin_index, &callback, None);
+            }
+        }
+    }
+
+    /// The time to wait for an event, it is shorter if a popup expires sooner.
+    pub(in crate::app) fn get_poll_time(&self) -> Duration {
+        match &self.popup {
+            Some(PopupState::PluginDialog {
+                expires: Some(expires),
+                ..
+            }) => self
+                .poll_time
+                .min(expires.saturating_duration_since(Instant::now())),
+            // the file being opened is checked often so that it opens as soon as it is read
+            _ if self.opening_file.is_some() => self.poll_time.min(Duration::from_millis(100)),
+            _ => self.poll_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::plugins::plugin_manager::PluginManager;
+
+    use super::*;
+
+    #[test]
+    fn test_plugin_dialog_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("dialog.lua"),
+            "
+            function init(context)
+                context.add_command(\"ask\", \"Opens a dialog\")
+            end
+
+            function ask(context)
+                context.open_dialog({title=\"Saved\", timeout=0.01}, \"on_choice\")
+            end
+
+            function on_choice(choice, context)
+                if choice == nil then
+                    context.jump_to(0x10)
+                end
+            end
+            ",
+        )
+        .unwrap();
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.resize_to_size(80, 24);
+        let mut app_context = get_app_context!(app);
+        app.plugin_manager = PluginManager::load(Some(dir.path()), &mut app_context).unwrap();
+        app.plugin_manager
+            .run_command("ask", &mut app_context, &mut |_| Ok(()))
+            .unwrap();
+        drop(app_context);
+        assert!(app.get_poll_time() <= Duration::from_millis(10));
+        app.close_expired_popup();
+        assert!(app.popup.is_some());
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(app.get_poll_time(), Duration::ZERO);
+        app.close_expired_popup();
+        assert!(app.popup.is_none());
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+        assert_eq!(app.get_poll_time(), app.poll_time);
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
 };
 
 use super::{
-    app_context::AppContext,
+    app_context::{AppContext, Redraw},
     event::{Event, Events},
     plugin::Plugin,
     popup_context::PopupContext,
@@ -20,6 +20,7 @@ pub struct PluginManager {
     plugins: Vec<Plugin>,
     on_open: Vec<usize>,
     on_save: Vec<usize>,
+    on_before_save: Vec<usize>,
     on_edit: Vec<usize>,
     on_key: Vec<usize>,
     on_mouse: Vec<usize>,
@@ -44,6 +45,9 @@ impl PluginManager {
             if handlers.contains(Events::ON_SAVE) {
                 plugin_manager.on_save.push(i);
             }
+            if handlers.contains(Events::ON_BEFORE_SAVE) {
+                plugin_manager.on_before_save.push(i);
+            }
             if handlers.contains(Events::ON_EDIT) {
                 plugin_manager.on_edit.push(i);
             }
@@ -113,6 +117,21 @@ impl PluginManager {
         }
     }
 
+    /// Lets the plugins modify the bytes that are about to be saved,
+    /// the first error is returned without calling the remaining plugins.
+    pub fn on_before_save(
+        &mut self,
+        bytes: &mut Vec<u8>,
+        app_context: &mut AppContext,
+    ) -> mlua::Result<()> {
+        for i in self.on_before_save.iter() {
+            app_context.plugin_index = Some(*i);
+            let event = Event::BeforeSave { bytes };
+            self.plugins[*i].handle_with_error(event, app_context)?;
+        }
+        Ok(())
+    }
+
     pub fn on_edit(&mut self, new_bytes: &mut Vec<u8>, app_context: &mut AppContext) {
         for i in self.on_edit.iter() {
             app_context.plugin_index = Some(*i);
@@ -179,6 +198,15 @@ impl PluginManager {
         }
     }
 
+    pub fn get_unsaved_data(&mut self, app_context: &mut AppContext) -> Vec<String> {
+        let mut ret = Vec::new();
+        for (i, plugin) in self.plugins.iter_mut().enumerate() {
+            app_context.plugin_index = Some(i);
+            ret.extend(plugin.get_unsaved_data(app_context));
+        }
+        ret
+    }
+
     pub fn get_commands(&self) -> Vec<&CommandInfo> {
         let mut commands = Vec::new();
         let command_count = self.plugins.iter().map(|p| p.get_commands().len()).sum();
@@ -189,13 +217,18 @@ impl PluginManager {
         commands
     }
 
-    pub fn run_command(&mut self, command: &str, app_context: &mut AppContext) -> mlua::Result<()> {
+    pub fn run_command(
+        &mut self,
+        command: &str,
+        app_context: &mut AppContext,
+        redraw: &mut Redraw,
+    ) -> mlua::Result<()> {
         let mut found = false;
         for (i, plugin) in self.plugins.iter_mut().enumerate() {
             if let Some(_command_info) = plugin.get_commands().iter().find(|c| c.command == command)
             {
                 app_context.plugin_index = Some(i);
-                plugin.run_command(command, app_context)?;
+                plugin.run_command(command, app_context, redraw)?;
                 found = true;
                 break;
             }
@@ -220,6 +253,16 @@ impl PluginManager {
         self.plugins[plugin_index].fill_popup(callback, popup_context, app_context)
     }
 
+    pub fn answer_dialog(
+        &mut self,
+        plugin_index: usize,
+        callback: &str,
+        choice: Option<usize>,
+        app_context: &mut AppContext,
+    ) -> mlua::Result<()> {
+        self.plugins[plugin_index].answer_dialog(callback, choice, app_context)
+    }
+
     pub fn try_parse_header(&mut self, app_context: &mut AppContext) -> Option<CustomHeader> {
         for (i, plugin) in self.plugins.iter_mut().enumerate() {
             app_context.plugin_index = Some(i);
@@ -251,16 +294,16 @@ mod test {
         assert_eq!(app.plugin_manager.plugins.len(), 2);
 
         app.plugin_manager
-            .run_command("p1c1", &mut app_context)
+            .run_command("p1c1", &mut app_context, &mut |_| Ok(()))
             .unwrap();
         app.plugin_manager
-            .run_command("p1c2", &mut app_context)
+            .run_command("p1c2", &mut app_context, &mut |_| Ok(()))
             .unwrap();
         app.plugin_manager
-            .run_command("p2c1", &mut app_context)
+            .run_command("p2c1", &mut app_context, &mut |_| Ok(()))
             .unwrap();
         app.plugin_manager
-            .run_command("p2c2", &mut app_context)
+            .run_command("p2c2", &mut app_context, &mut |_| Ok(()))
             .unwrap();
 
         app.plugin_manager.on_open(&mut app_context);
@@ -1,4 +1,5 @@
 use mlua::IntoLua;
+use ratatui::layout::Rect;
 
 use crate::app::{
     asm::assembly_line::AssemblyLine, frame_info::InfoViewFrameInfo,
@@ -66,6 +67,116 @@ impl App {
         }
     }
 
+    fn get_pane_view_byte_info(
+        &self,
+        relative_location: Point,
+        borders: RectBorders,
+    ) -> (Option<u64>, Option<u8>) {
+        if borders.top || borders.right {
+            return (None, None);
+        }
+        let offset_x = relative_location.x as usize;
+        let current_block = self.last_frame_info.blocks_per_row
+            * (self.last_frame_info.scroll + (relative_location.y - 1) as usize)
+            + offset_x / (self.block_size * 3 + 1);
+        let block_offset = offset_x % (self.block_size * 3 + 1);
+        let current_byte = current_block * self.block_size + block_offset / 3;
+        if block_offset % 3 == 2 || block_offset == self.block_size * 3 {
+            return (None, None);
+        }
+        match self
+            .pane
+            .as_ref()
+            .and_then(|pane| pane.bytes.get(current_byte))
+        {
+            Some(byte) => (Some(current_byte as u64), Some(*byte)),
+            None => (None, None),
+        }
+    }
+
+    fn get_info_view_location(
+        &self,
+        info_view_frame_info: &InfoViewFrameInfo,
+        rect: Rect,
+        relative_location: Point,
+        borders: RectBorders,
+    ) -> Option<UiLocation> {
+        match info_view_frame_info {
+            InfoViewFrameInfo::TextView => {
+                // the rows of the text view are aligned with the hex view
+                let (file_address, virtual_address, byte, _high) = self
+                    .get_hex_and_text_view_byte_info(
+                        Point::new(relative_location.x, relative_location.y + rect.y),
+                        true,
+                        borders,
+                    );
+
+                Some(UiLocation {
+                    info: UiLocationInfo::TextView {
+                        file_address,
+                        virtual_address,
+                        byte,
+                        character: byte.and_then(|b| char::from_u32(b as u32)),
+                    },
+                    relative_location,
+                })
+            }
+            InfoViewFrameInfo::PaneView => {
+                let (file_address, byte) =
+                    self.get_pane_view_byte_info(relative_location, borders);
+                Some(UiLocation {
+                    info: UiLocationInfo::PaneView { file_address, byte },
+                    relative_location,
+                })
+            }
+            InfoViewFrameInfo::AssemblyView { scroll } => {
+                let (section, file_address, virtual_address, instruction) =
+                    if borders.top || borders.right {
+                        (None, None, None, None)
+                    } else {
+                        let (instruction_offset, _) = Self::assembly_row_to_line(
+                            *scroll + relative_location.y as usize - 1,
+                            &self.get_folded_lines(),
+                        );
+                        if let Some(assembly_line) =
+                            self.assembly_instructions.get(instruction_offset)
+                        {
+                            match assembly_line {
+                                AssemblyLine::Instruction(instruction_tag) => (
+                                    Some(
+                                        self.header
+                                            .get_text_section()
+                                            .map(|s| s.name.clone())
+                                            .unwrap_or(".text".into()),
+                                    ),
+                                    Some(instruction_tag.file_address),
+                                    Some(instruction_tag.instruction.ip()),
+                                    Some(instruction_tag.instruction.to_string()),
+                                ),
+                                AssemblyLine::SectionTag(section_tag) => (
+                                    Some(section_tag.name.clone()),
+                                    Some(section_tag.file_address),
+                                    Some(section_tag.virtual_address),
+                                    None,
+                                ),
+                            }
+                        } else {
+                            (None, None, None, None)
+                        }
+                    };
+                Some(UiLocation {
+                    info: UiLocationInfo::AssemblyView {
+                        section,
+                        file_address,
+                        virtual_address,
+                        instruction,
+                    },
+                    relative_location,
+                })
+            }
+        }
+    }
+
     pub(in crate::app) fn get_ui_location(&self, global_location: Point) -> Option<UiLocation> {
         if let (Some(popup_rect), Some(popup_info)) = (&self.last_frame_info.popup, &self.popup) {
             if let Some((relative_location, _borders)) =
@@ -75,16 +186,46 @@ impl App {
                     PopupState::Open { .. } => "Open",
                     PopupState::Run { .. } => "Run",
                     PopupState::FindText { .. } => "FindText",
+                    PopupState::FindHex { .. } => "FindHex",
                     PopupState::FindSymbol { .. } => "FindSymbol",
+                    PopupState::Sections { .. } => "Sections",
                     PopupState::Log(_) => "Log",
                     PopupState::InsertText { .. } => "InsertText",
                     PopupState::Patch { .. } => "Patch",
                     PopupState::JumpToAddress { .. } => "JumpToAddress",
+                    PopupState::AssemblyGoto { .. } => "AssemblyGoto",
+                    PopupState::GotoRow { .. } => "GotoRow",
                     PopupState::QuitDirtySave(_) => "QuitDirtySave",
+                    PopupState::QuitPluginData { .. } => "QuitPluginData",
+                    PopupState::PreviewPatch { .. } => "PreviewPatch",
                     PopupState::SaveAndQuit(_) => "SaveAndQuit",
+                    PopupState::Fill { .. } => "Fill",
+                    PopupState::FindOccurrence { .. } => "FindOccurrence",
+                    PopupState::Calculator { .. } => "Calculator",
+                    PopupState::WriteValue { .. } => "WriteValue",
                     PopupState::SaveAs { .. } => "SaveAs",
+                    PopupState::ExtractAs { .. } => "ExtractAs",
+                    PopupState::ExportHexdump { .. } => "ExportHexdump",
+                    PopupState::ExportStrings { .. } => "ExportStrings",
+                    PopupState::CompareWith { .. } => "CompareWith",
+                    PopupState::SavePatch { .. } => "SavePatch",
+                    PopupState::ApplyPatch { .. } => "ApplyPatch",
+                    PopupState::OpenPane { .. } => "OpenPane",
+                    PopupState::AddField { .. } => "AddField",
+                    PopupState::Fields(_) => "Fields",
+                    PopupState::Pointers { .. } => "Pointers",
+                    PopupState::RecentDirs { .. } => "RecentDirs",
+                    PopupState::SaveProfile { .. } => "SaveProfile",
+                    PopupState::Profiles { .. } => "Profiles",
+                    PopupState::Strings { .. } => "Strings",
                     PopupState::Save(_) => "Save",
+                    PopupState::Revert(_) => "Revert",
+                    PopupState::DuplicateSelection(_) => "DuplicateSelection",
+                    PopupState::OpenLargeFile { .. } => "OpenLargeFile",
+                    PopupState::ReadTimedOut { .. } => "ReadTimedOut",
                     PopupState::Help(_) => "Help",
+                    PopupState::History(_) => "History",
+                    PopupState::PluginDialog { .. } => "PluginDialog",
                     PopupState::Custom { .. } => "Custom",
                 }
                 .into();
@@ -146,64 +287,15 @@ impl App {
         } else if let Some((relative_location, borders)) =
             global_location.get_relative_location(&self.last_frame_info.info_view)
         {
-            match &self.last_frame_info.info_view_frame_info {
-                InfoViewFrameInfo::TextView => {
-                    let (file_address, virtual_address, byte, _high) =
-                        self.get_hex_and_text_view_byte_info(relative_location, true, borders);
-
-                    Some(UiLocation {
-                        info: UiLocationInfo::TextView {
-                            file_address,
-                            virtual_address,
-                            byte,
-                            character: byte.and_then(|b| char::from_u32(b as u32)),
-                        },
-                        relative_location,
-                    })
-                }
-                InfoViewFrameInfo::AssemblyView { scroll } => {
-                    let (section, file_address, virtual_address, instruction) =
-                        if borders.top || borders.right {
-                            (None, None, None, None)
-                        } else {
-                            let instruction_offset = scroll + relative_location.y as usize - 1;
-                            if let Some(assembly_line) =
-                                self.assembly_instructions.get(instruction_offset)
-                            {
-                                match assembly_line {
-                                    AssemblyLine::Instruction(instruction_tag) => (
-                                        Some(
-                                            self.header
-                                                .get_text_section()
-                                                .map(|s| s.name.clone())
-                                                .unwrap_or(".text".into()),
-                                        ),
-                                        Some(instruction_tag.file_address),
-                                        Some(instruction_tag.instruction.ip()),
-                                        Some(instruction_tag.instruction.to_string()),
-                                    ),
-                                    AssemblyLine::SectionTag(section_tag) => (
-                                        Some(section_tag.name.clone()),
-                                        Some(section_tag.file_address),
-                                        Some(section_tag.virtual_address),
-                                        None,
-                                    ),
-                                }
-                            } else {
-                                (None, None, None, None)
-                            }
-                        };
-                    Some(UiLocation {
-                        info: UiLocationInfo::AssemblyView {
-                            section,
-                            file_address,
-                            virtual_address,
-                            instruction,
-                        },
-                        relative_location,
-                    })
-                }
-            }
+            self.get_info_view_location(
+                &self.last_frame_info.info_view_frame_info,
+                self.last_frame_info.info_view,
+                relative_location,
+                borders,
+            )
+        } else if let Some((rect, info_view_frame_info)) = &self.last_frame_info.split_info_view {
+            let (relative_location, borders) = global_location.get_relative_location(rect)?;
+            self.get_info_view_location(info_view_frame_info, *rect, relative_location, borders)
         } else {
             None
         }
@@ -381,4 +473,43 @@ mod tests {
             UiLocationInfo::Popup { name: "Run".into() }
         );
     }
+
+    #[test]
+    fn test_get_ui_location_split_view() {
+        let mut app = App::mockup(vec![0x90; 0x100]);
+        app.resize_to_size(80, 25);
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.toggle_split_view();
+        app.draw(&mut terminal).unwrap();
+
+        let ui_location = app.get_ui_location(Point::new(42, 2)).unwrap();
+        assert!(matches!(
+            ui_location.info,
+            UiLocationInfo::TextView {
+                file_address: Some(0x8),
+                ..
+            }
+        ));
+        let ui_location = app.get_ui_location(Point::new(42, 13)).unwrap();
+        assert!(matches!(
+            ui_location.info,
+            UiLocationInfo::AssemblyView {
+                file_address: Some(_),
+                ..
+            }
+        ));
+        assert_eq!(ui_location.relative_location, Point::new(0, 1));
+
+        // The text view is aligned with the hex view even when it is at the bottom
+        app.request_view_change();
+        app.draw(&mut terminal).unwrap();
+        let ui_location = app.get_ui_location(Point::new(42, 13)).unwrap();
+        assert!(matches!(
+            ui_location.info,
+            UiLocationInfo::TextView {
+                file_address: Some(0x60),
+                ..
+            }
+        ));
+    }
 }
@@ -23,6 +23,10 @@ pub enum UiLocationInfo {
         virtual_address: Option<u64>,
         instruction: Option<String>,
     },
+    PaneView {
+        file_address: Option<u64>,
+        byte: Option<u8>,
+    },
     StatusBar,
     ScrollBar,
     Popup {
@@ -74,6 +78,11 @@ impl IntoLua for UiLocationInfo {
                 ret.set("virtual_address", virtual_address)?;
                 ret.set("instruction", instruction)?;
             }
+            UiLocationInfo::PaneView { file_address, byte } => {
+                ret.set("type", "PaneView")?;
+                ret.set("file_address", file_address)?;
+                ret.set("byte", byte)?;
+            }
             UiLocationInfo::StatusBar => {
                 ret.set("type", "StatusBar")?;
             }
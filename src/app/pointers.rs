@@ -0,0 +1,200 @@
+use object::Endianness;
+
+use super::{log::NotificationLevel, popup::popup_state::PopupState, App};
+
+/// A pointer read from the selection, `target` is the file offset it points to if it resolves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pointer {
+    pub offset: usize,
+    pub value: u64,
+    pub target: Option<usize>,
+}
+
+impl App {
+    /// Converts the value of a pointer to a file offset, if the file has no sections
+    /// the value is already a file offset.
+    pub(in crate::app) fn resolve_pointer(&self, value: u64) -> Option<usize> {
+        let offset = if self.header.get_sections().is_empty() {
+            Some(value)
+        } else {
+            self.header.virtual_to_physical_address(value)
+        };
+        offset
+            .map(|offset| offset as usize)
+            .filter(|&offset| offset < self.data.len())
+    }
+
+    /// Reads the selection as a table of pointers using the bitness and the endianness
+    /// of the file, trailing bytes that are not a whole pointer are ignored.
+    pub(super) fn get_pointers(&self, start: usize, end: usize) -> Vec<Pointer> {
+        let pointer_size = self.header.bitness() as usize / 8;
+        let endianness = self.header.endianness();
+        self.data.bytes()[start..end]
+            .chunks_exact(pointer_size)
+            .enumerate()
+            .map(|(i, bytes)| {
+                let mut buffer = [0u8; 8];
+                let value = match endianness {
+                    Endianness::Little => {
+                        buffer[..pointer_size].copy_from_slice(bytes);
+                        u64::from_le_bytes(buffer)
+                    }
+                    Endianness::Big => {
+                        buffer[8 - pointer_size..].copy_from_slice(bytes);
+                        u64::from_be_bytes(buffer)
+                    }
+                };
+                Pointer {
+                    offset: start + i * pointer_size,
+                    value,
+                    target: self.resolve_pointer(value),
+                }
+            })
+            .collect()
+    }
+
+    pub(in crate::app) fn request_popup_pointers(&mut self) {
+        let Some((start, end)) = self.get_selection() else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+            return;
+        };
+        let pointers = self.get_pointers(start, end);
+        if pointers.is_empty() {
+            self.log(
+                NotificationLevel::Warning,
+                "The selection is smaller than a pointer.",
+            );
+            return;
+        }
+        let unresolved = pointers
+            .iter()
+            .filter(|pointer| pointer.target.is_none())
+            .count();
+        if unresolved > 0 {
+            self.log(
+                NotificationLevel::Warning,
+                &format!(
+                    "{} of {} pointers do not point inside the file",
+                    unresolved,
+                    pointers.len()
+                ),
+            );
+        }
+        self.popup = Some(PopupState::Pointers {
+            pointers,
+            scroll: 0,
+        });
+    }
+
+    /// Parses the number written as text around `index`, it is read as hexadecimal if it starts
+    /// with `0x`, ends with `h` or contains hex letters, as decimal otherwise.
+    pub(super) fn parse_text_offset(bytes: &[u8], index: usize) -> Option<u64> {
+        if !bytes.get(index)?.is_ascii_alphanumeric() {
+            return None;
+        }
+        let start = bytes[..index]
+            .iter()
+            .rposition(|byte| !byte.is_ascii_alphanumeric())
+            .map_or(0, |position| position + 1);
+        let end = bytes[index..]
+            .iter()
+            .position(|byte| !byte.is_ascii_alphanumeric())
+            .map_or(bytes.len(), |position| index + position);
+        let text = std::str::from_utf8(&bytes[start..end]).ok()?;
+        if let Some(hex) = text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .or_else(|| text.strip_suffix('h'))
+            .or_else(|| text.strip_suffix('H'))
+        {
+            u64::from_str_radix(hex, 16).ok()
+        } else {
+            text.parse::<u64>()
+                .ok()
+                .or_else(|| u64::from_str_radix(text, 16).ok())
+        }
+    }
+
+    pub(in crate::app) fn jump_to_text_offset(&mut self) {
+        let index = self.get_cursor_position().global_byte_index;
+        match Self::parse_text_offset(self.data.bytes(), index) {
+            Some(offset) if offset < self.data.len() as u64 => {
+                self.log(
+                    NotificationLevel::Info,
+                    &format!("Jumping to the offset {:#X}", offset),
+                );
+                self.jump_to_remembered(offset as usize, false);
+            }
+            Some(offset) => self.log(
+                NotificationLevel::Warning,
+                &format!("The offset {:#X} is not inside the file.", offset),
+            ),
+            None => self.log(
+                NotificationLevel::Info,
+                "The text at the cursor is not a number.",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pointers() {
+        let mut data = vec![0; 0x40];
+        data[..8].copy_from_slice(&0x20u64.to_le_bytes());
+        data[8..16].copy_from_slice(&0x1000u64.to_le_bytes());
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        let pointers = app.get_pointers(0, 20);
+        assert_eq!(
+            pointers,
+            vec![
+                Pointer {
+                    offset: 0,
+                    value: 0x20,
+                    target: Some(0x20)
+                },
+                Pointer {
+                    offset: 8,
+                    value: 0x1000,
+                    target: None
+                }
+            ]
+        );
+
+        app.toggle_selection();
+        app.jump_to(15, false);
+        app.request_popup_pointers();
+        assert!(matches!(
+            app.popup,
+            Some(PopupState::Pointers { ref pointers, scroll: 0 }) if pointers.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_text_offset() {
+        let text = b"at 0x20, 48 and 1Ah or 1f; none";
+        assert_eq!(App::parse_text_offset(text, 4), Some(0x20));
+        assert_eq!(App::parse_text_offset(text, 10), Some(48));
+        assert_eq!(App::parse_text_offset(text, 16), Some(0x1A));
+        assert_eq!(App::parse_text_offset(text, 24), Some(0x1F));
+        assert_eq!(App::parse_text_offset(text, 2), None);
+        assert_eq!(App::parse_text_offset(text, 28), None);
+
+        let mut data = text.to_vec();
+        data.resize(0x40, 0);
+        let mut app = App::mockup(data);
+        app.jump_to(10, false);
+        app.jump_to_text_offset();
+        assert_eq!(app.get_cursor_position().global_byte_index, 48);
+        app.jump_to(0, false);
+        app.jump_to_text_offset();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+        app.jump_to(4, false);
+        app.jump_to_text_offset();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x20);
+    }
+}
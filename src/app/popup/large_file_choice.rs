@@ -0,0 +1,54 @@
+use ratatui::text::{Line, Span};
+
+use crate::app::settings::color_settings::ColorSettings;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LargeFileChoice {
+    Open,
+    ReadOnly,
+    NoDisassembly,
+    Cancel,
+}
+
+impl LargeFileChoice {
+    pub fn to_line(&self, color_settings: &ColorSettings) -> Line<'static> {
+        let mut ret = Line::from(vec![
+            Span::styled("Open", color_settings.yes),
+            Span::raw("  "),
+            Span::styled("Read-only", color_settings.menu_text),
+            Span::raw("  "),
+            Span::styled("No disassembly", color_settings.menu_text),
+            Span::raw("  "),
+            Span::styled("Cancel", color_settings.no),
+        ]);
+
+        match self {
+            LargeFileChoice::Open => ret.spans[0].style = color_settings.yes_selected,
+            LargeFileChoice::ReadOnly => ret.spans[2].style = color_settings.menu_text_selected,
+            LargeFileChoice::NoDisassembly => {
+                ret.spans[4].style = color_settings.menu_text_selected
+            }
+            LargeFileChoice::Cancel => ret.spans[6].style = color_settings.no_selected,
+        }
+
+        ret
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LargeFileChoice::Open => LargeFileChoice::ReadOnly,
+            LargeFileChoice::ReadOnly => LargeFileChoice::NoDisassembly,
+            LargeFileChoice::NoDisassembly => LargeFileChoice::Cancel,
+            LargeFileChoice::Cancel => LargeFileChoice::Open,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            LargeFileChoice::Open => LargeFileChoice::Cancel,
+            LargeFileChoice::ReadOnly => LargeFileChoice::Open,
+            LargeFileChoice::NoDisassembly => LargeFileChoice::ReadOnly,
+            LargeFileChoice::Cancel => LargeFileChoice::NoDisassembly,
+        }
+    }
+}
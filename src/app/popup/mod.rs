@@ -1,3 +1,4 @@
 pub mod binary_choice;
+pub mod large_file_choice;
 pub mod popup_state;
 pub mod simple_choice;
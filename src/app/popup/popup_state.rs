@@ -1,19 +1,24 @@
-use std::error::Error;
+use std::{error::Error, time::Instant};
 
 use ratatui::text::{Line, Span, Text};
 
-use crate::get_app_context;
+use crate::{get_app_context, headers::section::Section};
 
 use crate::app::{
     asm::assembly_line::AssemblyLine,
     commands::command_info::CommandInfo,
     files::{path, path_result::PathResult},
-    plugins::popup_context::PopupContext,
+    patch_file::PatchFile,
+    plugins::{plugin_dialog::PluginDialog, popup_context::PopupContext},
+    pointers::Pointer,
     settings::color_settings::ColorSettings,
+    strings::FoundString,
+    text_encoding::TextEncoding,
     App,
 };
 
 use super::binary_choice::BinaryChoice;
+use super::large_file_choice::LargeFileChoice;
 use super::simple_choice::SimpleChoice;
 
 #[derive(Clone, Debug)]
@@ -32,6 +37,11 @@ pub enum PopupState {
         scroll: usize,
     },
     FindText {
+        text: String,
+        encoding: TextEncoding,
+        cursor: usize,
+    },
+    FindHex {
         text: String,
         cursor: usize,
     },
@@ -42,6 +52,12 @@ pub enum PopupState {
         scroll: usize,
     },
     Log(usize),
+    Sections {
+        filter: String,
+        cursor: usize,
+        results: Vec<Section>,
+        scroll: usize,
+    },
     InsertText {
         text: String,
         cursor: usize,
@@ -55,20 +71,164 @@ pub enum PopupState {
         location: String,
         cursor: usize,
     },
+    AssemblyGoto {
+        text: String,
+        cursor: usize,
+    },
+    GotoRow {
+        text: String,
+        cursor: usize,
+    },
+    Fill {
+        text: String,
+        cursor: usize,
+    },
+    FindOccurrence {
+        text: String,
+        cursor: usize,
+    },
+    Calculator {
+        text: String,
+        cursor: usize,
+    },
+    WriteValue {
+        text: String,
+        cursor: usize,
+    },
     QuitDirtySave(SimpleChoice),
+    QuitPluginData {
+        messages: Vec<String>,
+        choice: BinaryChoice,
+    },
     SaveAndQuit(BinaryChoice),
     SaveAs {
         path: String,
         cursor: usize,
     },
     Save(BinaryChoice),
+    Revert(BinaryChoice),
+    DuplicateSelection(BinaryChoice),
+    OpenLargeFile {
+        path: String,
+        size: u64,
+        choice: LargeFileChoice,
+    },
+    ReadTimedOut {
+        path: String,
+        choice: BinaryChoice,
+    },
+    ExtractAs {
+        path: String,
+        cursor: usize,
+    },
+    ExportHexdump {
+        path: String,
+        cursor: usize,
+    },
+    ExportStrings {
+        path: String,
+        cursor: usize,
+    },
+    CompareWith {
+        path: String,
+        cursor: usize,
+    },
+    SavePatch {
+        path: String,
+        cursor: usize,
+    },
+    ApplyPatch {
+        path: String,
+        cursor: usize,
+    },
+    PreviewPatch {
+        path: String,
+        patch: PatchFile,
+        choice: BinaryChoice,
+    },
+    OpenPane {
+        path: String,
+        cursor: usize,
+    },
+    AddField {
+        text: String,
+        cursor: usize,
+    },
+    Fields(usize),
+    Pointers {
+        pointers: Vec<Pointer>,
+        scroll: usize,
+    },
+    RecentDirs {
+        dirs: Vec<String>,
+        scroll: usize,
+    },
+    SaveProfile {
+        text: String,
+        cursor: usize,
+    },
+    Profiles {
+        profiles: Vec<String>,
+        scroll: usize,
+    },
+    Strings {
+        strings: Vec<FoundString>,
+        scroll: usize,
+    },
     Help(usize),
+    History(usize),
+    PluginDialog {
+        plugin_index: usize,
+        dialog: PluginDialog,
+        selected: usize,
+        expires: Option<Instant>,
+    },
     Custom {
         plugin_index: usize,
         callback: String,
     },
 }
 
+impl PopupState {
+    /// Moves the selection to the next or previous button of the popups that have buttons,
+    /// the selection wraps around. Returns false if the popup has no buttons.
+    pub fn select_button(&mut self, next: bool) -> bool {
+        match self {
+            PopupState::Save(choice)
+            | PopupState::SaveAndQuit(choice)
+            | PopupState::Revert(choice)
+            | PopupState::DuplicateSelection(choice)
+            | PopupState::QuitPluginData { choice, .. }
+            | PopupState::PreviewPatch { choice, .. }
+            | PopupState::ReadTimedOut { choice, .. } => {
+                *choice = if next {
+                    choice.next()
+                } else {
+                    choice.previous()
+                };
+                true
+            }
+            PopupState::QuitDirtySave(choice) => {
+                *choice = if next {
+                    choice.next()
+                } else {
+                    choice.previous()
+                };
+                true
+            }
+            PopupState::OpenLargeFile { choice, .. } => {
+                *choice = if next {
+                    choice.next()
+                } else {
+                    choice.previous()
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
 impl App {
     pub(in crate::app) fn get_scrollable_popup_line_count(&self) -> usize {
         let screen_height = self.screen_size.1 as isize;
@@ -76,8 +236,15 @@ impl App {
             Some(PopupState::Open { .. }) => screen_height - 7 - 2,
             Some(PopupState::Run { .. }) => screen_height - 6 - 2,
             Some(PopupState::FindSymbol { .. }) => screen_height - 6 - 2,
+            Some(PopupState::Sections { .. }) => screen_height - 6 - 2,
             Some(PopupState::Log(_)) => screen_height - 4 - 2,
             Some(PopupState::Help(_)) => screen_height - 4 - 2,
+            Some(PopupState::History(_)) => screen_height - 4 - 2,
+            Some(PopupState::Fields(_)) => screen_height - 4 - 2,
+            Some(PopupState::Pointers { .. }) => screen_height - 4 - 2,
+            Some(PopupState::RecentDirs { .. }) => screen_height - 4 - 2,
+            Some(PopupState::Profiles { .. }) => screen_height - 4 - 2,
+            Some(PopupState::Strings { .. }) => screen_height - 4 - 2,
             Some(PopupState::Patch { .. }) => screen_height - 6 - 2,
             Some(PopupState::InsertText { .. }) => screen_height - 5 - 2,
             _ => unimplemented!("Popup is not supposed to have scrollable lines"),
@@ -153,11 +320,28 @@ impl App {
         preview_string
     }
 
+    fn get_save_summary(&self) -> Line<'static> {
+        let changed = self.data.changed_bytes();
+        Line::raw(format!(
+            "{} byte{} changed, the new size is {} bytes.",
+            changed,
+            if changed == 1 { "" } else { "s" },
+            self.data.len()
+        ))
+    }
+
     pub(in crate::app) fn resize_popup_if_needed(popup: &mut Option<PopupState>) {
         match popup {
             Some(PopupState::FindSymbol { scroll, .. })
+            | Some(PopupState::Sections { scroll, .. })
             | Some(PopupState::Log(scroll))
-            | Some(PopupState::Help(scroll)) => {
+            | Some(PopupState::Help(scroll))
+            | Some(PopupState::History(scroll))
+            | Some(PopupState::Fields(scroll))
+            | Some(PopupState::Pointers { scroll, .. })
+            | Some(PopupState::RecentDirs { scroll, .. })
+            | Some(PopupState::Profiles { scroll, .. })
+            | Some(PopupState::Strings { scroll, .. }) => {
                 *scroll = 0;
             }
             _ => {}
@@ -447,10 +631,14 @@ impl App {
                     popup_text.lines.push(Line::raw(""));
                 }
             }
-            Some(PopupState::FindText { text, cursor }) => {
-                *popup_title = "Find Text".into();
+            Some(PopupState::FindText {
+                text,
+                encoding,
+                cursor,
+            }) => {
+                *popup_title = format!("Find Text ({})", encoding);
                 let available_width = width.saturating_sub(2);
-                *height = 3;
+                *height = 4;
                 let editable_string = Self::get_line_from_string_and_cursor(
                     &self.settings.color,
                     text,
@@ -462,6 +650,30 @@ impl App {
                 popup_text
                     .lines
                     .extend(vec![editable_string.left_aligned()]);
+                popup_text.lines.push(Line::styled(
+                    format!(
+                        "{} or {} to change the encoding",
+                        Self::key_event_to_string(self.settings.key.next_button),
+                        Self::key_event_to_string(self.settings.key.previous_button)
+                    ),
+                    self.settings.color.placeholder,
+                ));
+            }
+            Some(PopupState::FindHex { text, cursor }) => {
+                *popup_title = "Find Hex".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Bytes, such as de ad be ef",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
             }
             Some(PopupState::FindSymbol {
                 filter,
@@ -600,6 +812,74 @@ impl App {
                         .extend(vec![Line::raw("No symbol table found.").left_aligned()]);
                 }
             }
+            Some(PopupState::Sections {
+                filter,
+                cursor,
+                results,
+                scroll,
+            }) => {
+                *popup_title = "Sections".into();
+                let available_width = width.saturating_sub(2);
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 2 + 4;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    filter,
+                    *cursor,
+                    "Filter",
+                    available_width,
+                    true,
+                );
+                popup_text.lines.extend(vec![
+                    editable_string.left_aligned(),
+                    Line::raw("─".repeat(*width)),
+                ]);
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(results.len().saturating_sub(max_lines));
+                if skip > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                if results.is_empty() {
+                    popup_text
+                        .lines
+                        .push(Line::raw("No sections found.").left_aligned());
+                }
+                popup_text
+                    .lines
+                    .extend(results.iter().enumerate().skip(skip).take(max_lines).map(
+                        |(i, section)| {
+                            let style = if i == *scroll {
+                                self.settings.color.menu_text_selected
+                            } else {
+                                self.settings.color.menu_text
+                            };
+                            Line::styled(
+                                format!(
+                                    "{} VA {:X} offset {:X} size {:X}",
+                                    section.name,
+                                    section.virtual_address,
+                                    section.file_offset,
+                                    section.size
+                                ),
+                                style,
+                            )
+                            .left_aligned()
+                        },
+                    ));
+                if results.len() as isize - skip as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
             Some(PopupState::Log(scroll)) => {
                 *popup_title = "Log".into();
                 let max_lines = self.get_scrollable_popup_line_count();
@@ -762,21 +1042,23 @@ impl App {
             }
             Some(PopupState::SaveAndQuit(choice)) => {
                 *popup_title = "Save and Quit".into();
+                *height = 6;
                 popup_text.lines.extend(vec![
                     Line::raw("The file will be saved and the program will quit."),
+                    self.get_save_summary(),
                     Line::raw("Are you sure?"),
                     choice.to_line(&self.settings.color),
                 ]);
             }
-            Some(PopupState::SaveAs { path, cursor }) => {
-                *popup_title = "Save As".into();
+            Some(PopupState::Fill { text, cursor }) => {
+                *popup_title = "Fill".into();
                 let available_width = width.saturating_sub(2);
                 *height = 3;
                 let editable_string = Self::get_line_from_string_and_cursor(
                     &self.settings.color,
-                    path,
+                    text,
                     *cursor,
-                    "Path",
+                    "Pattern * Length",
                     available_width,
                     true,
                 );
@@ -784,27 +1066,128 @@ impl App {
                     .lines
                     .extend(vec![editable_string.left_aligned()]);
             }
-            Some(PopupState::Save(choice)) => {
-                *popup_title = "Save".into();
+            Some(PopupState::AssemblyGoto { text, cursor }) => {
+                *popup_title = "Go to Instruction".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Virtual address or +/-offset",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::GotoRow { text, cursor }) => {
+                *popup_title = "Go to Row".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Row number",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::SaveProfile { text, cursor }) => {
+                *popup_title = "Save Profile".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Name",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::FindOccurrence { text, cursor }) => {
+                *popup_title = "Find Nth".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Byte # Ordinal",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::Calculator { text, cursor }) => {
+                *popup_title = "Calculator".into();
+                let available_width = width.saturating_sub(2);
+                *height = 4;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Expression",
+                    available_width,
+                    true,
+                );
                 popup_text.lines.extend(vec![
-                    Line::raw("The file will be saved."),
-                    Line::raw("Are you sure?"),
-                    choice.to_line(&self.settings.color),
+                    editable_string.left_aligned(),
+                    Self::get_calculator_result(&self.settings.color, text).left_aligned(),
                 ]);
             }
-            Some(PopupState::QuitDirtySave(choice)) => {
-                *popup_title = "Quit".into();
+            Some(PopupState::WriteValue { text, cursor }) => {
+                *popup_title = "Write Value".into();
+                let available_width = width.saturating_sub(2);
+                *height = 4;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Value Type [le|be]",
+                    available_width,
+                    true,
+                );
                 popup_text.lines.extend(vec![
-                    Line::raw("The file has been modified."),
-                    Line::raw("Do you want to save before quitting?"),
-                    choice.to_line(&self.settings.color),
+                    editable_string.left_aligned(),
+                    Self::get_value_preview(&self.settings.color, text, self.header.endianness())
+                        .left_aligned(),
                 ]);
             }
-            Some(PopupState::Help(scroll)) => {
+            Some(PopupState::AddField { text, cursor }) => {
+                *popup_title = "Add Field".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    text,
+                    *cursor,
+                    "Name: Type",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::Fields(scroll)) => {
+                *popup_title = "Fields".into();
                 let max_lines = self.get_scrollable_popup_line_count();
                 *height = max_lines + 4;
-                *popup_title = "Help".into();
-                if *scroll > 0 {
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(self.fields.len().saturating_sub(max_lines));
+                if skip > 0 {
                     popup_text.lines.push(Line::from(vec![Span::styled(
                         "▲",
                         self.settings.color.menu_text,
@@ -813,13 +1196,31 @@ impl App {
                     popup_text.lines.push(Line::raw(""));
                 }
                 popup_text.lines.extend(
-                    self.help_list
+                    self.fields
                         .iter()
-                        .skip(*scroll)
+                        .enumerate()
+                        .skip(skip)
                         .take(max_lines)
-                        .map(|h| h.to_line(&self.settings.color)),
+                        .map(|(i, field)| {
+                            let style = if i == *scroll {
+                                self.settings.color.menu_text_selected
+                            } else {
+                                self.settings.color.menu_text
+                            };
+                            Line::styled(
+                                format!(
+                                    "{:X} {}: {} = {}",
+                                    field.offset,
+                                    field.name,
+                                    field.field_type,
+                                    self.decode_field(field).unwrap_or_default()
+                                ),
+                                style,
+                            )
+                            .left_aligned()
+                        }),
                 );
-                if self.help_list.len() as isize - *scroll as isize > max_lines as isize {
+                if self.fields.len() as isize - skip as isize > max_lines as isize {
                     popup_text.lines.push(Line::from(vec![Span::styled(
                         "▼",
                         self.settings.color.menu_text,
@@ -828,6 +1229,473 @@ impl App {
                     popup_text.lines.push(Line::raw(""));
                 }
             }
+            Some(PopupState::History(scroll)) => {
+                *popup_title = "History".into();
+                let entries = self.data.history().changes().len() + 1;
+                let current = self.data.history().current();
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 4;
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(entries.saturating_sub(max_lines));
+                if skip > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                popup_text
+                    .lines
+                    .extend((0..entries).skip(skip).take(max_lines).map(|i| {
+                        let style = if i == *scroll {
+                            self.settings.color.menu_text_selected
+                        } else if i > current {
+                            self.settings.color.placeholder
+                        } else {
+                            self.settings.color.menu_text
+                        };
+                        let marker = if i == current { "* " } else { "  " };
+                        Line::styled(format!("{}{}", marker, self.get_history_entry(i)), style)
+                            .left_aligned()
+                    }));
+                if entries as isize - skip as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
+            Some(PopupState::Pointers { pointers, scroll }) => {
+                *popup_title = "Pointers".into();
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 4;
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(pointers.len().saturating_sub(max_lines));
+                if skip > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                popup_text.lines.extend(
+                    pointers
+                        .iter()
+                        .enumerate()
+                        .skip(skip)
+                        .take(max_lines)
+                        .map(|(i, pointer)| {
+                            let style = if i == *scroll {
+                                self.settings.color.menu_text_selected
+                            } else {
+                                self.settings.color.menu_text
+                            };
+                            let target = match pointer.target {
+                                Some(target) => format!("{:X}", target),
+                                None => "invalid".into(),
+                            };
+                            Line::styled(
+                                format!("{:X}: {:X} -> {}", pointer.offset, pointer.value, target),
+                                style,
+                            )
+                            .left_aligned()
+                        }),
+                );
+                if pointers.len() as isize - skip as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
+            Some(PopupState::Strings { strings, scroll }) => {
+                *popup_title = "Strings".into();
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 4;
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(strings.len().saturating_sub(max_lines));
+                if skip > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                popup_text
+                    .lines
+                    .extend(strings.iter().enumerate().skip(skip).take(max_lines).map(
+                        |(i, string)| {
+                            let style = if i == *scroll {
+                                self.settings.color.menu_text_selected
+                            } else {
+                                self.settings.color.menu_text
+                            };
+                            Line::styled(format!("{:X}: {}", string.offset, string.text), style)
+                                .left_aligned()
+                        },
+                    ));
+                if strings.len() as isize - skip as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
+            Some(PopupState::RecentDirs { dirs, scroll }) => {
+                *popup_title = "Recent Directories".into();
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 4;
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(dirs.len().saturating_sub(max_lines));
+                if skip > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                popup_text
+                    .lines
+                    .extend(
+                        dirs.iter()
+                            .enumerate()
+                            .skip(skip)
+                            .take(max_lines)
+                            .map(|(i, dir)| {
+                                let style = if i == *scroll {
+                                    self.settings.color.menu_text_selected
+                                } else {
+                                    self.settings.color.menu_text
+                                };
+                                Line::styled(dir.clone(), style).left_aligned()
+                            }),
+                    );
+                if dirs.len() as isize - skip as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
+            Some(PopupState::Profiles { profiles, scroll }) => {
+                *popup_title = "Profiles".into();
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 4;
+                let skip = 0.max(*scroll as isize - max_lines as isize / 2) as usize;
+                let skip = skip.min(profiles.len().saturating_sub(max_lines));
+                if skip > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                popup_text.lines.extend(
+                    profiles
+                        .iter()
+                        .enumerate()
+                        .skip(skip)
+                        .take(max_lines)
+                        .map(|(i, profile)| {
+                            let style = if i == *scroll {
+                                self.settings.color.menu_text_selected
+                            } else {
+                                self.settings.color.menu_text
+                            };
+                            Line::styled(profile.clone(), style).left_aligned()
+                        }),
+                );
+                if profiles.len() as isize - skip as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
+            Some(PopupState::SaveAs { path, cursor }) => {
+                *popup_title = "Save As".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::ExtractAs { path, cursor }) => {
+                *popup_title = "Extract Selection".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::ExportHexdump { path, cursor }) => {
+                *popup_title = "Export Hex Dump".into();
+                let available_width = width.saturating_sub(2);
+                *height = 4;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text.lines.extend(vec![
+                    editable_string.left_aligned(),
+                    Line::styled(
+                        "Files ending with .html are exported as HTML",
+                        self.settings.color.placeholder,
+                    ),
+                ]);
+            }
+            Some(PopupState::ExportStrings { path, cursor }) => {
+                *popup_title = "Export Strings".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::CompareWith { path, cursor }) => {
+                *popup_title = "Compare With".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::SavePatch { path, cursor }) => {
+                *popup_title = "Save Patch".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::ApplyPatch { path, cursor }) => {
+                *popup_title = "Apply Patch".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::OpenPane { path, cursor }) => {
+                *popup_title = "Open In Second Pane".into();
+                let available_width = width.saturating_sub(2);
+                *height = 3;
+                let editable_string = Self::get_line_from_string_and_cursor(
+                    &self.settings.color,
+                    path,
+                    *cursor,
+                    "Path",
+                    available_width,
+                    true,
+                );
+                popup_text
+                    .lines
+                    .extend(vec![editable_string.left_aligned()]);
+            }
+            Some(PopupState::Save(choice)) => {
+                *popup_title = "Save".into();
+                *height = 6;
+                popup_text.lines.extend(vec![
+                    Line::raw("The file will be saved."),
+                    self.get_save_summary(),
+                    Line::raw("Are you sure?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::OpenLargeFile { path, size, choice }) => {
+                *popup_title = "Large File".into();
+                popup_text.lines.extend(vec![
+                    Line::raw(path::filename(path).unwrap_or(path).to_string()),
+                    Line::raw(if *size >= 1024 * 1024 * 1024 {
+                        format!(
+                            "This file is {:.1} GiB, open anyway?",
+                            *size as f64 / (1024 * 1024 * 1024) as f64
+                        )
+                    } else {
+                        format!(
+                            "This file is {} MiB, open anyway?",
+                            size.div_ceil(1024 * 1024)
+                        )
+                    }),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::ReadTimedOut { path, choice }) => {
+                *popup_title = "Read Timed Out".into();
+                popup_text.lines.extend(vec![
+                    Line::raw(path::filename(path).unwrap_or(path).to_string()),
+                    Line::raw("Reading the file timed out, keep waiting?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::PreviewPatch {
+                path: _,
+                patch,
+                choice,
+            }) => {
+                *popup_title = "Apply Patch".into();
+                let ranges = patch.ranges(App::PATCH_PREVIEW_RANGES);
+                *height = 5 + ranges.len();
+                popup_text.lines.push(Line::raw(format!(
+                    "{} bytes in {} regions will change:",
+                    patch.changed_bytes(),
+                    patch.entries.len()
+                )));
+                popup_text.lines.extend(ranges.into_iter().map(Line::raw));
+                popup_text.lines.extend(vec![
+                    Line::raw("Do you want to apply it?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::Revert(choice)) => {
+                *popup_title = "Revert".into();
+                popup_text.lines.extend(vec![
+                    Line::raw("All the changes will be discarded."),
+                    Line::raw("Are you sure?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::DuplicateSelection(choice)) => {
+                *popup_title = "Duplicate".into();
+                let size = self
+                    .get_selection()
+                    .map(|(start, end)| end - start)
+                    .unwrap_or_default();
+                popup_text.lines.extend(vec![
+                    Line::raw(format!(
+                        "{} MiB will be inserted.",
+                        size.div_ceil(1024 * 1024)
+                    )),
+                    Line::raw("Are you sure?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::QuitDirtySave(choice)) => {
+                *popup_title = "Quit".into();
+                popup_text.lines.extend(vec![
+                    Line::raw("The file has been modified."),
+                    Line::raw("Do you want to save before quitting?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::QuitPluginData { messages, choice }) => {
+                *popup_title = "Quit".into();
+                *height = 5 + messages.len();
+                popup_text
+                    .lines
+                    .push(Line::raw("Some plugins have unsaved data:"));
+                popup_text
+                    .lines
+                    .extend(messages.iter().map(|message| Line::raw(message.clone())));
+                popup_text.lines.extend(vec![
+                    Line::raw("Do you want to quit anyway?"),
+                    choice.to_line(&self.settings.color),
+                ]);
+            }
+            Some(PopupState::Help(scroll)) => {
+                let max_lines = self.get_scrollable_popup_line_count();
+                *height = max_lines + 4;
+                *popup_title = "Help".into();
+                if *scroll > 0 {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▲",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+                popup_text.lines.extend(
+                    self.help_list
+                        .iter()
+                        .skip(*scroll)
+                        .take(max_lines)
+                        .map(|h| h.to_line(&self.settings.color)),
+                );
+                if self.help_list.len() as isize - *scroll as isize > max_lines as isize {
+                    popup_text.lines.push(Line::from(vec![Span::styled(
+                        "▼",
+                        self.settings.color.menu_text,
+                    )]));
+                } else {
+                    popup_text.lines.push(Line::raw(""));
+                }
+            }
+            Some(PopupState::PluginDialog {
+                dialog, selected, ..
+            }) => {
+                *popup_title = dialog.title.clone();
+                *height = dialog.height();
+                popup_text
+                    .lines
+                    .extend(dialog.to_lines(*selected, &self.settings.color));
+            }
             Some(PopupState::Custom {
                 plugin_index,
                 callback,
@@ -849,6 +1717,20 @@ impl App {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_select_button() {
+        let mut popup = PopupState::QuitDirtySave(SimpleChoice::Yes);
+        assert!(popup.select_button(false));
+        assert!(matches!(
+            popup,
+            PopupState::QuitDirtySave(SimpleChoice::Cancel)
+        ));
+        let mut popup = PopupState::Save(BinaryChoice::Yes);
+        assert!(popup.select_button(true));
+        assert!(matches!(popup, PopupState::Save(BinaryChoice::No)));
+        assert!(!PopupState::Log(0).select_button(true));
+    }
+
     #[test]
     fn test_get_line_from_string_and_cursor() {
         let color_settings = ColorSettings::get_default_dark_theme();
@@ -0,0 +1,245 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use ratatui::style::Style;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    info_mode::InfoMode, log::NotificationLevel, popup::popup_state::PopupState,
+    settings::app_settings::AppSettings, App,
+};
+
+/// A named set of preferences that can be applied at once,
+/// the key bindings are not part of a profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub app: AppSettings,
+    #[serde(default)]
+    pub color: HashMap<String, Style>,
+    #[serde(default)]
+    pub info_mode: Option<InfoMode>,
+}
+
+/// The saved profiles, sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profiles {
+    profiles: BTreeMap<String, Profile>,
+}
+
+impl Profiles {
+    /// Reads the profiles from `path`, a missing file means there are no profiles yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Could not read the profiles: {}", e)),
+        };
+        serde_json::from_str(&text).map_err(|e| format!("Could not parse the profiles: {}", e))
+    }
+
+    pub fn save(&self, path: &Path) -> Option<()> {
+        let profiles = serde_json::to_string_pretty(self).ok()?;
+        std::fs::create_dir_all(path.parent()?).ok()?;
+        std::fs::write(path, profiles).ok()?;
+        Some(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    /// Stores the profile under `name`, replacing the one with the same name.
+    pub fn set(&mut self, name: &str, profile: Profile) {
+        self.profiles.insert(name.to_string(), profile);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    pub fn get_default_path() -> Option<PathBuf> {
+        let config = dirs::config_dir()?;
+        Some(config.join("HexPatch").join("profiles.json"))
+    }
+}
+
+impl App {
+    /// Saves the app settings, the colors and the current view as a profile named `name`.
+    pub(super) fn save_profile(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.log(NotificationLevel::Warning, "The profile needs a name.");
+            return;
+        }
+        let Some(path) = self.profiles_path.clone() else {
+            self.log(NotificationLevel::Error, "Could not get the profiles path");
+            return;
+        };
+        let color =
+            match serde_json::to_value(&self.settings.color).and_then(serde_json::from_value) {
+                Ok(color) => color,
+                Err(e) => {
+                    self.log(
+                        NotificationLevel::Error,
+                        &format!("Could not save the colors: {}", e),
+                    );
+                    return;
+                }
+            };
+        let profile = Profile {
+            app: self.settings.app.clone(),
+            color,
+            info_mode: Some(self.info_mode),
+        };
+        let mut profiles = match Profiles::load(&path) {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return;
+            }
+        };
+        profiles.set(name, profile);
+        if profiles.save(&path).is_none() {
+            self.log(NotificationLevel::Error, "Could not save the profile");
+        } else {
+            self.log(
+                NotificationLevel::Info,
+                &format!("Saved the profile \"{}\"", name),
+            );
+        }
+    }
+
+    /// Replaces the app settings and the colors with the ones of the profile,
+    /// then lays out the views again.
+    pub(super) fn apply_profile(&mut self, name: &str) {
+        let Some(profiles) = self.load_profiles() else {
+            return;
+        };
+        let Some(profile) = profiles.get(name).cloned() else {
+            self.log(
+                NotificationLevel::Warning,
+                &format!("The profile \"{}\" does not exist.", name),
+            );
+            return;
+        };
+        let mut color = self.settings.color.clone();
+        if let Err(e) = color.edit_color_settings(&profile.color) {
+            self.log(
+                NotificationLevel::Warning,
+                &format!("The colors of the profile were not applied: {}", e),
+            );
+        } else {
+            self.settings.color = color;
+        }
+        self.settings.app = profile.app;
+        self.logger.change_limit(self.settings.app.log_limit);
+        self.logger.change_verbosity(self.settings.app.log_level);
+
+        let available_info_modes = self.get_available_info_modes();
+        match profile.info_mode {
+            Some(info_mode) if available_info_modes.contains(&info_mode) => {
+                self.set_info_mode(info_mode)
+            }
+            _ if !available_info_modes.contains(&self.info_mode) => {
+                self.set_info_mode(InfoMode::Text)
+            }
+            _ => {}
+        }
+        self.resize(self.get_blocks_per_row(self.screen_size.0));
+        self.log(
+            NotificationLevel::Info,
+            &format!("Applied the profile \"{}\"", name),
+        );
+    }
+
+    /// Loads the saved profiles, logging why they could not be read.
+    fn load_profiles(&mut self) -> Option<Profiles> {
+        let Some(path) = self.profiles_path.as_deref() else {
+            self.log(NotificationLevel::Error, "Could not get the profiles path");
+            return None;
+        };
+        match Profiles::load(path) {
+            Ok(profiles) => Some(profiles),
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                None
+            }
+        }
+    }
+
+    pub(super) fn request_popup_save_profile(&mut self) {
+        self.popup = Some(PopupState::SaveProfile {
+            text: String::new(),
+            cursor: 0,
+        });
+    }
+
+    pub(super) fn request_popup_profiles(&mut self) {
+        let Some(profiles) = self.load_profiles() else {
+            return;
+        };
+        let profiles = profiles.names();
+        if profiles.is_empty() {
+            self.log(
+                NotificationLevel::Warning,
+                "No profiles, save one with the \"saveprofile\" command.",
+            );
+            return;
+        }
+        self.popup = Some(PopupState::Profiles {
+            profiles,
+            scroll: 0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::style::Color;
+
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.profiles_path = Some(dir.path().join("profiles.json"));
+        app.resize_to_size(120, 24);
+        app.request_popup_profiles();
+        assert!(app.popup.is_none());
+
+        app.settings.app.bytes_per_row = 8;
+        app.settings.color.hex_null = Style::default().fg(Color::Red);
+        app.save_profile("firmware");
+        app.save_profile(" ");
+
+        app.settings.app.bytes_per_row = 16;
+        app.settings.color.hex_null = Style::default();
+        app.resize(app.get_blocks_per_row(app.screen_size.0));
+        assert_eq!(app.blocks_per_row, 2);
+
+        app.request_popup_profiles();
+        assert!(matches!(
+            app.popup,
+            Some(PopupState::Profiles { ref profiles, scroll: 0 }) if profiles == &["firmware"]
+        ));
+        app.apply_profile("firmware");
+        assert_eq!(app.settings.app.bytes_per_row, 8);
+        assert_eq!(app.blocks_per_row, 1);
+        assert_eq!(app.settings.color.hex_null, Style::default().fg(Color::Red));
+
+        app.apply_profile("missing");
+        assert_eq!(app.settings.app.bytes_per_row, 8);
+
+        let path = dir.path().join("profiles.json");
+        std::fs::write(&path, "{ broken").unwrap();
+        app.popup = None;
+        app.save_profile("other");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ broken");
+        app.request_popup_profiles();
+        assert!(app.popup.is_none());
+        assert!(Profiles::load(&path).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use super::{log::NotificationLevel, App};
+
+impl App {
+    pub const RECENT_OFFSETS_LIMIT: usize = 64;
+
+    /// Jumps like [`App::jump_to`] and remembers both the offset the cursor left and the one it
+    /// landed on, like the history of a browser, the offsets after the current one are dropped.
+    pub(super) fn jump_to_remembered(&mut self, address: usize, is_virtual: bool) {
+        let from = self.get_cursor_position().global_byte_index;
+        self.jump_to(address, is_virtual);
+        let to = self.get_cursor_position().global_byte_index;
+        if from != to {
+            self.push_recent_offset(from);
+            self.push_recent_offset(to);
+        }
+    }
+
+    fn push_recent_offset(&mut self, offset: usize) {
+        self.recent_offsets
+            .truncate((self.recent_offset_index + 1).min(self.recent_offsets.len()));
+        if self.recent_offsets.last() != Some(&offset) {
+            self.recent_offsets.push(offset);
+        }
+        if self.recent_offsets.len() > Self::RECENT_OFFSETS_LIMIT {
+            let excess = self.recent_offsets.len() - Self::RECENT_OFFSETS_LIMIT;
+            self.recent_offsets.drain(..excess);
+        }
+        self.recent_offset_index = self.recent_offsets.len() - 1;
+    }
+
+    /// Moves through the recently visited offsets, backward if `forward` is false.
+    pub(super) fn move_in_recent_offsets(&mut self, forward: bool) {
+        let index = if forward {
+            self.recent_offset_index + 1
+        } else {
+            self.recent_offset_index.wrapping_sub(1)
+        };
+        match self.recent_offsets.get(index) {
+            Some(&offset) => {
+                self.recent_offset_index = index;
+                self.jump_to(offset.min(self.data.len().saturating_sub(1)), false);
+            }
+            None if forward => self.log(
+                NotificationLevel::Warning,
+                "There is no next offset in the history.",
+            ),
+            None => self.log(
+                NotificationLevel::Warning,
+                "There is no previous offset in the history.",
+            ),
+        }
+    }
+
+    pub(super) fn clear_recent_offsets(&mut self) {
+        self.recent_offsets.clear();
+        self.recent_offset_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_recent_offsets() {
+        let mut app = App::mockup(vec![0; 0x100]);
+        app.move_in_recent_offsets(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+
+        app.jump_to_remembered(0x10, false);
+        app.jump_to_remembered(0x10, false);
+        app.jump_to_remembered(0x20, false);
+        assert_eq!(app.recent_offsets, vec![0, 0x10, 0x20]);
+        assert_eq!(app.recent_offset_index, 2);
+
+        app.move_in_recent_offsets(false);
+        app.move_in_recent_offsets(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+        app.move_in_recent_offsets(false);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+        app.move_in_recent_offsets(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+
+        app.jump_to_remembered(0x30, false);
+        assert_eq!(app.recent_offsets, vec![0, 0x10, 0x30]);
+        app.move_in_recent_offsets(true);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x30);
+
+        for offset in 0..App::RECENT_OFFSETS_LIMIT {
+            app.jump_to_remembered(0x40 + offset, false);
+        }
+        assert_eq!(app.recent_offsets.len(), App::RECENT_OFFSETS_LIMIT);
+        assert_eq!(app.recent_offset_index, App::RECENT_OFFSETS_LIMIT - 1);
+    }
+}
@@ -0,0 +1,163 @@
+use super::{log::NotificationLevel, App};
+
+/// The bytes looked for by the last text or hex search and its direction,
+/// the search is repeated with the `find_next` and `find_prev` keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub bytes: Vec<u8>,
+    pub forward: bool,
+}
+
+impl App {
+    pub(super) fn set_last_search(&mut self, bytes: &[u8], forward: bool) {
+        self.last_search = Some(SearchQuery {
+            bytes: bytes.to_vec(),
+            forward,
+        });
+    }
+
+    /// Jumps to the next occurrence of the bytes, starting from the cursor and wrapping around
+    /// the end of the file, and remembers them as the last search. An occurrence at the cursor
+    /// is skipped so that searching again finds the next one.
+    /// `what` names the bytes in the log.
+    pub(super) fn find_bytes(&mut self, pattern: &[u8], what: &str) {
+        self.set_last_search(pattern, true);
+        let bytes = self.data.bytes();
+        if pattern.is_empty() || bytes.is_empty() {
+            return;
+        }
+        let cursor = self.get_cursor_position().global_byte_index;
+        let start = if bytes
+            .get(cursor..)
+            .is_some_and(|here| here.starts_with(pattern))
+        {
+            cursor + 1
+        } else {
+            cursor
+        };
+        let found = (0..bytes.len())
+            .map(|i| (start + i) % bytes.len())
+            .find(|&here| bytes[here..].starts_with(pattern));
+        match found {
+            Some(here) => {
+                self.jump_to_remembered(here, false);
+                let message = if here < start {
+                    format!("{} found at {:X}, the search wrapped around", what, here)
+                } else {
+                    format!("{} found at {:X}", what, here)
+                };
+                self.log(NotificationLevel::Info, &message);
+            }
+            None => self.log(NotificationLevel::Warning, &format!("{} not found", what)),
+        }
+    }
+
+    /// The offsets of every occurrence of the bytes, occurrences that overlap are all included.
+    fn find_all(&self, pattern: &[u8]) -> Vec<usize> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        self.data
+            .bytes()
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, window)| *window == pattern)
+            .map(|(offset, _)| offset)
+            .collect()
+    }
+
+    /// Jumps to the first occurrence of the last search after the cursor,
+    /// or to the last one before it if `forward` is false, wrapping around the end of the file.
+    pub(super) fn repeat_search(&mut self, forward: bool) {
+        let Some(query) = &self.last_search else {
+            self.log(NotificationLevel::Warning, "There is no previous search.");
+            return;
+        };
+        let matches = self.find_all(&query.bytes);
+        if matches.is_empty() {
+            self.log(
+                NotificationLevel::Warning,
+                "There are no matches for the last search.",
+            );
+            return;
+        }
+        let cursor = self.get_cursor_position().global_byte_index;
+        let index = if forward {
+            matches.partition_point(|&offset| offset <= cursor) % matches.len()
+        } else {
+            matches
+                .partition_point(|&offset| offset < cursor)
+                .checked_sub(1)
+                .unwrap_or(matches.len() - 1)
+        };
+        let offset = matches[index];
+        self.jump_to_remembered(offset, false);
+        self.log(
+            NotificationLevel::Info,
+            &format!("Match {}/{} at {:X}", index + 1, matches.len(), offset),
+        );
+    }
+
+    /// Repeats the last search in its direction.
+    pub(super) fn find_next(&mut self) {
+        let forward = self.last_search.as_ref().is_none_or(|query| query.forward);
+        self.repeat_search(forward);
+    }
+
+    /// Repeats the last search in the opposite direction.
+    pub(super) fn find_prev(&mut self) {
+        let forward = self.last_search.as_ref().is_none_or(|query| query.forward);
+        self.repeat_search(!forward);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::text_encoding::TextEncoding;
+
+    use super::*;
+
+    #[test]
+    fn test_repeat_search() {
+        let mut data = vec![0; 0x40];
+        data[0x10..0x14].copy_from_slice(b"aaaa");
+        data[0x30..0x32].copy_from_slice(b"aa");
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.find_next();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0);
+
+        app.find_text("aa", TextEncoding::Utf8);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+        app.find_next();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x11);
+        app.find_next();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x12);
+        app.find_next();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x30);
+        app.find_next();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+        app.find_prev();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x30);
+        app.find_prev();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x12);
+
+        assert!(app.find_hex("ff"));
+        app.find_next();
+        app.find_prev();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x12);
+        assert_eq!(
+            app.last_search,
+            Some(SearchQuery {
+                bytes: vec![0xFF],
+                forward: true
+            })
+        );
+
+        app.set_last_search(b"aa", false);
+        app.find_next();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x11);
+        app.find_prev();
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x12);
+    }
+}
@@ -0,0 +1,421 @@
+use crate::get_app_context;
+
+use super::{
+    log::NotificationLevel,
+    popup::{binary_choice::BinaryChoice, popup_state::PopupState},
+    App,
+};
+
+impl App {
+    /// Returns the selected range as (start, end), start is included, end is excluded.
+    pub(super) fn get_selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection_anchor?;
+        if self.data.is_empty() {
+            return None;
+        }
+        let current = self.get_cursor_position().global_byte_index;
+        let anchor = anchor.min(self.data.len() - 1);
+        let current = current.min(self.data.len() - 1);
+        Some((anchor.min(current), anchor.max(current) + 1))
+    }
+
+    pub(super) fn get_selection_bytes(&self) -> Option<&[u8]> {
+        self.get_selection()
+            .map(|(start, end)| &self.data.bytes()[start..end])
+    }
+
+    pub(super) fn toggle_selection(&mut self) {
+        if self.selection_anchor.is_some() {
+            self.clear_selection();
+        } else {
+            self.selection_anchor = Some(self.get_cursor_position().global_byte_index);
+            self.shift_selection = false;
+            self.log(NotificationLevel::Debug, "Selection started");
+        }
+    }
+
+    /// Moves the cursor extending the selection, if nothing is selected
+    /// a selection is started at the cursor.
+    pub(super) fn move_cursor_selecting(&mut self, dx: isize, dy: isize) {
+        if self.selection_anchor.is_none() {
+            self.selection_anchor = Some(self.get_cursor_position().global_byte_index);
+            self.shift_selection = true;
+        }
+        self.move_cursor(dx, dy, false);
+    }
+
+    /// Moves the cursor without Shift, a selection started with Shift is cleared
+    /// unless the `keep_shift_selection` setting is on.
+    pub(super) fn move_cursor_not_selecting(&mut self, dx: isize, dy: isize) {
+        if self.shift_selection {
+            self.shift_selection = false;
+            if !self.settings.app.keep_shift_selection {
+                self.clear_selection();
+            }
+        }
+        self.move_cursor(dx, dy, false);
+    }
+
+    /// Moves the cursor to the anchor of the selection and the anchor to where the cursor was,
+    /// the selected range does not change but it can be extended from the other end.
+    pub(super) fn swap_selection_anchor(&mut self) {
+        if let Some(anchor) = self.selection_anchor {
+            self.selection_anchor = Some(self.get_cursor_position().global_byte_index);
+            self.jump_to(anchor, false);
+        } else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+        }
+    }
+
+    /// Selects the next occurrence of the selected bytes, the search wraps around
+    /// the end of the file.
+    pub(super) fn find_selection(&mut self) {
+        let Some((start, end)) = self.get_selection() else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+            return;
+        };
+        let bytes = self.data.bytes();
+        let pattern = &bytes[start..end];
+        let found = (1..bytes.len())
+            .map(|i| (start + i) % bytes.len())
+            .find(|&here| bytes[here..].starts_with(pattern));
+        match found {
+            Some(here) => {
+                self.selection_anchor = Some(here);
+                self.jump_to_remembered(here + end - start - 1, false);
+            }
+            None => {
+                self.log(
+                    NotificationLevel::Warning,
+                    "The selected bytes are not found anywhere else.",
+                );
+            }
+        }
+    }
+
+    /// Inserts a copy of the selected bytes right after the selection and selects the copy,
+    /// the bytes after the selection are shifted. The insertion is a single change in the undo history.
+    pub(in crate::app) fn duplicate_selection(&mut self) {
+        let Some((start, end)) = self.get_selection() else {
+            self.log(NotificationLevel::Warning, "Nothing is selected.");
+            return;
+        };
+        if self.data.read_only() {
+            self.log(NotificationLevel::Warning, "The file is read-only.");
+            return;
+        }
+        let mut bytes = self.data.bytes()[start..end].to_vec();
+        let mut app_context = get_app_context!(self);
+        app_context.offset = end;
+        self.plugin_manager.on_edit(&mut bytes, &mut app_context);
+        let inserted = self.data.insert(end, bytes);
+        if inserted == 0 {
+            return;
+        }
+        self.refresh_after_insertion();
+        self.selection_anchor = Some(end);
+        self.jump_to(end + inserted - 1, false);
+        self.log(
+            NotificationLevel::Info,
+            &format!("Inserted {} bytes at {:X}", inserted, end),
+        );
+    }
+
+    /// Asks for confirmation before duplicating a selection larger than the `large_file_size` setting.
+    pub(in crate::app) fn request_duplicate_selection(&mut self) {
+        let large_size = self
+            .settings
+            .app
+            .large_file_size
+            .saturating_mul(1024 * 1024);
+        match self.get_selection() {
+            Some((start, end)) if large_size != 0 && (end - start) as u64 > large_size => {
+                self.popup = Some(PopupState::DuplicateSelection(BinaryChoice::No));
+            }
+            _ => self.duplicate_selection(),
+        }
+    }
+
+    /// Moves an offset after bytes were inserted or removed, see `shift_annotations`.
+    /// Returns None if the byte at `offset` was removed.
+    fn shift_offset(offset: usize, at: usize, delta: isize) -> Option<usize> {
+        if offset < at {
+            Some(offset)
+        } else if delta < 0 && offset < at + delta.unsigned_abs() {
+            None
+        } else {
+            offset.checked_add_signed(delta)
+        }
+    }
+
+    /// Moves a range after bytes were inserted or removed, see `shift_annotations`.
+    /// The range grows if bytes are inserted in it and shrinks if some of its bytes are removed,
+    /// returns None if all of them were removed.
+    fn shift_range(start: usize, end: usize, at: usize, delta: isize) -> Option<(usize, usize)> {
+        let shift = |offset: usize| {
+            if offset < at {
+                offset
+            } else {
+                offset.saturating_add_signed(delta).max(at)
+            }
+        };
+        let (start, end) = (shift(start), shift(end));
+        (start < end).then_some((start, end))
+    }
+
+    /// Moves the pinned byte, the mark, the compared bytes, the fields, the folds and the color tags
+    /// after `delta` bytes were inserted at `at`, or removed from `at` if `delta` is negative.
+    /// A field is removed if any of its bytes is removed or if bytes are inserted in it.
+    fn shift_annotations(&mut self, at: usize, delta: isize) {
+        self.pinned = self
+            .pinned
+            .and_then(|pinned| Self::shift_offset(pinned, at, delta));
+        self.mark = self
+            .mark
+            .and_then(|mark| Self::shift_offset(mark, at, delta));
+        self.compare_range = self
+            .compare_range
+            .and_then(|(start, end)| Self::shift_range(start, end, at, delta));
+        self.selection_differences = self
+            .selection_differences
+            .iter()
+            .filter_map(|&offset| Self::shift_offset(offset, at, delta))
+            .collect();
+        self.fields.retain_mut(|field| {
+            match Self::shift_range(field.offset, field.end(), at, delta) {
+                Some((start, end)) if end - start == field.size => {
+                    field.offset = start;
+                    true
+                }
+                _ => false,
+            }
+        });
+        self.assembly_folds.retain_mut(|fold| {
+            match Self::shift_range(fold.start, fold.end, at, delta) {
+                Some((start, end)) => {
+                    (fold.start, fold.end) = (start, end);
+                    true
+                }
+                None => false,
+            }
+        });
+        self.color_tags.retain_mut(
+            |tag| match Self::shift_range(tag.start, tag.end, at, delta) {
+                Some((start, end)) => {
+                    (tag.start, tag.end) = (start, end);
+                    true
+                }
+                None => false,
+            },
+        );
+    }
+
+    /// Parses the file again after bytes were inserted or removed,
+    /// everything that depends on the offsets of the bytes is moved or rebuilt.
+    pub(in crate::app) fn refresh_after_insertion(&mut self) {
+        for (at, delta) in self.data.take_shifts() {
+            self.shift_annotations(at, delta);
+        }
+        self.header = self.parse_header();
+        self.disassemble();
+        if !self.data.is_empty() {
+            let cursor = self.get_cursor_position().global_byte_index;
+            self.jump_to(cursor.min(self.data.len() - 1), false);
+        }
+    }
+
+    pub(super) fn clear_selection(&mut self) {
+        self.selection_anchor = None;
+        self.shift_selection = false;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_selection() {
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        assert_eq!(app.get_selection(), None);
+        app.jump_to(4, false);
+        app.toggle_selection();
+        assert_eq!(app.get_selection(), Some((4, 5)));
+        app.jump_to(8, false);
+        assert_eq!(app.get_selection(), Some((4, 9)));
+        assert_eq!(app.get_selection_bytes(), Some(&[4, 5, 6, 7, 8][..]));
+        app.jump_to(2, false);
+        assert_eq!(app.get_selection(), Some((2, 5)));
+        app.toggle_selection();
+        assert_eq!(app.get_selection(), None);
+        assert_eq!(app.get_selection_bytes(), None);
+    }
+
+    #[test]
+    fn test_shift_selection() {
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        // the cursor moves by half bytes, every step is a whole byte
+        app.move_cursor_selecting(2, 0);
+        app.move_cursor_selecting(2, 0);
+        assert_eq!(app.get_selection(), Some((0, 3)));
+        app.move_cursor_not_selecting(2, 0);
+        assert_eq!(app.get_selection(), None);
+        assert_eq!(app.get_cursor_position().global_byte_index, 3);
+
+        app.settings.app.keep_shift_selection = true;
+        app.move_cursor_selecting(-2, 0);
+        app.move_cursor_not_selecting(-2, 0);
+        assert_eq!(app.get_selection(), Some((1, 4)));
+
+        app.clear_selection();
+        app.toggle_selection();
+        app.settings.app.keep_shift_selection = false;
+        app.move_cursor_not_selecting(2, 0);
+        assert_eq!(app.get_selection(), Some((1, 3)));
+    }
+
+    #[test]
+    fn test_swap_selection_anchor() {
+        let mut app = App::mockup((0..0x40).collect());
+        app.resize_to_size(80, 24);
+        app.jump_to(4, false);
+        app.toggle_selection();
+        app.jump_to(8, false);
+        app.swap_selection_anchor();
+        assert_eq!(app.get_cursor_position().global_byte_index, 4);
+        assert_eq!(app.get_selection(), Some((4, 9)));
+        app.jump_to(2, false);
+        assert_eq!(app.get_selection(), Some((2, 9)));
+        app.swap_selection_anchor();
+        assert_eq!(app.get_cursor_position().global_byte_index, 8);
+        app.jump_to(12, false);
+        assert_eq!(app.get_selection(), Some((2, 13)));
+    }
+
+    #[test]
+    fn test_find_selection() {
+        let mut data = vec![0; 0x40];
+        data[0x10..0x13].copy_from_slice(&[1, 2, 3]);
+        data[0x30..0x33].copy_from_slice(&[1, 2, 3]);
+        data[0x3E..0x40].copy_from_slice(&[1, 2]);
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        app.jump_to(0x10, false);
+        app.toggle_selection();
+        app.jump_to(0x12, false);
+        app.find_selection();
+        assert_eq!(app.get_selection(), Some((0x30, 0x33)));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x32);
+        // the partial match at the end of the file is skipped and the search wraps around
+        app.find_selection();
+        assert_eq!(app.get_selection(), Some((0x10, 0x13)));
+
+        app.jump_to(0x11, false);
+        app.find_selection();
+        assert_eq!(app.get_selection(), Some((0x30, 0x32)));
+        app.find_selection();
+        assert_eq!(app.get_selection(), Some((0x3E, 0x40)));
+        app.find_selection();
+        assert_eq!(app.get_selection(), Some((0x10, 0x12)));
+    }
+
+    #[test]
+    fn test_duplicate_selection() {
+        let mut app = App::mockup((0..0x10).collect());
+        app.resize_to_size(80, 24);
+        app.jump_to(2, false);
+        app.toggle_selection();
+        app.jump_to(4, false);
+        app.duplicate_selection();
+        assert_eq!(app.data.len(), 0x13);
+        assert_eq!(&app.data.bytes()[..9], &[0, 1, 2, 3, 4, 2, 3, 4, 5]);
+        assert_eq!(app.get_selection(), Some((5, 8)));
+        assert!(app.save_patch("unused").is_err());
+
+        app.undo();
+        assert_eq!(app.data.bytes(), (0..0x10).collect::<Vec<u8>>());
+        app.redo();
+        assert_eq!(app.data.len(), 0x13);
+
+        app.selection_anchor = Some(5);
+        app.jump_to(7, false);
+        app.settings.app.large_file_size = 0;
+        app.request_duplicate_selection();
+        assert_eq!(app.data.len(), 0x16);
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn test_shift_annotations() {
+        use crate::app::{
+            asm::fold::Fold,
+            color_tags::ColorTag,
+            fields::{Field, FieldType},
+        };
+        use ratatui::style::Style;
+
+        let mut app = App::mockup((0..0x20).collect());
+        app.resize_to_size(80, 24);
+        app.pinned = Some(0x10);
+        app.mark = Some(0x14);
+        app.compare_range = Some((0x18, 0x1C));
+        app.selection_differences = vec![5, 0x19];
+        app.fields.push(Field {
+            name: "before".into(),
+            offset: 0,
+            size: 2,
+            field_type: FieldType::U16,
+        });
+        app.fields.push(Field {
+            name: "inside".into(),
+            offset: 4,
+            size: 4,
+            field_type: FieldType::U32,
+        });
+        app.fields.push(Field {
+            name: "after".into(),
+            offset: 0x10,
+            size: 1,
+            field_type: FieldType::U8,
+        });
+        app.assembly_folds.push(Fold { start: 4, end: 8 });
+        app.color_tags.push(ColorTag {
+            start: 8,
+            end: 0x10,
+            style: Style::default(),
+        });
+        app.jump_to(4, false);
+        app.toggle_selection();
+        app.jump_to(5, false);
+        app.duplicate_selection();
+        // two bytes inserted at 6
+        assert_eq!(app.pinned, Some(0x12));
+        assert_eq!(app.mark, Some(0x16));
+        assert_eq!(app.compare_range, Some((0x1A, 0x1E)));
+        assert_eq!(app.selection_differences, vec![5, 0x1B]);
+        let names = app
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["before", "after"]);
+        assert_eq!(app.fields[1].offset, 0x12);
+        assert_eq!(app.assembly_folds, vec![Fold { start: 4, end: 10 }]);
+        assert_eq!((app.color_tags[0].start, app.color_tags[0].end), (10, 0x12));
+
+        app.undo();
+        assert_eq!(app.pinned, Some(0x10));
+        assert_eq!(app.fields[1].offset, 0x10);
+        assert_eq!(app.assembly_folds, vec![Fold { start: 4, end: 8 }]);
+        assert_eq!((app.color_tags[0].start, app.color_tags[0].end), (8, 0x10));
+
+        app.shift_annotations(0xE, -4);
+        assert_eq!(app.pinned, None);
+        assert_eq!(app.compare_range, Some((0x14, 0x18)));
+        assert_eq!(app.selection_differences, vec![5, 0x15]);
+        assert_eq!(app.fields.len(), 1);
+        assert_eq!((app.color_tags[0].start, app.color_tags[0].end), (8, 0xE));
+    }
+}
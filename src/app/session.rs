@@ -0,0 +1,334 @@
+use std::{
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+};
+
+use ratatui::backend::Backend;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    asm::fold::Fold, fields::Field, files::filesystem::FileSystem, info_mode::InfoMode,
+    log::NotificationLevel, App,
+};
+
+/// The working context of a file, enough to reopen it exactly as it was left.
+/// Every field added after the first version must have a default,
+/// so that older sessions can still be loaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    #[serde(default)]
+    pub version: u32,
+    pub path: String,
+    pub offset: usize,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    /// Offset of the first byte shown in the hex view.
+    #[serde(default)]
+    pub scroll_offset: usize,
+    #[serde(default)]
+    pub selection_anchor: Option<usize>,
+    #[serde(default)]
+    pub info_mode: Option<InfoMode>,
+    #[serde(default)]
+    pub folds: Vec<Fold>,
+}
+
+impl Session {
+    /// The version of the sessions saved by this build, sessions without a version are version 0.
+    pub const VERSION: u32 = 1;
+
+    pub fn load(path: Option<&Path>) -> Result<Session, io::Error> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => Self::get_default_session_path()
+                .ok_or(io::Error::other("Could not get default session path"))?,
+        };
+
+        let session = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&session).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Could not parse session file: {}", e),
+            )
+        })
+    }
+
+    pub fn save(&self, path: Option<&Path>) -> Option<()> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => Self::get_default_session_path()?,
+        };
+
+        let session = serde_json::to_string_pretty(self).ok()?;
+        std::fs::create_dir_all(path.parent()?).ok()?;
+        std::fs::write(&path, session).ok()?;
+        Some(())
+    }
+
+    pub fn get_default_session_path() -> Option<PathBuf> {
+        let config = dirs::config_dir()?;
+        Some(config.join("HexPatch").join("session.json"))
+    }
+}
+
+impl App {
+    /// Returns the session of the currently open file,
+    /// only local files can be restored.
+    pub(super) fn get_session(&self) -> Option<Session> {
+        match &self.filesystem {
+            FileSystem::Local { path } if !self.data.is_empty() => Some(Session {
+                version: Session::VERSION,
+                path: path.clone(),
+                offset: self.get_cursor_position().global_byte_index,
+                fields: self.fields.clone(),
+                scroll_offset: self.scroll * self.block_size * self.blocks_per_row,
+                selection_anchor: self.selection_anchor,
+                info_mode: Some(self.info_mode),
+                folds: self.assembly_folds.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Saves the session of the open file, called when the file is closed
+    /// by opening another one or by quitting.
+    pub(super) fn save_session(&mut self) {
+        let Some(path) = self.session_path.clone() else {
+            return;
+        };
+        if let Some(session) = self.get_session() {
+            if session.save(Some(&path)).is_none() {
+                self.log(NotificationLevel::Warning, "Could not save the session");
+            }
+        }
+    }
+
+    pub(super) fn restore_session<B: Backend>(
+        &mut self,
+        session: Session,
+        terminal: &mut ratatui::Terminal<B>,
+    ) -> Result<(), Box<dyn Error>> {
+        if Path::new(&session.path).is_file() {
+            if session.version > Session::VERSION {
+                self.log(
+                    NotificationLevel::Warning,
+                    "The session was saved by a newer version, some of it may not be restored",
+                );
+            }
+            self.filesystem = FileSystem::new_local(&session.path)?;
+            self.open_file(&session.path, terminal)?;
+            let field_count = session.fields.len();
+            self.fields = session.fields;
+            self.fields.retain(|field| field.is_valid(self.data.len()));
+            if self.fields.len() < field_count {
+                self.log(
+                    NotificationLevel::Warning,
+                    &format!(
+                        "{} invalid fields of the session were not restored",
+                        field_count - self.fields.len()
+                    ),
+                );
+            }
+            self.assembly_folds = session.folds;
+            self.assembly_folds
+                .retain(|fold| fold.start < fold.end && fold.end <= self.data.len());
+            if let Some(info_mode) = session.info_mode {
+                if self.get_available_info_modes().contains(&info_mode) {
+                    self.info_mode = info_mode;
+                }
+            }
+            self.restore_view(session.offset, session.scroll_offset);
+            self.selection_anchor = session
+                .selection_anchor
+                .filter(|&anchor| anchor < self.data.len());
+        } else {
+            self.log(
+                NotificationLevel::Warning,
+                &format!(
+                    "Could not restore the session, \"{}\" does not exist anymore",
+                    session.path
+                ),
+            );
+            let dir = self.filesystem.pwd().to_string();
+            Self::open_dir(
+                &mut self.popup,
+                &dir,
+                &mut self.filesystem,
+                self.settings.app.follow_symlinks,
+                &self.recent_dirs,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor to `offset` and scrolls so that the view starts at `scroll_offset`,
+    /// unless that would hide the cursor.
+    fn restore_view(&mut self, offset: usize, scroll_offset: usize) {
+        let offset = offset.min(self.data.len().saturating_sub(1));
+        self.jump_to(offset, false);
+        if self.data.is_empty() || self.screen_size.1 <= self.vertical_margin {
+            return;
+        }
+        let bytes_per_row = self.block_size * self.blocks_per_row;
+        let visible_rows = (self.screen_size.1 - self.vertical_margin) as usize;
+        let row = offset / bytes_per_row;
+        let top_row = scroll_offset / bytes_per_row;
+        if top_row <= row && row < top_row + visible_rows {
+            self.scroll = top_row;
+            if let Some(cursor) = self.get_expected_cursor_position(offset, true).cursor {
+                self.cursor = cursor;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use crate::app::{fields::FieldType, popup::popup_state::PopupState};
+
+    use super::*;
+
+    #[test]
+    fn test_session_save_load() {
+        let session_file = tempfile::NamedTempFile::new().unwrap();
+        let session = Session {
+            version: Session::VERSION,
+            path: "/some/file.bin".to_string(),
+            offset: 0x42,
+            fields: vec![Field {
+                name: "magic".to_string(),
+                offset: 0,
+                size: 4,
+                field_type: FieldType::U32,
+            }],
+            scroll_offset: 0x40,
+            selection_anchor: Some(0x40),
+            info_mode: Some(InfoMode::Assembly),
+            folds: vec![Fold { start: 0, end: 4 }],
+        };
+        session.save(Some(session_file.path())).unwrap();
+        let loaded = Session::load(Some(session_file.path())).unwrap();
+        assert_eq!(loaded, session);
+
+        // sessions saved before the view state was added can still be loaded
+        std::fs::write(
+            session_file.path(),
+            r#"{"path": "/some/file.bin", "offset": 66}"#,
+        )
+        .unwrap();
+        let loaded = Session::load(Some(session_file.path())).unwrap();
+        assert_eq!(
+            loaded,
+            Session {
+                path: "/some/file.bin".to_string(),
+                offset: 0x42,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_restore_session() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0; 0x100]).unwrap();
+        let session = Session {
+            path: file
+                .path()
+                .canonicalize()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            offset: 0x42,
+            fields: vec![Field {
+                name: "magic".to_string(),
+                offset: 0x10,
+                size: 4,
+                field_type: FieldType::U32,
+            }],
+            version: Session::VERSION,
+            scroll_offset: 0x40,
+            selection_anchor: Some(0x44),
+            info_mode: Some(InfoMode::Text),
+            folds: Vec::new(),
+        };
+        let mut app = App::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.restore_session(session.clone(), &mut terminal).unwrap();
+        assert_eq!(app.filesystem.pwd(), session.path);
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x42);
+        assert_eq!(app.get_selection(), Some((0x42, 0x45)));
+        assert_eq!(app.scroll * app.block_size * app.blocks_per_row, 0x40);
+        assert_eq!(app.get_session(), Some(session));
+    }
+
+    #[test]
+    fn test_save_session_on_open() {
+        let session_dir = tempfile::tempdir().unwrap();
+        let session_path = session_dir.path().join("session.json");
+        let mut first = tempfile::NamedTempFile::new().unwrap();
+        first.write_all(&[0; 0x100]).unwrap();
+        let mut second = tempfile::NamedTempFile::new().unwrap();
+        second.write_all(&[0; 0x100]).unwrap();
+        let mut app = App {
+            session_path: Some(session_path.clone()),
+            ..Default::default()
+        };
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.open_file(&first.path().to_string_lossy(), &mut terminal)
+            .unwrap();
+        assert!(!session_path.exists());
+        app.jump_to(0x42, false);
+        let first_path = app.filesystem.pwd().to_string();
+        app.open_file(&second.path().to_string_lossy(), &mut terminal)
+            .unwrap();
+        let session = Session::load(Some(&session_path)).unwrap();
+        assert_eq!(session.path, first_path);
+        assert_eq!(session.offset, 0x42);
+    }
+
+    #[test]
+    fn test_restore_session_invalid_fields() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0; 0x100]).unwrap();
+        let field = |offset, size, field_type| Field {
+            name: "field".to_string(),
+            offset,
+            size,
+            field_type,
+        };
+        let session = Session {
+            path: file.path().to_string_lossy().to_string(),
+            fields: vec![
+                field(0x10, 4, FieldType::U32),
+                field(0x10, 2, FieldType::U32),
+                field(usize::MAX, 4, FieldType::U32),
+                field(0xF0, 0x20, FieldType::Bytes),
+            ],
+            ..Default::default()
+        };
+        let mut app = App::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.restore_session(session, &mut terminal).unwrap();
+        assert_eq!(app.fields, vec![field(0x10, 4, FieldType::U32)]);
+        app.draw(&mut terminal).unwrap();
+    }
+
+    #[test]
+    fn test_restore_missing_session() {
+        let session = Session {
+            path: "this/file/does/not/exist".to_string(),
+            offset: 0x42,
+            ..Default::default()
+        };
+        let mut app = App::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 25)).unwrap();
+        app.restore_session(session, &mut terminal).unwrap();
+        assert!(app.data.is_empty());
+        assert!(matches!(app.popup, Some(PopupState::Open { .. })));
+    }
+}
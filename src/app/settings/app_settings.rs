@@ -1,7 +1,16 @@
+use std::collections::BTreeMap;
+
 use mlua::UserDataRegistry;
 use serde::{Deserialize, Serialize};
 
-use super::{theme_preference::ThemePreference, verbosity::Verbosity, Settings};
+use crate::app::info_mode::InfoMode;
+
+use super::{
+    assembly_address_base::AssemblyAddressBase, bom_display::BomDisplay,
+    control_characters::ControlCharacters, copy_format::CopyFormat, initial_cursor::InitialCursor,
+    large_file_action::LargeFileAction, line_ending::LineEnding, strings_encoding::StringsEncoding,
+    theme_preference::ThemePreference, verbosity::Verbosity, Settings,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
@@ -10,6 +19,42 @@ pub struct AppSettings {
     pub log_limit: usize,
     pub log_level: Verbosity,
     pub theme: ThemePreference,
+    pub bytes_per_row: usize,
+    pub power_of_two_rows: bool,
+    pub info_modes: Vec<InfoMode>,
+    pub split_info_view: bool,
+    pub disassembly: bool,
+    pub uppercase_hex: bool,
+    pub column_highlight: usize,
+    pub large_file_size: u64,
+    pub auto_scroll: bool,
+    pub follow_symlinks: bool,
+    pub byte_breakdown: bool,
+    pub trailing_newline: bool,
+    pub info_view_width: usize,
+    pub line_ending: LineEnding,
+    pub decompress: bool,
+    pub large_file_action: LargeFileAction,
+    pub read_timeout: u64,
+    pub remember_files: bool,
+    pub control_characters: ControlCharacters,
+    pub compact_address: bool,
+    pub assembly_address_base: AssemblyAddressBase,
+    pub assembly_address_width: usize,
+    pub assembly_uppercase_hex: bool,
+    pub strings_min_length: usize,
+    pub strings_encoding: StringsEncoding,
+    pub save_unchanged: bool,
+    pub keep_shift_selection: bool,
+    pub initial_cursor: InitialCursor,
+    pub copy_format: CopyFormat,
+    pub preview_patches: bool,
+    pub change_gutter: bool,
+    pub bom_display: BomDisplay,
+    pub command_aliases: BTreeMap<String, String>,
+    pub digit_grouping: String,
+    pub terminal_cursor: bool,
+    pub editor: String,
 }
 
 impl AppSettings {
@@ -36,6 +81,316 @@ impl AppSettings {
                 Ok(())
             },
         );
+        mlua::UserDataFields::add_field_method_get(data, "app_bytes_per_row", |_lua, settings| {
+            Ok(settings.app.bytes_per_row)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_bytes_per_row",
+            |_lua, settings, value| {
+                settings.app.bytes_per_row = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_power_of_two_rows",
+            |_lua, settings| Ok(settings.app.power_of_two_rows),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_power_of_two_rows",
+            |_lua, settings, value| {
+                settings.app.power_of_two_rows = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_split_info_view",
+            |_lua, settings| Ok(settings.app.split_info_view),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_split_info_view",
+            |_lua, settings, value| {
+                settings.app.split_info_view = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_disassembly", |_lua, settings| {
+            Ok(settings.app.disassembly)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_disassembly",
+            |_lua, settings, value| {
+                settings.app.disassembly = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_uppercase_hex", |_lua, settings| {
+            Ok(settings.app.uppercase_hex)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_uppercase_hex",
+            |_lua, settings, value| {
+                settings.app.uppercase_hex = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_column_highlight",
+            |_lua, settings| Ok(settings.app.column_highlight),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_column_highlight",
+            |_lua, settings, value| {
+                settings.app.column_highlight = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_large_file_size",
+            |_lua, settings| Ok(settings.app.large_file_size),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_large_file_size",
+            |_lua, settings, value| {
+                settings.app.large_file_size = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_auto_scroll", |_lua, settings| {
+            Ok(settings.app.auto_scroll)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_auto_scroll",
+            |_lua, settings, value| {
+                settings.app.auto_scroll = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_follow_symlinks",
+            |_lua, settings| Ok(settings.app.follow_symlinks),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_follow_symlinks",
+            |_lua, settings, value| {
+                settings.app.follow_symlinks = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_byte_breakdown", |_lua, settings| {
+            Ok(settings.app.byte_breakdown)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_byte_breakdown",
+            |_lua, settings, value| {
+                settings.app.byte_breakdown = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_trailing_newline",
+            |_lua, settings| Ok(settings.app.trailing_newline),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_trailing_newline",
+            |_lua, settings, value| {
+                settings.app.trailing_newline = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_info_view_width",
+            |_lua, settings| Ok(settings.app.info_view_width),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_info_view_width",
+            |_lua, settings, value| {
+                settings.app.info_view_width = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_read_timeout", |_lua, settings| {
+            Ok(settings.app.read_timeout)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_read_timeout",
+            |_lua, settings, value| {
+                settings.app.read_timeout = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_decompress", |_lua, settings| {
+            Ok(settings.app.decompress)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_decompress",
+            |_lua, settings, value| {
+                settings.app.decompress = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_remember_files", |_lua, settings| {
+            Ok(settings.app.remember_files)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_remember_files",
+            |_lua, settings, value| {
+                settings.app.remember_files = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_compact_address",
+            |_lua, settings| Ok(settings.app.compact_address),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_compact_address",
+            |_lua, settings, value| {
+                settings.app.compact_address = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_assembly_address_width",
+            |_lua, settings| Ok(settings.app.assembly_address_width),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_assembly_address_width",
+            |_lua, settings, value| {
+                settings.app.assembly_address_width = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_assembly_uppercase_hex",
+            |_lua, settings| Ok(settings.app.assembly_uppercase_hex),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_assembly_uppercase_hex",
+            |_lua, settings, value| {
+                settings.app.assembly_uppercase_hex = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_strings_min_length",
+            |_lua, settings| Ok(settings.app.strings_min_length),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_strings_min_length",
+            |_lua, settings, value| {
+                settings.app.strings_min_length = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_save_unchanged", |_lua, settings| {
+            Ok(settings.app.save_unchanged)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_save_unchanged",
+            |_lua, settings, value| {
+                settings.app.save_unchanged = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_keep_shift_selection",
+            |_lua, settings| Ok(settings.app.keep_shift_selection),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_keep_shift_selection",
+            |_lua, settings, value| {
+                settings.app.keep_shift_selection = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_preview_patches",
+            |_lua, settings| Ok(settings.app.preview_patches),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_preview_patches",
+            |_lua, settings, value| {
+                settings.app.preview_patches = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_change_gutter", |_lua, settings| {
+            Ok(settings.app.change_gutter)
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_change_gutter",
+            |_lua, settings, value| {
+                settings.app.change_gutter = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_digit_grouping", |_lua, settings| {
+            Ok(settings.app.digit_grouping.clone())
+        });
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_digit_grouping",
+            |_lua, settings, value| {
+                settings.app.digit_grouping = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(
+            data,
+            "app_terminal_cursor",
+            |_lua, settings| Ok(settings.app.terminal_cursor),
+        );
+        mlua::UserDataFields::add_field_method_set(
+            data,
+            "app_terminal_cursor",
+            |_lua, settings, value| {
+                settings.app.terminal_cursor = value;
+                Ok(())
+            },
+        );
+        mlua::UserDataFields::add_field_method_get(data, "app_editor", |_lua, settings| {
+            Ok(settings.app.editor.clone())
+        });
+        mlua::UserDataFields::add_field_method_set(data, "app_editor", |_lua, settings, value| {
+            settings.app.editor = value;
+            Ok(())
+        });
     }
 }
 
@@ -46,6 +401,42 @@ impl Default for AppSettings {
             log_limit: 1024,
             log_level: Verbosity::default(),
             theme: ThemePreference::default(),
+            bytes_per_row: 0,
+            power_of_two_rows: false,
+            info_modes: vec![InfoMode::Text, InfoMode::Assembly],
+            split_info_view: false,
+            disassembly: true,
+            uppercase_hex: true,
+            column_highlight: 0,
+            large_file_size: 256,
+            auto_scroll: true,
+            follow_symlinks: true,
+            byte_breakdown: true,
+            trailing_newline: false,
+            info_view_width: 0,
+            line_ending: LineEnding::default(),
+            decompress: false,
+            large_file_action: LargeFileAction::default(),
+            read_timeout: 0,
+            remember_files: false,
+            control_characters: ControlCharacters::default(),
+            compact_address: false,
+            assembly_address_base: AssemblyAddressBase::default(),
+            assembly_address_width: 0,
+            assembly_uppercase_hex: true,
+            strings_min_length: 4,
+            strings_encoding: StringsEncoding::default(),
+            save_unchanged: false,
+            keep_shift_selection: false,
+            initial_cursor: InitialCursor::default(),
+            copy_format: CopyFormat::default(),
+            preview_patches: true,
+            change_gutter: false,
+            bom_display: BomDisplay::default(),
+            command_aliases: BTreeMap::new(),
+            digit_grouping: String::new(),
+            terminal_cursor: false,
+            editor: String::new(),
         }
     }
 }
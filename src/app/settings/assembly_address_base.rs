@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// The address shown in the address column of the assembly view,
+/// the other one is shown after the instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AssemblyAddressBase {
+    /// The offset in the file, like the address column of the hex view.
+    #[default]
+    File,
+    Virtual,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// How a byte order mark at the start of the file is shown in the text view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BomDisplay {
+    /// The bytes of the mark are shown like any other byte.
+    #[default]
+    Show,
+    /// The bytes of the mark are left blank.
+    Hide,
+    /// The bytes of the mark are shown with the `text_bom` style.
+    Highlight,
+}
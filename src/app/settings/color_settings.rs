@@ -16,18 +16,28 @@ use super::theme_preference::ThemePreference;
 pub struct ColorSettings {
     pub address_selected: Style,
     pub address_default: Style,
+    pub address_pinned: Style,
+    pub address_changed: Style,
 
     pub hex_selected: Style,
     pub hex_null: Style,
+    pub hex_full: Style,
     pub hex_alphanumeric: Style,
     pub hex_symbol: Style,
     pub hex_end_of_line: Style,
     pub hex_whitespace: Style,
     pub hex_current_instruction: Style,
     pub hex_current_section: Style,
+    pub hex_selection: Style,
+    pub hex_difference: Style,
+    pub hex_field: Style,
+    pub hex_column: Style,
+    pub hex_equal: Style,
+    pub hex_pinned: Style,
     pub hex_default: Style,
 
     pub text_selected: Style,
+    pub text_bom: Style,
 
     pub assembly_symbol: Style,
     pub assembly_selected: Style,
@@ -64,6 +74,7 @@ pub struct ColorSettings {
     pub path_dir: Style,
     pub path_file: Style,
     pub path_selected: Style,
+    pub path_symlink: Style,
 
     pub log_info: Style,
     pub log_debug: Style,
@@ -79,6 +90,7 @@ pub struct ColorSettings {
 
     pub scrollbar: Style,
     pub placeholder: Style,
+    pub focused_border: Style,
 }
 
 impl ColorSettings {
@@ -98,18 +110,30 @@ impl ColorSettings {
         Self {
             address_selected: Style::default().fg(Color::White).bg(Color::Black),
             address_default: Style::default().fg(Color::Gray),
+            address_pinned: Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            address_changed: Style::default().fg(Color::Red),
 
             hex_selected: Style::default().fg(Color::White).bg(Color::Black),
-            hex_null: Style::default().fg(Color::Gray),
+            hex_null: Style::default().fg(Color::Gray).add_modifier(Modifier::DIM),
+            hex_full: Style::default(),
             hex_alphanumeric: Style::default().fg(light_brown),
             hex_symbol: Style::default().fg(light_brown).add_modifier(Modifier::DIM),
             hex_end_of_line: Style::default().fg(Color::Red),
             hex_whitespace: Style::default().fg(desaturated_dark_brown),
             hex_current_instruction: Style::default().fg(Color::White).bg(dark_orange),
             hex_current_section: Style::default().fg(Color::White).bg(dark_orange),
+            hex_selection: Style::default().fg(Color::Black).bg(Color::Gray),
+            hex_difference: Style::default().fg(Color::White).bg(Color::Red),
+            hex_field: Style::default().add_modifier(Modifier::UNDERLINED),
+            hex_column: Style::default().add_modifier(Modifier::BOLD),
+            hex_equal: Style::default().bg(Color::LightBlue),
+            hex_pinned: Style::default().fg(Color::White).bg(Color::Magenta),
             hex_default: Style::default(),
 
             text_selected: Style::default().fg(Color::White).bg(Color::Black),
+            text_bom: Style::default().fg(Color::Blue),
 
             assembly_symbol: Style::default().fg(Color::Green),
             assembly_selected: Style::default().fg(Color::White).bg(Color::Black),
@@ -146,6 +170,7 @@ impl ColorSettings {
             path_dir: Style::default().fg(Color::Blue),
             path_file: Style::default().fg(dark_yellow),
             path_selected: Style::default().fg(Color::White).bg(Color::Black),
+            path_symlink: Style::default().fg(Color::Cyan),
 
             log_info: Style::default().fg(Color::Blue),
             log_debug: Style::default().fg(Color::Green),
@@ -161,6 +186,7 @@ impl ColorSettings {
 
             scrollbar: Style::default().fg(status_bar_bg).bg(Color::Gray),
             placeholder: Style::default().fg(Color::Gray),
+            focused_border: Style::default().fg(Color::Blue),
         }
     }
 
@@ -169,9 +195,16 @@ impl ColorSettings {
         Self {
             address_selected: Style::default().fg(Color::Black).bg(Color::White),
             address_default: Style::default().fg(Color::DarkGray),
+            address_pinned: Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+            address_changed: Style::default().fg(Color::LightYellow),
 
             hex_selected: Style::default().fg(Color::Black).bg(Color::White),
-            hex_null: Style::default().fg(Color::DarkGray),
+            hex_null: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::DIM),
+            hex_full: Style::default(),
             hex_alphanumeric: Style::default().fg(Color::Rgb(204, 152, 113)),
             hex_symbol: Style::default()
                 .fg(Color::Rgb(204, 152, 113))
@@ -184,9 +217,16 @@ impl ColorSettings {
             hex_current_section: Style::default()
                 .fg(Color::Black)
                 .bg(Color::Rgb(215, 170, 92)),
+            hex_selection: Style::default().fg(Color::White).bg(Color::DarkGray),
+            hex_difference: Style::default().fg(Color::Black).bg(Color::LightRed),
+            hex_field: Style::default().add_modifier(Modifier::UNDERLINED),
+            hex_column: Style::default().add_modifier(Modifier::BOLD),
+            hex_equal: Style::default().bg(Color::Blue),
+            hex_pinned: Style::default().fg(Color::Black).bg(Color::LightMagenta),
             hex_default: Style::default(),
 
             text_selected: Style::default().fg(Color::Black).bg(Color::White),
+            text_bom: Style::default().fg(Color::LightCyan),
 
             assembly_symbol: Style::default().fg(Color::LightGreen),
             assembly_selected: Style::default().fg(Color::Black).bg(Color::White),
@@ -225,6 +265,7 @@ impl ColorSettings {
             path_dir: Style::default().fg(Color::Blue),
             path_file: Style::default().fg(Color::Yellow),
             path_selected: Style::default().fg(Color::Black).bg(Color::White),
+            path_symlink: Style::default().fg(Color::LightCyan),
 
             log_info: Style::default().fg(Color::LightBlue),
             log_debug: Style::default().fg(Color::LightGreen),
@@ -240,6 +281,7 @@ impl ColorSettings {
 
             scrollbar: Style::default().fg(status_bar_bg).bg(Color::DarkGray),
             placeholder: Style::default().fg(Color::DarkGray),
+            focused_border: Style::default().fg(Color::Yellow),
         }
     }
 
@@ -267,6 +309,8 @@ impl App {
         {
             // null
             0x00 => color_settings.hex_null,
+            // all bits set
+            0xFF => color_settings.hex_full,
             // newline
             0x0A | 0x0C | 0x0D => color_settings.hex_end_of_line,
             // whitespace
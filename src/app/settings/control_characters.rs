@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// How the bytes that are not printable ASCII are shown in the text view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlCharacters {
+    /// A symbol for the common control characters, `.` for the others.
+    #[default]
+    Symbols,
+    Dot,
+    /// The Unicode control pictures (U+2400 to U+241F) for 0x00 to 0x1F.
+    Pictures,
+    /// The value of the byte in hex, it takes the space between two bytes.
+    Hex,
+}
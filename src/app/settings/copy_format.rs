@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// How the selected bytes are copied to the clipboard by the `copy` key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyFormat {
+    /// The bytes in hex separated by spaces, like `DE AD BE EF`.
+    #[default]
+    Hex,
+    /// The bytes as they are.
+    Raw,
+    /// A C string literal without the quotes, like `AB\x00\n`.
+    Escaped,
+}
+
+impl CopyFormat {
+    /// The format used by the `copy_alternate` key, hex unless hex is already the default.
+    pub fn alternate(&self) -> Self {
+        match self {
+            CopyFormat::Hex => CopyFormat::Escaped,
+            CopyFormat::Raw | CopyFormat::Escaped => CopyFormat::Hex,
+        }
+    }
+}
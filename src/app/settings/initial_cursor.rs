@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Where the cursor is placed when a file is opened,
+/// it is placed at offset 0 if the target can't be found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialCursor {
+    #[default]
+    Start,
+    EntryPoint,
+    FirstNonZero,
+    /// The entry point of executables, the first non-zero byte of the other files.
+    Auto,
+}
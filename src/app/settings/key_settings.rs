@@ -13,6 +13,10 @@ pub struct KeySettings {
     pub down: KeyEvent,
     pub left: KeyEvent,
     pub right: KeyEvent,
+    pub select_up: KeyEvent,
+    pub select_down: KeyEvent,
+    pub select_left: KeyEvent,
+    pub select_right: KeyEvent,
 
     pub next: KeyEvent,
     pub previous: KeyEvent,
@@ -22,6 +26,12 @@ pub struct KeySettings {
 
     pub goto_start: KeyEvent,
     pub goto_end: KeyEvent,
+    pub section_start: KeyEvent,
+    pub section_end: KeyEvent,
+    pub previous_section: KeyEvent,
+    pub next_section: KeyEvent,
+    pub previous_offset: KeyEvent,
+    pub next_offset: KeyEvent,
 
     pub quit: KeyEvent,
     pub save_and_quit: KeyEvent,
@@ -38,15 +48,42 @@ pub struct KeySettings {
     pub patch_assembly: KeyEvent,
     pub jump: KeyEvent,
     pub change_view: KeyEvent,
+    pub view_text: KeyEvent,
+    pub view_assembly: KeyEvent,
+    pub previous_view: KeyEvent,
+    pub jump_to_instruction: KeyEvent,
+    pub jump_to_operand: KeyEvent,
+    pub switch_focus: KeyEvent,
+    pub select: KeyEvent,
+    pub swap_selection: KeyEvent,
+    pub find_selection: KeyEvent,
+    pub find_next: KeyEvent,
+    pub find_prev: KeyEvent,
+    pub copy: KeyEvent,
+    pub copy_alternate: KeyEvent,
+    pub pin: KeyEvent,
+    pub jump_to_pin: KeyEvent,
+    pub set_mark: KeyEvent,
+    pub measure: KeyEvent,
+    pub toggle_hex_case: KeyEvent,
+    pub highlight_equal_bytes: KeyEvent,
+    pub toggle_split_view: KeyEvent,
+    pub toggle_fold: KeyEvent,
 
     pub confirm: KeyEvent,
     pub close_popup: KeyEvent,
+    pub next_button: KeyEvent,
+    pub previous_button: KeyEvent,
 
     pub new_line: KeyEvent,
     pub clear_log: KeyEvent,
 
     pub undo: KeyEvent,
     pub redo: KeyEvent,
+    pub increment_byte: KeyEvent,
+    pub decrement_byte: KeyEvent,
+    pub increment_byte_by_16: KeyEvent,
+    pub decrement_byte_by_16: KeyEvent,
 }
 
 impl KeySettings {
@@ -207,6 +244,10 @@ impl Default for KeySettings {
             down: KeyEvent::new(KeyCode::Down, KeyModifiers::empty()),
             left: KeyEvent::new(KeyCode::Left, KeyModifiers::empty()),
             right: KeyEvent::new(KeyCode::Right, KeyModifiers::empty()),
+            select_up: KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT),
+            select_down: KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT),
+            select_left: KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT),
+            select_right: KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT),
 
             next: KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL),
             previous: KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL),
@@ -216,6 +257,12 @@ impl Default for KeySettings {
 
             goto_start: KeyEvent::new(KeyCode::Home, KeyModifiers::empty()),
             goto_end: KeyEvent::new(KeyCode::End, KeyModifiers::empty()),
+            section_start: KeyEvent::new(KeyCode::Char('['), KeyModifiers::empty()),
+            section_end: KeyEvent::new(KeyCode::Char(']'), KeyModifiers::empty()),
+            previous_section: KeyEvent::new(KeyCode::Char(','), KeyModifiers::empty()),
+            next_section: KeyEvent::new(KeyCode::Char('.'), KeyModifiers::empty()),
+            previous_offset: KeyEvent::new(KeyCode::Left, KeyModifiers::ALT),
+            next_offset: KeyEvent::new(KeyCode::Right, KeyModifiers::ALT),
 
             quit: KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
             save_and_quit: KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL),
@@ -232,15 +279,42 @@ impl Default for KeySettings {
             patch_assembly: KeyEvent::new(KeyCode::Char('p'), KeyModifiers::empty()),
             jump: KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty()),
             change_view: KeyEvent::new(KeyCode::Char('v'), KeyModifiers::empty()),
+            view_text: KeyEvent::new(KeyCode::F(2), KeyModifiers::empty()),
+            view_assembly: KeyEvent::new(KeyCode::F(3), KeyModifiers::empty()),
+            previous_view: KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+            jump_to_instruction: KeyEvent::new(KeyCode::Char('i'), KeyModifiers::empty()),
+            jump_to_operand: KeyEvent::new(KeyCode::Char('r'), KeyModifiers::empty()),
+            switch_focus: KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            select: KeyEvent::new(KeyCode::Char('m'), KeyModifiers::empty()),
+            swap_selection: KeyEvent::new(KeyCode::Char('o'), KeyModifiers::empty()),
+            find_selection: KeyEvent::new(KeyCode::Char('*'), KeyModifiers::empty()),
+            find_next: KeyEvent::new(KeyCode::Char('n'), KeyModifiers::empty()),
+            find_prev: KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT),
+            copy: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::empty()),
+            copy_alternate: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::ALT),
+            pin: KeyEvent::new(KeyCode::Char('k'), KeyModifiers::empty()),
+            jump_to_pin: KeyEvent::new(KeyCode::Char('k'), KeyModifiers::ALT),
+            set_mark: KeyEvent::new(KeyCode::Char('g'), KeyModifiers::empty()),
+            measure: KeyEvent::new(KeyCode::Char('w'), KeyModifiers::empty()),
+            toggle_hex_case: KeyEvent::new(KeyCode::Char('u'), KeyModifiers::empty()),
+            highlight_equal_bytes: KeyEvent::new(KeyCode::Char('='), KeyModifiers::empty()),
+            toggle_split_view: KeyEvent::new(KeyCode::F(4), KeyModifiers::empty()),
+            toggle_fold: KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()),
 
             confirm: KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()),
             close_popup: KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()),
+            next_button: KeyEvent::new(KeyCode::Tab, KeyModifiers::empty()),
+            previous_button: KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
 
             new_line: KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT),
             clear_log: KeyEvent::new(KeyCode::Delete, KeyModifiers::empty()),
 
             undo: KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL),
             redo: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL),
+            increment_byte: KeyEvent::new(KeyCode::Char('+'), KeyModifiers::empty()),
+            decrement_byte: KeyEvent::new(KeyCode::Char('-'), KeyModifiers::empty()),
+            increment_byte_by_16: KeyEvent::new(KeyCode::Char('+'), KeyModifiers::ALT),
+            decrement_byte_by_16: KeyEvent::new(KeyCode::Char('-'), KeyModifiers::ALT),
         }
     }
 }
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::popup::large_file_choice::LargeFileChoice;
+
+/// What happens when a file larger than the `large_file_size` setting is opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LargeFileAction {
+    /// Ask with a popup.
+    #[default]
+    Ask,
+    Open,
+    ReadOnly,
+    NoDisassembly,
+    /// Don't open the file.
+    Refuse,
+}
+
+impl LargeFileAction {
+    /// The choice made without asking, `None` if the user must be asked.
+    pub fn choice(&self) -> Option<LargeFileChoice> {
+        match self {
+            LargeFileAction::Ask => None,
+            LargeFileAction::Open => Some(LargeFileChoice::Open),
+            LargeFileAction::ReadOnly => Some(LargeFileChoice::ReadOnly),
+            LargeFileAction::NoDisassembly => Some(LargeFileChoice::NoDisassembly),
+            LargeFileAction::Refuse => Some(LargeFileChoice::Cancel),
+        }
+    }
+}
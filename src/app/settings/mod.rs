@@ -2,14 +2,22 @@ mod settings;
 pub use settings::Settings;
 
 pub mod app_settings;
+pub mod assembly_address_base;
+pub mod bom_display;
 pub mod color_settings;
+pub mod control_characters;
+pub mod copy_format;
 pub mod key_settings;
+pub mod large_file_action;
+pub mod line_ending;
 #[macro_use]
 pub mod register_key_settings_macro;
 #[macro_use]
 pub mod register_color_settings_macro;
 #[macro_use]
 pub mod edit_color_settings;
+pub mod initial_cursor;
 pub mod settings_value;
+pub mod strings_encoding;
 pub mod theme_preference;
 pub mod verbosity;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// The encoding of the characters looked for by the `strings` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StringsEncoding {
+    #[default]
+    Ascii,
+    /// UTF-16 little endian, only the characters in the ASCII range are found.
+    Utf16,
+}
@@ -3,6 +3,7 @@ use std::{error::Error, fmt::Display, path::PathBuf, sync::Arc};
 use russh::client::{self, Handler};
 use russh_keys::key::PrivateKeyWithHashAlg;
 use russh_sftp::client::SftpSession;
+use tokio::io::AsyncWriteExt;
 
 use crate::app::files::path;
 
@@ -117,6 +118,11 @@ impl Connection {
         Ok(remote_file)
     }
 
+    pub fn file_size(&self, path: &str) -> Result<u64, Box<dyn Error>> {
+        let metadata = self.runtime.block_on(self.sftp.metadata(path))?;
+        Ok(metadata.size.unwrap_or_default())
+    }
+
     pub fn mkdirs(&self, path: &str) -> Result<(), Box<dyn Error>> {
         self.runtime.block_on(async {
             let mut paths = vec![path];
@@ -142,8 +148,15 @@ impl Connection {
         Ok(())
     }
 
+    /// Replaces the content of the file with `data`, the file is truncated so that
+    /// no stale bytes are left after the end of `data`.
     pub fn write(&self, path: &str, data: &[u8]) -> Result<(), Box<dyn Error>> {
-        self.runtime.block_on(self.sftp.write(path, data))?;
+        self.runtime.block_on(async {
+            let mut file = self.sftp.create(path).await?;
+            file.write_all(data).await?;
+            file.shutdown().await?;
+            Ok::<(), russh_sftp::client::error::Error>(())
+        })?;
         Ok(())
     }
 
@@ -154,6 +167,10 @@ impl Connection {
             .collect()
     }
 
+    pub fn read_link(&self, path: &str) -> Option<String> {
+        self.runtime.block_on(self.sftp.read_link(path)).ok()
+    }
+
     pub fn is_file(&self, path: &str) -> bool {
         self.runtime
             .block_on(self.sftp.metadata(path))
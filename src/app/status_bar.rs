@@ -28,6 +28,13 @@ impl App {
         line.spans
             .push(Span::styled(" ", self.settings.color.status_bar));
         if self.logger.get_notification_level() != NotificationLevel::None {
+            let hidden_messages = self.logger.unseen().saturating_sub(1);
+            if hidden_messages > 0 {
+                line.spans.push(Span::styled(
+                    format!("(+{}) ", hidden_messages),
+                    notification_style,
+                ));
+            }
             line.spans.push(Span::styled(
                 self.logger[self.logger.len() - 1]
                     .message
@@ -40,14 +47,71 @@ impl App {
 
         let current_location_span = Span::styled(
             format!(
-                "{:16X} {} ",
-                current_position.global_byte_index,
+                "{} {} ",
+                if self.settings.app.uppercase_hex {
+                    format!("{:16X}", current_position.global_byte_index)
+                } else {
+                    format!("{:16x}", current_position.global_byte_index)
+                },
                 if current_position.high_byte { "H" } else { "L" }
             ),
             self.settings.color.status_bar,
         );
-        let space_number =
-            max_len as isize - line.width() as isize - current_location_span.width() as isize - 2;
+        let history_span = if self.data.read_only() {
+            Span::styled("Read-only ", self.settings.color.status_warning)
+        } else if self.data.history_truncated() {
+            Span::styled("History truncated ", self.settings.color.status_warning)
+        } else {
+            Span::raw("")
+        };
+        let byte_span = match self.data.bytes().get(current_position.global_byte_index) {
+            Some(&byte) if self.settings.app.byte_breakdown => Span::styled(
+                format!(
+                    "{} {} {:04b}_{:04b} ",
+                    if self.settings.app.uppercase_hex {
+                        format!("0x{:02X}", byte)
+                    } else {
+                        format!("0x{:02x}", byte)
+                    },
+                    byte,
+                    byte >> 4,
+                    byte & 0xF
+                ),
+                self.settings.color.status_bar,
+            ),
+            _ => Span::raw(""),
+        };
+        let field_span = match self.get_field_at(current_position.global_byte_index) {
+            Some(field) => Span::styled(
+                format!(
+                    "{}: {} ",
+                    field.name,
+                    self.decode_field(field).unwrap_or_default()
+                ),
+                self.settings.color.status_bar,
+            ),
+            None => Span::raw(""),
+        };
+        let recent_offsets_span = if self.recent_offsets.is_empty() {
+            Span::raw("")
+        } else {
+            Span::styled(
+                format!(
+                    "[{}/{}] ",
+                    self.recent_offset_index + 1,
+                    self.recent_offsets.len()
+                ),
+                self.settings.color.status_bar,
+            )
+        };
+        let space_number = max_len as isize
+            - line.width() as isize
+            - field_span.width() as isize
+            - history_span.width() as isize
+            - recent_offsets_span.width() as isize
+            - byte_span.width() as isize
+            - current_location_span.width() as isize
+            - 2;
         if space_number < 0 {
             return Text::default();
         }
@@ -55,6 +119,10 @@ impl App {
         let padding_spaces_string = " ".repeat(space_number);
 
         line.spans.push(Span::raw(padding_spaces_string));
+        line.spans.push(field_span);
+        line.spans.push(history_span);
+        line.spans.push(recent_offsets_span);
+        line.spans.push(byte_span);
         line.spans.push(current_location_span);
         status_bar.lines.push(line);
         status_bar
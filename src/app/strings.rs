@@ -0,0 +1,158 @@
+use std::error::Error;
+
+use super::{
+    files::path, log::NotificationLevel, popup::popup_state::PopupState,
+    settings::strings_encoding::StringsEncoding, App,
+};
+
+/// A run of printable characters found in the file, like the ones listed by `strings`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundString {
+    pub offset: usize,
+    pub text: String,
+}
+
+impl App {
+    fn is_printable(byte: u8) -> bool {
+        byte.is_ascii_graphic() || byte == b' ' || byte == b'\t'
+    }
+
+    /// Finds the runs of at least `min_length` printable characters,
+    /// in UTF-16 only the characters in the ASCII range are considered printable.
+    pub(super) fn find_strings(
+        bytes: &[u8],
+        min_length: usize,
+        encoding: StringsEncoding,
+    ) -> Vec<FoundString> {
+        let char_size = match encoding {
+            StringsEncoding::Ascii => 1,
+            StringsEncoding::Utf16 => 2,
+        };
+        let char_at = |i: usize| match encoding {
+            StringsEncoding::Ascii => Some(bytes[i]),
+            StringsEncoding::Utf16 => match bytes.get(i + 1) {
+                Some(0) => Some(bytes[i]),
+                _ => None,
+            },
+        };
+        let min_length = min_length.max(1);
+        let mut strings = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = i;
+            let mut text = String::new();
+            while i < bytes.len() {
+                match char_at(i) {
+                    Some(c) if Self::is_printable(c) => {
+                        text.push(c as char);
+                        i += char_size;
+                    }
+                    _ => break,
+                }
+            }
+            if text.len() >= min_length {
+                strings.push(FoundString {
+                    offset: start,
+                    text,
+                });
+            }
+            if i == start {
+                i += 1;
+            }
+        }
+        strings
+    }
+
+    fn get_strings(&self) -> Vec<FoundString> {
+        Self::find_strings(
+            self.data.bytes(),
+            self.settings.app.strings_min_length,
+            self.settings.app.strings_encoding,
+        )
+    }
+
+    pub(in crate::app) fn request_popup_strings(&mut self) {
+        let strings = self.get_strings();
+        if strings.is_empty() {
+            self.log(NotificationLevel::Warning, "No strings found.");
+            return;
+        }
+        self.popup = Some(PopupState::Strings { strings, scroll: 0 });
+    }
+
+    /// Writes every string found in the file to `path`, one per line after its offset.
+    pub(super) fn export_strings(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let strings = self.get_strings();
+        let line_ending = self.settings.app.line_ending.as_str();
+        let text = strings
+            .iter()
+            .map(|string| {
+                let offset = if self.settings.app.uppercase_hex {
+                    format!("{:08X}", string.offset)
+                } else {
+                    format!("{:08x}", string.offset)
+                };
+                format!("{}  {}{}", offset, string.text, line_ending)
+            })
+            .collect::<String>();
+
+        if let Some(parent) = path::parent(path) {
+            self.filesystem.mkdirs(parent)?;
+        };
+        self.filesystem.create(path)?;
+        let path = self.filesystem.canonicalize(path)?;
+        self.filesystem.write(&path, text.as_bytes())?;
+        self.log(
+            NotificationLevel::Info,
+            &format!("Exported {} strings to {}", strings.len(), path),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_strings() {
+        let data = b"\x00\x01Hello, world!\x00ab\x01t\x00e\x00s\x00t\x00\xFFlast".to_vec();
+        let strings = App::find_strings(&data, 4, StringsEncoding::Ascii);
+        assert_eq!(
+            strings,
+            vec![
+                FoundString {
+                    offset: 2,
+                    text: "Hello, world!".into()
+                },
+                FoundString {
+                    offset: 0x1C,
+                    text: "last".into()
+                }
+            ]
+        );
+        let strings = App::find_strings(&data, 4, StringsEncoding::Utf16);
+        assert_eq!(
+            strings,
+            vec![FoundString {
+                offset: 0x13,
+                text: "test".into()
+            }]
+        );
+        assert_eq!(App::find_strings(&data, 14, StringsEncoding::Ascii), vec![]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("strings.txt").to_string_lossy().to_string();
+        let mut app = App::mockup(data);
+        app.request_popup_strings();
+        assert!(matches!(
+            app.popup,
+            Some(PopupState::Strings { ref strings, scroll: 0 }) if strings.len() == 2
+        ));
+        app.export_strings(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "00000002  Hello, world!\n0000001C  last\n"
+        );
+    }
+}
@@ -1,6 +1,14 @@
-use ratatui::text::{Line, Span, Text};
+use ratatui::{
+    style::Style,
+    text::{Line, Span, Text},
+};
 
-use super::{log::NotificationLevel, settings::color_settings::ColorSettings, App};
+use super::{
+    log::NotificationLevel,
+    settings::{color_settings::ColorSettings, control_characters::ControlCharacters},
+    text_encoding::TextEncoding,
+    App,
+};
 
 impl App {
     pub(super) fn bytes_to_styled_text(
@@ -9,6 +17,8 @@ impl App {
         block_size: usize,
         blocks_per_row: usize,
         selected_byte_offset: usize,
+        selection: Option<(usize, usize)>,
+        control_characters: ControlCharacters,
     ) -> Text<'static> {
         let mut ret = Text::default();
         ret.lines
@@ -17,17 +27,32 @@ impl App {
         let mut local_block = 0;
         let mut local_byte = 0;
         for (byte_index, b) in bytes.iter().enumerate() {
+            let is_in_selection = selection.is_some_and(|(selection_start, selection_end)| {
+                byte_index >= selection_start && byte_index < selection_end
+            });
             let style = if byte_index == selected_byte_offset {
                 color_settings.text_selected
+            } else if is_in_selection {
+                color_settings.hex_selection
             } else {
                 Self::get_style_for_byte(color_settings, *b)
             };
+            let space_style = if is_in_selection
+                && selection.is_some_and(|(_, selection_end)| byte_index + 1 != selection_end)
+            {
+                color_settings.hex_selection
+            } else {
+                Style::default()
+            };
             let mut next_line = false;
-            let char = Self::u8_to_char(*b);
-            let char_string = char.to_string();
+            let char_string = Self::u8_to_text(*b, control_characters);
+            let mut spacing_string = " ".to_string();
+            // hex escapes are two characters wide
+            if char_string.chars().count() > 1 {
+                spacing_string.pop();
+            }
             let span = Span::styled(char_string, style);
             current_line.spans.push(span);
-            let mut spacing_string = " ".to_string();
             local_byte += 1;
             if local_byte % block_size == 0 {
                 local_byte = 0;
@@ -40,7 +65,7 @@ impl App {
                 }
             }
 
-            let span = Span::raw(spacing_string);
+            let span = Span::styled(spacing_string, space_style);
             current_line.spans.push(span);
 
             if next_line {
@@ -59,16 +84,6 @@ impl App {
         self.patch_bytes(text.as_bytes(), false);
     }
 
-    fn found_text_here(&self, starting_from: usize, text: &str) -> bool {
-        for (i, byte) in text.bytes().enumerate() {
-            if self.data.len() <= starting_from + i || self.data.bytes()[starting_from + i] != byte
-            {
-                return false;
-            }
-        }
-        true
-    }
-
     pub(super) fn get_text_view(&self, start_row: usize, end_row: usize) -> Text<'static> {
         let start_byte = start_row * self.blocks_per_row * self.block_size;
         let end_byte = end_row * self.blocks_per_row * self.block_size;
@@ -78,40 +93,43 @@ impl App {
             .get_cursor_position()
             .global_byte_index
             .saturating_sub(start_byte);
-        Self::bytes_to_styled_text(
+        let mut text_view = Self::bytes_to_styled_text(
             &self.settings.color,
             bytes,
             self.block_size,
             self.blocks_per_row,
             selected_byte_offset,
-        )
+            self.get_selection().map(|(selection_start, selection_end)| {
+                (
+                    selection_start.saturating_sub(start_byte),
+                    selection_end.saturating_sub(start_byte),
+                )
+            }),
+            self.settings.app.control_characters,
+        );
+        self.style_annotations(&mut text_view, start_byte, 2, 0);
+        self.style_bom(&mut text_view, start_byte);
+        text_view
     }
 
-    pub(super) fn find_text(&mut self, text: &str) {
-        if text.is_empty() || self.data.is_empty() {
-            return;
-        }
-        let already_searched = self.text_last_searched_string == text;
-        if !already_searched {
-            self.text_last_searched_string = text.to_string();
-        }
-        let mut search_here = self.get_cursor_position().global_byte_index;
-        // find the next occurrence of the text
-        if already_searched && Self::found_text_here(self, search_here, text) {
-            search_here += text.len();
-        } else {
-            search_here = 0;
+    /// Jumps to the next occurrence of the text written in the encoding like [`App::find_bytes`],
+    /// unaligned occurrences of multi-byte encodings are found too.
+    /// Returns false if the text can't be written in the encoding.
+    pub(super) fn find_text(&mut self, text: &str, encoding: TextEncoding) -> bool {
+        if text.is_empty() {
+            return true;
         }
-        let max_search_index = self.data.len() + search_here;
-        while search_here < max_search_index {
-            let actual_search_here = search_here % self.data.len();
-            if Self::found_text_here(self, actual_search_here, text) {
-                self.jump_to(actual_search_here, false);
-                return;
+        let pattern = match encoding.encode(text) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                self.log(NotificationLevel::Error, &e);
+                return false;
             }
-            search_here += 1;
-        }
-        self.log(NotificationLevel::Warning, "Text not found");
+        };
+        self.text_last_searched_string = text.to_string();
+        self.text_last_searched_encoding = encoding;
+        self.find_bytes(&pattern, &format!("{} text", encoding));
+        true
     }
 
     pub(super) fn u8_to_char(input: u8) -> char {
@@ -127,6 +145,22 @@ impl App {
             _ => '.',
         }
     }
+
+    /// Returns how a byte is shown in the text view,
+    /// printable ASCII is always shown as it is.
+    pub(super) fn u8_to_text(input: u8, control_characters: ControlCharacters) -> String {
+        match (control_characters, input) {
+            (_, 0x20..=0x7E) => (input as char).to_string(),
+            (ControlCharacters::Symbols, _) => Self::u8_to_char(input).to_string(),
+            (ControlCharacters::Dot, _) => ".".to_string(),
+            (ControlCharacters::Pictures, 0x00..=0x1F) => {
+                char::from_u32(0x2400 + input as u32).unwrap().to_string()
+            }
+            (ControlCharacters::Pictures, 0x7F) => "␡".to_string(),
+            (ControlCharacters::Pictures, _) => ".".to_string(),
+            (ControlCharacters::Hex, _) => format!("{:02X}", input),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -146,6 +180,8 @@ mod tests {
             block_size,
             blocks_per_row,
             selected_byte_offset,
+            None,
+            ControlCharacters::Symbols,
         );
         assert_eq!(text.lines.len(), 1);
         let mut char_index = 0;
@@ -161,4 +197,47 @@ mod tests {
         }
         assert_eq!(char_index, bytes.len());
     }
+
+    #[test]
+    fn test_control_characters() {
+        assert_eq!(App::u8_to_text(b'A', ControlCharacters::Hex), "A");
+        assert_eq!(App::u8_to_text(0x1B, ControlCharacters::Symbols), "␛");
+        assert_eq!(App::u8_to_text(0x1B, ControlCharacters::Dot), ".");
+        assert_eq!(App::u8_to_text(0x00, ControlCharacters::Pictures), "␀");
+        assert_eq!(App::u8_to_text(0x1B, ControlCharacters::Pictures), "␛");
+        assert_eq!(App::u8_to_text(0x80, ControlCharacters::Pictures), ".");
+        assert_eq!(App::u8_to_text(0x1B, ControlCharacters::Hex), "1B");
+
+        // hex escapes must not change the width of the rows
+        let color_settings = ColorSettings::get_default_dark_theme();
+        let bytes = [0x1B, b'A', 0x00, 0xFF];
+        let widths = [ControlCharacters::Symbols, ControlCharacters::Hex].map(|mode| {
+            let text = App::bytes_to_styled_text(&color_settings, &bytes, 2, 2, 0, None, mode);
+            assert_eq!(text.lines[0].spans.len(), bytes.len() * 2);
+            text.lines[0].width()
+        });
+        assert_eq!(widths[0], widths[1]);
+    }
+
+    #[test]
+    fn test_find_text_encoding() {
+        let mut data = vec![0; 0x40];
+        // aligned and unaligned UTF-16 LE
+        data[0x10..0x14].copy_from_slice(&[b'h', 0, b'i', 0]);
+        data[0x21..0x25].copy_from_slice(&[b'h', 0, b'i', 0]);
+        data[0x30..0x32].copy_from_slice(&[b'h', 0xE9]);
+        let mut app = App::mockup(data);
+        app.resize_to_size(80, 24);
+        assert!(app.find_text("hi", TextEncoding::Utf16Le));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x10);
+        assert!(app.find_text("hi", TextEncoding::Utf16Le));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x21);
+        assert!(app.find_text("hé", TextEncoding::Latin1));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x30);
+        // the zero before the aligned LE match makes an unaligned BE match
+        assert!(app.find_text("hi", TextEncoding::Utf16Be));
+        assert_eq!(app.get_cursor_position().global_byte_index, 0x0F);
+        assert!(!app.find_text("hé", TextEncoding::Ascii));
+        assert_eq!(app.text_last_searched_encoding, TextEncoding::Utf16Be);
+    }
 }
@@ -0,0 +1,91 @@
+use std::fmt::Display;
+
+/// The encoding used to turn the text of the find text popup into the bytes to look for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextEncoding {
+    Ascii,
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl TextEncoding {
+    const ALL: [Self; 5] = [
+        Self::Ascii,
+        Self::Utf8,
+        Self::Utf16Le,
+        Self::Utf16Be,
+        Self::Latin1,
+    ];
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|e| e == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(&self) -> Self {
+        let index = Self::ALL.iter().position(|e| e == self).unwrap();
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Returns an error if a character can't be written in the encoding.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        let unsupported = |c: char| format!("The character '{}' is not in {}", c, self);
+        match self {
+            Self::Ascii => match text.chars().find(|c| !c.is_ascii()) {
+                Some(c) => Err(unsupported(c)),
+                None => Ok(text.as_bytes().to_vec()),
+            },
+            Self::Utf8 => Ok(text.as_bytes().to_vec()),
+            Self::Utf16Le => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Self::Utf16Be => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            Self::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).map_err(|_| unsupported(c)))
+                .collect(),
+        }
+    }
+}
+
+impl Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ascii => write!(f, "ASCII"),
+            Self::Utf8 => write!(f, "UTF-8"),
+            Self::Utf16Le => write!(f, "UTF-16 LE"),
+            Self::Utf16Be => write!(f, "UTF-16 BE"),
+            Self::Latin1 => write!(f, "Latin-1"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_text_encoding() {
+        assert_eq!(TextEncoding::Ascii.encode("Hi"), Ok(b"Hi".to_vec()));
+        assert!(TextEncoding::Ascii.encode("é").is_err());
+        assert_eq!(TextEncoding::Utf8.encode("é"), Ok(vec![0xC3, 0xA9]));
+        assert_eq!(
+            TextEncoding::Utf16Le.encode("Hé"),
+            Ok(vec![0x48, 0x00, 0xE9, 0x00])
+        );
+        assert_eq!(
+            TextEncoding::Utf16Be.encode("Hé"),
+            Ok(vec![0x00, 0x48, 0x00, 0xE9])
+        );
+        assert_eq!(TextEncoding::Latin1.encode("Hé"), Ok(vec![0x48, 0xE9]));
+        assert!(TextEncoding::Latin1.encode("€").is_err());
+
+        let mut encoding = TextEncoding::default();
+        for _ in 0..TextEncoding::ALL.len() {
+            encoding = encoding.next();
+        }
+        assert_eq!(encoding, TextEncoding::default());
+        assert_eq!(TextEncoding::Ascii.previous(), TextEncoding::Latin1);
+    }
+}
@@ -0,0 +1,143 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use object::Endianness;
+use ratatui::text::Line;
+
+use super::{
+    fields::FieldType, log::NotificationLevel, settings::color_settings::ColorSettings, App,
+};
+
+impl App {
+    /// Parses a value to write in the form `VALUE TYPE [le|be]`, such as `0x1234 u32 be`,
+    /// the endianness of the file is used if it is omitted.
+    /// For numeric types the value `now` stands for the current Unix time in seconds.
+    pub(super) fn parse_value(
+        text: &str,
+        default_endianness: Endianness,
+    ) -> Result<Vec<u8>, String> {
+        let mut tokens = text.split_whitespace().collect::<Vec<_>>();
+        let endianness = match tokens.last().map(|token| token.to_lowercase()).as_deref() {
+            Some("le") => {
+                tokens.pop();
+                Endianness::Little
+            }
+            Some("be") => {
+                tokens.pop();
+                Endianness::Big
+            }
+            _ => default_endianness,
+        };
+        let field_type = tokens
+            .pop()
+            .ok_or("Expected a value and a type")?
+            .parse::<FieldType>()?;
+        if tokens.is_empty() {
+            return Err("Expected a value before the type".into());
+        }
+        let mut value = tokens.join(" ");
+        if field_type.size().is_some() && value.eq_ignore_ascii_case("now") {
+            value = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| e.to_string())?
+                .as_secs()
+                .to_string();
+        }
+        let bytes = field_type.encode(&value, endianness)?;
+        if bytes.is_empty() {
+            return Err("There is nothing to write".into());
+        }
+        Ok(bytes)
+    }
+
+    /// The line of the write value popup that shows the bytes that will be written
+    /// or why the value is invalid.
+    pub(super) fn get_value_preview(
+        color_settings: &ColorSettings,
+        text: &str,
+        default_endianness: Endianness,
+    ) -> Line<'static> {
+        if text.trim().is_empty() {
+            return Line::styled("Bytes", color_settings.placeholder);
+        }
+        match Self::parse_value(text, default_endianness) {
+            Ok(bytes) => Line::styled(
+                bytes
+                    .iter()
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                color_settings.menu_text,
+            ),
+            Err(e) => Line::styled(e, color_settings.log_error),
+        }
+    }
+
+    /// Writes the value at the start of the selection, or at the cursor if nothing is selected.
+    /// Returns false if the value is invalid.
+    pub(super) fn write_value(&mut self, text: &str) -> bool {
+        let bytes = match Self::parse_value(text, self.header.endianness()) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.log(NotificationLevel::Warning, &e);
+                return false;
+            }
+        };
+        if let Some((start, _end)) = self.get_selection() {
+            self.jump_to(start, false);
+        }
+        self.patch_bytes(&bytes, false);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write_value() {
+        assert_eq!(
+            App::parse_value("0x1234 u16 be", Endianness::Little),
+            Ok(vec![0x12, 0x34])
+        );
+        assert_eq!(
+            App::parse_value("0x1234 u16", Endianness::Little),
+            Ok(vec![0x34, 0x12])
+        );
+        assert_eq!(
+            App::parse_value("hello world string", Endianness::Little),
+            Ok(b"hello world".to_vec())
+        );
+        assert!(App::parse_value("u16", Endianness::Little).is_err());
+        assert!(App::parse_value("1 u24", Endianness::Little).is_err());
+        assert_eq!(
+            App::parse_value("now string", Endianness::Little),
+            Ok(b"now".to_vec())
+        );
+
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let now = App::parse_value("NOW u64 be", Endianness::Little).unwrap();
+        let now = u64::from_be_bytes(now.try_into().unwrap());
+        let after = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        assert!(before <= now && now <= after);
+        assert!(App::parse_value("now u16", Endianness::Little).is_err());
+
+        let mut app = App::mockup(vec![0; 0x10]);
+        app.resize_to_size(80, 24);
+        app.jump_to(2, false);
+        assert!(app.write_value("-2 i32 BE"));
+        assert_eq!(
+            &app.data.bytes()[..8],
+            &[0, 0, 0xFF, 0xFF, 0xFF, 0xFE, 0, 0]
+        );
+        assert!(!app.write_value("300 u8"));
+        app.undo();
+        assert_eq!(app.data.bytes(), &[0; 0x10]);
+    }
+}
@@ -1,5 +1,9 @@
 use std::path::PathBuf;
 
+use object::Architecture;
+
+use crate::headers::arch_override::{parse_architecture, ArchOverride};
+
 #[derive(Debug, clap::Parser)]
 #[command(name = "hex-patch", about, version, author)]
 pub struct Args {
@@ -20,6 +24,22 @@ pub struct Args {
     pub config: Option<PathBuf>,
     #[arg(short, long, help = "The plugin directory to use")]
     pub plugins: Option<PathBuf>,
+    #[arg(
+        short,
+        long,
+        help = "Restore the last session, reopening the last file at the last cursor position",
+        conflicts_with = "ssh"
+    )]
+    pub restore: bool,
+    #[arg(
+        short,
+        long,
+        help = "Disassemble the file as this architecture instead of detecting it from the header",
+        value_parser = parse_architecture
+    )]
+    pub arch: Option<Architecture>,
+    #[arg(long, help = "Disassemble ARM code in thumb mode", requires = "arch")]
+    pub thumb: bool,
     #[arg(
         index = 1,
         help = "The starting path of the editor",
@@ -27,3 +47,11 @@ pub struct Args {
     )]
     pub path: String,
 }
+
+impl Args {
+    pub fn arch_override(&self) -> Result<Option<ArchOverride>, String> {
+        self.arch
+            .map(|architecture| ArchOverride::new(architecture, self.thumb))
+            .transpose()
+    }
+}
@@ -0,0 +1,106 @@
+use object::{Architecture, Endianness};
+
+use super::bitness::Bitness;
+
+/// The architecture chosen from the command line for files that should not be parsed,
+/// `thumb` is only valid for ARM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchOverride {
+    pub architecture: Architecture,
+    pub thumb: bool,
+}
+
+impl ArchOverride {
+    pub fn new(architecture: Architecture, thumb: bool) -> Result<Self, String> {
+        if thumb && architecture != Architecture::Arm {
+            return Err("Thumb mode is only available for the arm architecture".into());
+        }
+        Ok(Self {
+            architecture,
+            thumb,
+        })
+    }
+
+    pub fn bitness(&self) -> Bitness {
+        match self.architecture {
+            Architecture::Arm
+            | Architecture::I386
+            | Architecture::Mips
+            | Architecture::PowerPc
+            | Architecture::Riscv32 => Bitness::Bit32,
+            _ => Bitness::Bit64,
+        }
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        match self.architecture {
+            Architecture::S390x | Architecture::Sparc64 => Endianness::Big,
+            _ => Endianness::Little,
+        }
+    }
+}
+
+/// Parses the name of an architecture that can be disassembled.
+pub fn parse_architecture(name: &str) -> Result<Architecture, String> {
+    match name.to_lowercase().as_str() {
+        "x86" | "i386" => Ok(Architecture::I386),
+        "x86_64" | "x64" | "amd64" => Ok(Architecture::X86_64),
+        "arm" => Ok(Architecture::Arm),
+        "aarch64" | "arm64" => Ok(Architecture::Aarch64),
+        "mips" => Ok(Architecture::Mips),
+        "mips64" => Ok(Architecture::Mips64),
+        "ppc" | "powerpc" => Ok(Architecture::PowerPc),
+        "ppc64" | "powerpc64" => Ok(Architecture::PowerPc64),
+        "riscv32" => Ok(Architecture::Riscv32),
+        "riscv64" => Ok(Architecture::Riscv64),
+        "s390x" => Ok(Architecture::S390x),
+        "sparc64" => Ok(Architecture::Sparc64),
+        _ => Err(format!(
+            "Unknown architecture \"{}\", expected one of: x86, x86_64, arm, aarch64, mips, mips64, ppc, ppc64, riscv32, riscv64, s390x, sparc64",
+            name
+        )),
+    }
+}
+
+/// The name of an architecture as accepted by [`parse_architecture`].
+pub fn architecture_name(architecture: Architecture) -> Option<&'static str> {
+    match architecture {
+        Architecture::I386 => Some("x86"),
+        Architecture::X86_64 => Some("x86_64"),
+        Architecture::Arm => Some("arm"),
+        Architecture::Aarch64 => Some("aarch64"),
+        Architecture::Mips => Some("mips"),
+        Architecture::Mips64 => Some("mips64"),
+        Architecture::PowerPc => Some("ppc"),
+        Architecture::PowerPc64 => Some("ppc64"),
+        Architecture::Riscv32 => Some("riscv32"),
+        Architecture::Riscv64 => Some("riscv64"),
+        Architecture::S390x => Some("s390x"),
+        Architecture::Sparc64 => Some("sparc64"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_architecture() {
+        assert_eq!(parse_architecture("ARM64"), Ok(Architecture::Aarch64));
+        assert_eq!(parse_architecture("x86"), Ok(Architecture::I386));
+        assert!(parse_architecture("z80").is_err());
+        assert_eq!(
+            parse_architecture(architecture_name(Architecture::PowerPc64).unwrap()),
+            Ok(Architecture::PowerPc64)
+        );
+        assert!(ArchOverride::new(Architecture::Arm, true).is_ok());
+        assert!(ArchOverride::new(Architecture::X86_64, true).is_err());
+        assert_eq!(
+            ArchOverride::new(Architecture::Mips, false)
+                .unwrap()
+                .bitness(),
+            Bitness::Bit32
+        );
+    }
+}
@@ -70,13 +70,17 @@ impl GenericHeader {
 
             let sections = header
                 .sections()
-                .map(|section| Section {
-                    name: section.name().unwrap_or_default().to_string(),
-                    file_offset: section.file_range().unwrap_or_default().0,
-                    virtual_address: section.address(),
-                    size: section.file_range().unwrap_or_default().1,
+                .filter_map(|section| {
+                    // sections without data in the file (e.g. .bss) are skipped,
+                    // zero-length sections are kept so that they can be flagged as malformed
+                    let (file_offset, size) = section.file_range()?;
+                    Some(Section {
+                        name: section.name().unwrap_or_default().to_string(),
+                        file_offset,
+                        virtual_address: section.address(),
+                        size,
+                    })
                 })
-                .filter(|section| section.size > 0)
                 .collect();
 
             let mut symbols: Vec<(u64, String)> = header
@@ -126,13 +130,15 @@ impl GenericHeader {
 
                         if ty == 2 {
                             let data_header_size = 24;
-                            let path = String::from_utf8_lossy(
-                                &bytes[data_header_size + pointer_to_raw_data as usize
-                                    ..(pointer_to_raw_data + size_of_data) as usize],
-                            )
-                            .trim_end_matches('\0')
-                            .to_string();
-                            pdb_file_path = Some(path);
+                            let start = pointer_to_raw_data as usize + data_header_size;
+                            let end = pointer_to_raw_data as usize + size_of_data as usize;
+                            // malformed debug directories are ignored
+                            if let Some(path) = bytes.get(start..end) {
+                                let path = String::from_utf8_lossy(path)
+                                    .trim_end_matches('\0')
+                                    .to_string();
+                                pdb_file_path = Some(path);
+                            }
                             break;
                         }
                     }
@@ -11,13 +11,16 @@ use object::{Architecture, Endianness};
 use crate::app::files::filesystem::FileSystem;
 
 use super::{
-    bitness::Bitness, custom_header::CustomHeader, generic::GenericHeader, section::Section,
+    arch_override::ArchOverride, bitness::Bitness, custom_header::CustomHeader,
+    generic::GenericHeader, section::Section,
 };
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub enum Header {
     GenericHeader(GenericHeader),
     CustomHeader(CustomHeader),
+    /// A file without a parsed header that is disassembled with the architecture chosen by the user.
+    Raw(ArchOverride),
     #[default]
     None,
 }
@@ -41,14 +44,30 @@ impl Header {
                 Bitness::Bit32 => 32,
                 Bitness::Bit64 => 64,
             },
+            Header::Raw(raw) => match raw.bitness() {
+                Bitness::Bit32 => 32,
+                Bitness::Bit64 => 64,
+            },
             Header::None => 64,
         }
     }
 
+    /// The name of the format of the file, such as `Elf64`, `Custom` if it was parsed by a plugin,
+    /// `Raw` if the architecture was chosen by the user and `None` if no header was found.
+    pub fn file_type(&self) -> String {
+        match self {
+            Header::GenericHeader(header) => format!("{:?}", header.file_type()),
+            Header::CustomHeader(_) => "Custom".to_string(),
+            Header::Raw(_) => "Raw".to_string(),
+            Header::None => "None".to_string(),
+        }
+    }
+
     pub fn endianness(&self) -> Endianness {
         match self {
             Header::GenericHeader(header) => header.endianness,
             Header::CustomHeader(header) => header.endianness,
+            Header::Raw(raw) => raw.endianness(),
             Header::None => object::Endianness::Little,
         }
     }
@@ -57,7 +76,7 @@ impl Header {
         match self {
             Header::GenericHeader(header) => header.entry,
             Header::CustomHeader(header) => header.entry,
-            Header::None => 0,
+            Header::Raw(_) | Header::None => 0,
         }
     }
 
@@ -65,6 +84,7 @@ impl Header {
         match self {
             Header::GenericHeader(header) => header.architecture,
             Header::CustomHeader(header) => header.architecture,
+            Header::Raw(raw) => raw.architecture,
             Header::None => Architecture::Unknown,
         }
     }
@@ -73,7 +93,21 @@ impl Header {
         match self {
             Header::GenericHeader(header) => header.sections.clone(),
             Header::CustomHeader(header) => header.sections.clone(),
-            Header::None => Vec::new(),
+            Header::Raw(_) | Header::None => Vec::new(),
+        }
+    }
+
+    /// Returns the file offset and the size of the data appended after the last section.
+    pub fn get_overlay(&self, file_len: u64) -> Option<(u64, u64)> {
+        let end = self
+            .get_sections()
+            .iter()
+            .map(|section| section.file_offset.saturating_add(section.size))
+            .max()?;
+        if end < file_len {
+            Some((end, file_len - end))
+        } else {
+            None
         }
     }
 
@@ -95,7 +129,7 @@ impl Header {
                 }
                 None
             }
-            Header::None => None,
+            Header::Raw(_) | Header::None => None,
         }
     }
 
@@ -103,7 +137,7 @@ impl Header {
         match self {
             Header::GenericHeader(header) => Some(&header.symbols),
             Header::CustomHeader(header) => Some(&header.symbols),
-            Header::None => None,
+            Header::Raw(_) | Header::None => None,
         }
     }
 
@@ -111,7 +145,7 @@ impl Header {
         match self {
             Header::GenericHeader(header) => header.symbols_by_name.get(symbol).cloned(),
             Header::CustomHeader(header) => header.symbols_by_name.get(symbol).cloned(),
-            Header::None => None,
+            Header::Raw(_) | Header::None => None,
         }
     }
 
@@ -220,6 +254,11 @@ impl Header {
         let ret = match self {
             Header::GenericHeader(header) => Self::get_decoder_for_arch(&header.architecture),
             Header::CustomHeader(header) => Self::get_decoder_for_arch(&header.architecture),
+            Header::Raw(raw) if raw.thumb => Capstone::new()
+                .arm()
+                .mode(arch::arm::ArchMode::Thumb)
+                .build(),
+            Header::Raw(raw) => Self::get_decoder_for_arch(&raw.architecture),
             Header::None => Capstone::new()
                 .x86()
                 .mode(capstone::arch::x86::ArchMode::Mode64)
@@ -235,6 +274,8 @@ impl Header {
         match self {
             Header::GenericHeader(header) => Self::get_encoder_for_arch(&header.architecture),
             Header::CustomHeader(header) => Self::get_encoder_for_arch(&header.architecture),
+            Header::Raw(raw) if raw.thumb => Keystone::new(Arch::ARM, Mode::THUMB),
+            Header::Raw(raw) => Self::get_encoder_for_arch(&raw.architecture),
             Header::None => Keystone::new(Arch::X86, Mode::MODE_64),
         }
     }
@@ -242,6 +283,7 @@ impl Header {
 
 impl UserData for Header {
     fn add_fields<'lua, F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("file_type", |_, this| Ok(this.file_type()));
         fields.add_field_method_get("bitness", |_, this| Ok(this.bitness()));
         fields.add_field_method_get("entry_point", |_, this| Ok(this.entry_point()));
         fields.add_field_method_get("architecture", |_, this| {
@@ -281,11 +323,30 @@ mod test {
         } else {
             panic!("Failed to parse ELF header.");
         }
+        assert_eq!(header.file_type(), "Elf64");
         assert_eq!(header.architecture(), Architecture::X86_64);
         assert_eq!(header.bitness(), 64);
         assert_eq!(header.endianness(), Endianness::Little);
     }
 
+    #[test]
+    fn test_overlay() {
+        let data = include_bytes!("../../test/pe.bin");
+        let header = Header::parse_header(data, "./pe.bin", &FileSystem::new_local(".").unwrap());
+        let last_section_end = header
+            .get_sections()
+            .iter()
+            .map(|section| section.file_offset + section.size)
+            .max()
+            .unwrap();
+        assert_eq!(
+            header.get_overlay(last_section_end + 0x10),
+            Some((last_section_end, 0x10))
+        );
+        assert_eq!(header.get_overlay(last_section_end), None);
+        assert_eq!(Header::None.get_overlay(0x10), None);
+    }
+
     #[test]
     fn test_parse_pe() {
         let data = include_bytes!("../../test/pe.bin");
@@ -1,6 +1,7 @@
 mod header;
 pub use header::*;
 
+pub mod arch_override;
 pub mod bitness;
 pub mod custom_header;
 pub mod generic;
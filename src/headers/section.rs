@@ -10,6 +10,22 @@ pub struct Section {
     pub size: u64,
 }
 
+impl Section {
+    /// Returns a description of what is wrong with the section, if anything,
+    /// `previous_end` is the end of the sections that come before this one.
+    pub fn malformation(&self, previous_end: u64, file_len: u64) -> Option<&'static str> {
+        if self.size == 0 {
+            Some("it is empty")
+        } else if self.file_offset.saturating_add(self.size) > file_len {
+            Some("it extends past the end of the file")
+        } else if self.file_offset < previous_end {
+            Some("it overlaps with a previous section")
+        } else {
+            None
+        }
+    }
+}
+
 impl Display for Section {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -17,7 +33,7 @@ impl Display for Section {
             "{}: [{:X} - {:X})",
             self.name,
             self.file_offset,
-            self.file_offset + self.size
+            self.file_offset.saturating_add(self.size)
         )
     }
 }
@@ -30,3 +46,22 @@ impl UserData for Section {
         fields.add_field_method_get("size", |_, this| Ok(this.size));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_section_malformation() {
+        let section = |file_offset, size| Section {
+            name: ".text".to_string(),
+            virtual_address: 0,
+            file_offset,
+            size,
+        };
+        assert_eq!(section(0x10, 0x10).malformation(0x10, 0x100), None);
+        assert!(section(0x10, 0).malformation(0, 0x100).is_some());
+        assert!(section(0xF0, 0x20).malformation(0, 0x100).is_some());
+        assert!(section(0x10, 0x10).malformation(0x18, 0x100).is_some());
+    }
+}
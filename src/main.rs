@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use clap::Parser;
+use clap::{error::ErrorKind, CommandFactory, Parser};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -11,6 +11,11 @@ use ratatui::backend::CrosstermBackend;
 
 fn main() {
     let args = args::Args::parse();
+    if let Err(e) = args.arch_override() {
+        args::Args::command()
+            .error(ErrorKind::ArgumentConflict, e)
+            .exit();
+    }
     let theme = termbg::theme(Duration::from_secs(2));
 
     enable_raw_mode().expect("Failed to enable raw mode");